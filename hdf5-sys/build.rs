@@ -160,6 +160,7 @@ pub struct Header {
     pub have_direct: bool,
     pub have_parallel: bool,
     pub have_threadsafe: bool,
+    pub have_ros3_vfd: bool,
     pub version: Version,
 }
 
@@ -185,6 +186,8 @@ impl Header {
                 hdr.have_parallel = value > 0;
             } else if name == "H5_HAVE_THREADSAFE" {
                 hdr.have_threadsafe = value > 0;
+            } else if name == "H5_HAVE_ROS3_VFD" {
+                hdr.have_ros3_vfd = value > 0;
             }
         }
 
@@ -581,7 +584,7 @@ impl Config {
         let version = self.header.version;
         assert!(version >= Version::new(1, 8, 4), "required HDF5 version: >=1.8.4");
         let mut vs: Vec<_> = (5..=21).map(|v| Version::new(1, 8, v)).collect(); // 1.8.[5-21]
-        vs.extend((0..=5).map(|v| Version::new(1, 10, v))); // 1.10.[0-5]
+        vs.extend((0..=7).map(|v| Version::new(1, 10, v))); // 1.10.[0-7]
         for v in vs.into_iter().filter(|&v| version >= v) {
             println!("cargo:rustc-cfg=hdf5_{}_{}_{}", v.major, v.minor, v.micro);
         }
@@ -597,6 +600,9 @@ impl Config {
         if self.header.have_threadsafe {
             println!("cargo:rustc-cfg=h5_have_threadsafe");
         }
+        if self.header.have_ros3_vfd {
+            println!("cargo:rustc-cfg=h5_have_ros3_vfd");
+        }
     }
 }
 
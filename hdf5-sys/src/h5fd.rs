@@ -361,6 +361,33 @@ extern "C" {
     pub fn H5FD_direct_init() -> hid_t;
 }
 
+#[cfg(feature = "ros3")]
+mod ros3 {
+    use crate::internal_prelude::*;
+
+    pub const H5FD_CURR_ROS3_FAPL_T_VERSION: i32 = 1;
+    pub const H5FD_ROS3_MAX_REGION_LEN: usize = 32;
+    pub const H5FD_ROS3_MAX_SECRET_ID_LEN: usize = 128;
+    pub const H5FD_ROS3_MAX_SECRET_KEY_LEN: usize = 128;
+
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct H5FD_ros3_fapl_t {
+        pub version: i32,
+        pub authenticate: hbool_t,
+        pub aws_region: [c_char; H5FD_ROS3_MAX_REGION_LEN + 1],
+        pub secret_id: [c_char; H5FD_ROS3_MAX_SECRET_ID_LEN + 1],
+        pub secret_key: [c_char; H5FD_ROS3_MAX_SECRET_KEY_LEN + 1],
+    }
+
+    extern "C" {
+        pub fn H5FD_ros3_init() -> hid_t;
+    }
+}
+
+#[cfg(feature = "ros3")]
+pub use self::ros3::*;
+
 #[cfg(hdf5_1_10_0)]
 extern "C" {
     pub fn H5FDlock(file: *mut H5FD_t, rw: hbool_t) -> herr_t;
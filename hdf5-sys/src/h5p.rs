@@ -11,6 +11,8 @@ use crate::h5z::{H5Z_EDC_t, H5Z_SO_scale_type_t, H5Z_filter_func_t, H5Z_filter_t
 
 #[cfg(hdf5_1_8_9)]
 use crate::h5fd::H5FD_file_image_callbacks_t;
+#[cfg(feature = "ros3")]
+use crate::h5fd::H5FD_ros3_fapl_t;
 #[cfg(hdf5_1_8_9)]
 use crate::h5o::H5O_mcdt_search_cb_t;
 #[cfg(hdf5_1_10_1)]
@@ -494,6 +496,12 @@ extern "C" {
         fapl_id: hid_t, comm: *mut mpi_sys::MPI_Comm, info: *mut mpi_sys::MPI_Info,
     ) -> herr_t;
 
+    // ros3
+    #[cfg(feature = "ros3")]
+    pub fn H5Pset_fapl_ros3(fapl_id: hid_t, fa: *mut H5FD_ros3_fapl_t) -> herr_t;
+    #[cfg(feature = "ros3")]
+    pub fn H5Pget_fapl_ros3(fapl_id: hid_t, fa_out: *mut H5FD_ros3_fapl_t) -> herr_t;
+
     // direct
     #[cfg(h5_have_direct)]
     pub fn H5Pset_fapl_direct(
@@ -720,3 +728,13 @@ extern "C" {
     pub fn H5Pget_dset_no_attrs_hint(dcpl_id: hid_t, minimize: *mut hbool_t) -> herr_t;
     pub fn H5Pset_dset_no_attrs_hint(dcpl_id: hid_t, minimize: hbool_t) -> herr_t;
 }
+
+#[cfg(hdf5_1_10_7)]
+extern "C" {
+    pub fn H5Pset_file_locking(
+        fapl_id: hid_t, use_file_locking: hbool_t, ignore_when_disabled: hbool_t,
+    ) -> herr_t;
+    pub fn H5Pget_file_locking(
+        fapl_id: hid_t, use_file_locking: *mut hbool_t, ignore_when_disabled: *mut hbool_t,
+    ) -> herr_t;
+}
@@ -13,6 +13,9 @@ macro_rules! extern_static {
 #[cfg(all(feature = "mpio", not(h5_have_parallel)))]
 compile_error!("Enabling \"mpio\" feature requires HDF5 library built with MPI support");
 
+#[cfg(all(feature = "ros3", not(h5_have_ros3_vfd)))]
+compile_error!("Enabling \"ros3\" feature requires HDF5 library built with ROS3 support");
+
 pub mod h5;
 pub mod h5a;
 pub mod h5ac;
@@ -83,9 +86,13 @@ pub fn emit_cfg_flags() {
     check_and_emit!(hdf5_1_10_2);
     check_and_emit!(hdf5_1_10_3);
     check_and_emit!(hdf5_1_10_4);
+    check_and_emit!(hdf5_1_10_5);
+    check_and_emit!(hdf5_1_10_6);
+    check_and_emit!(hdf5_1_10_7);
     check_and_emit!(h5_have_direct);
     check_and_emit!(h5_have_parallel);
     check_and_emit!(h5_have_threadsafe);
+    check_and_emit!(h5_have_ros3_vfd);
 }
 
 #[cfg(test)]
@@ -59,7 +59,7 @@ impl_array!(
 );
 
 #[repr(C)]
-pub struct VarLenArray<T: Copy> {
+pub struct VarLenArray<T> {
     len: usize,
     ptr: *const T,
     tag: PhantomData<T>,
@@ -76,12 +76,24 @@ impl<T: Copy> VarLenArray<T> {
         };
         VarLenArray { len, ptr: ptr as *const _, tag: PhantomData }
     }
+}
 
-    #[inline]
+impl<T: Clone> VarLenArray<T> {
     pub fn from_slice(arr: &[T]) -> VarLenArray<T> {
-        unsafe { VarLenArray::from_parts(arr.as_ptr(), arr.len()) }
+        if arr.is_empty() {
+            return VarLenArray::default();
+        }
+        unsafe {
+            let dst = libc::malloc(arr.len() * mem::size_of::<T>()) as *mut T;
+            for (i, item) in arr.iter().enumerate() {
+                ptr::write(dst.add(i), item.clone());
+            }
+            VarLenArray { len: arr.len(), ptr: dst as *const _, tag: PhantomData }
+        }
     }
+}
 
+impl<T> VarLenArray<T> {
     #[inline]
     pub fn as_ptr(&self) -> *const T {
         self.ptr
@@ -103,10 +115,12 @@ impl<T: Copy> VarLenArray<T> {
     }
 }
 
-impl<T: Copy> Drop for VarLenArray<T> {
+impl<T> Drop for VarLenArray<T> {
     fn drop(&mut self) {
         if !self.ptr.is_null() {
             unsafe {
+                // Elements may own variable-length data themselves (e.g. nested arrays).
+                ptr::drop_in_place(slice::from_raw_parts_mut(self.ptr as *mut T, self.len));
                 libc::free(self.ptr as *mut _);
             }
             self.ptr = ptr::null();
@@ -117,14 +131,14 @@ impl<T: Copy> Drop for VarLenArray<T> {
     }
 }
 
-impl<T: Copy> Clone for VarLenArray<T> {
+impl<T: Clone> Clone for VarLenArray<T> {
     #[inline]
     fn clone(&self) -> VarLenArray<T> {
         VarLenArray::from_slice(&*self)
     }
 }
 
-impl<T: Copy> Deref for VarLenArray<T> {
+impl<T> Deref for VarLenArray<T> {
     type Target = [T];
 
     #[inline]
@@ -137,14 +151,14 @@ impl<T: Copy> Deref for VarLenArray<T> {
     }
 }
 
-impl<'a, T: Copy> From<&'a [T]> for VarLenArray<T> {
+impl<'a, T: Clone> From<&'a [T]> for VarLenArray<T> {
     #[inline]
     fn from(arr: &[T]) -> VarLenArray<T> {
         VarLenArray::from_slice(arr)
     }
 }
 
-impl<T: Copy> Into<Vec<T>> for VarLenArray<T> {
+impl<T: Clone> Into<Vec<T>> for VarLenArray<T> {
     #[inline]
     fn into(self) -> Vec<T> {
         self.iter().cloned().collect()
@@ -158,37 +172,37 @@ impl<T: Copy, A: Array<Item = T>> From<A> for VarLenArray<T> {
     }
 }
 
-impl<T: Copy> Default for VarLenArray<T> {
+impl<T> Default for VarLenArray<T> {
     #[inline]
     fn default() -> VarLenArray<T> {
-        unsafe { VarLenArray::from_parts(ptr::null(), 0) }
+        VarLenArray { len: 0, ptr: ptr::null(), tag: PhantomData }
     }
 }
 
-impl<T: Copy + PartialEq> PartialEq for VarLenArray<T> {
+impl<T: PartialEq> PartialEq for VarLenArray<T> {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
         self.as_slice() == other.as_slice()
     }
 }
 
-impl<T: Copy + Eq> Eq for VarLenArray<T> {}
+impl<T: Eq> Eq for VarLenArray<T> {}
 
-impl<T: Copy + PartialEq> PartialEq<[T]> for VarLenArray<T> {
+impl<T: PartialEq> PartialEq<[T]> for VarLenArray<T> {
     #[inline]
     fn eq(&self, other: &[T]) -> bool {
         self.as_slice() == other
     }
 }
 
-impl<T: Copy + PartialEq, A: Array<Item = T>> PartialEq<A> for VarLenArray<T> {
+impl<T: PartialEq, A: Array<Item = T>> PartialEq<A> for VarLenArray<T> {
     #[inline]
     fn eq(&self, other: &A) -> bool {
         self.as_slice() == unsafe { slice::from_raw_parts(other.as_ptr(), A::capacity()) }
     }
 }
 
-impl<T: Copy + fmt::Debug> fmt::Debug for VarLenArray<T> {
+impl<T: fmt::Debug> fmt::Debug for VarLenArray<T> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         self.as_slice().fmt(f)
@@ -243,4 +257,21 @@ pub mod tests {
         let v: Vec<_> = a.iter().cloned().collect();
         assert_eq!(v, vec![1, 2, 3]);
     }
+
+    #[test]
+    pub fn test_vla_nested() {
+        type V = VarLenArray<VarLenArray<f64>>;
+        let inner = [VarLenArray::from([1., 2.]), VarLenArray::default(), VarLenArray::from([3.])];
+        let a = V::from_slice(&inner);
+        assert_eq!(a.len(), 3);
+        assert_eq!(a.iter().map(|v| v.len()).collect::<Vec<_>>(), vec![2, 0, 1]);
+        let c = a.clone();
+        assert_ne!(a[0].as_ptr(), c[0].as_ptr());
+        assert_eq!(a, c);
+        drop(a);
+        assert_eq!(&*c[0], &[1., 2.]);
+        assert_eq!(format!("{:?}", c), "[[1.0, 2.0], [], [3.0]]");
+        let v: Vec<_> = c.into();
+        assert_eq!(v, inner.to_vec());
+    }
 }
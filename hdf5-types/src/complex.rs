@@ -0,0 +1,58 @@
+use std::mem;
+
+use num_complex::Complex;
+
+use crate::h5type::{CompoundField, CompoundType, H5Type, TypeDescriptor};
+
+macro_rules! impl_complex {
+    ($ty:ty) => {
+        /// Complex numbers are stored as a compound type with fields named `r` and `i`
+        /// (this is the convention used by h5py and is thus compatible with NumPy).
+        unsafe impl H5Type for Complex<$ty> {
+            #[inline]
+            fn type_descriptor() -> TypeDescriptor {
+                let (ty, size) = (<$ty as H5Type>::type_descriptor(), mem::size_of::<$ty>());
+                TypeDescriptor::Compound(CompoundType {
+                    fields: vec![
+                        CompoundField::new("r", ty.clone(), 0, 0),
+                        CompoundField::new("i", ty, size, 1),
+                    ],
+                    size: 2 * size,
+                })
+            }
+        }
+    };
+}
+
+impl_complex!(f32);
+impl_complex!(f64);
+
+#[cfg(test)]
+pub mod tests {
+    use std::mem;
+
+    use num_complex::Complex;
+
+    use crate::h5type::{CompoundField, CompoundType, FloatSize, H5Type, TypeDescriptor as TD};
+
+    #[test]
+    pub fn test_complex() {
+        for (desc, size, float) in vec![
+            (Complex::<f32>::type_descriptor(), 4, FloatSize::U4),
+            (Complex::<f64>::type_descriptor(), 8, FloatSize::U8),
+        ] {
+            assert_eq!(
+                desc,
+                TD::Compound(CompoundType {
+                    fields: vec![
+                        CompoundField::new("r", TD::Float(float), 0, 0),
+                        CompoundField::new("i", TD::Float(float), size, 1),
+                    ],
+                    size: 2 * size,
+                })
+            );
+        }
+        assert_eq!(Complex::<f32>::type_descriptor().size(), mem::size_of::<Complex<f32>>());
+        assert_eq!(Complex::<f64>::type_descriptor().size(), mem::size_of::<Complex<f64>>());
+    }
+}
@@ -0,0 +1,136 @@
+use std::convert::TryFrom;
+use std::error::Error as StdError;
+use std::fmt;
+
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+
+use crate::h5type::{H5Type, IntSize, TypeDescriptor};
+
+const NANOS_PER_SEC: i64 = 1_000_000_000;
+
+fn epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(1970, 1, 1).and_then(|d| d.and_hms_opt(0, 0, 0)).unwrap()
+}
+
+/// Error returned when a datetime cannot be represented as a `Timestamp`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimestampError(NaiveDateTime);
+
+impl StdError for TimestampError {
+    fn description(&self) -> &str {
+        "datetime out of range for a nanosecond timestamp"
+    }
+}
+
+impl fmt::Display for TimestampError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "timestamp error: datetime out of range: {}", self.0)
+    }
+}
+
+/// Date and time stored as a 64-bit signed integer: the number of nanoseconds since
+/// the Unix epoch (1970-01-01T00:00:00, timezone-naive).
+///
+/// This allows representing datetimes between years 1677 and 2262; conversion of
+/// datetimes outside of that range fails with an error instead of overflowing.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(i64);
+
+impl Timestamp {
+    /// Epoch and unit of stored timestamps, e.g. for use in a `units` attribute.
+    pub const UNITS: &'static str = "nanoseconds since 1970-01-01T00:00:00";
+
+    /// Creates a timestamp from the number of nanoseconds since the epoch.
+    pub fn from_nanos(nanos: i64) -> Self {
+        Self(nanos)
+    }
+
+    /// Returns the number of nanoseconds since the epoch.
+    pub fn as_nanos(self) -> i64 {
+        self.0
+    }
+
+    /// Converts a datetime into a timestamp, failing if it's out of range.
+    pub fn from_datetime(datetime: &NaiveDateTime) -> Result<Self, TimestampError> {
+        datetime
+            .signed_duration_since(epoch())
+            .num_nanoseconds()
+            .map(Self)
+            .ok_or_else(|| TimestampError(*datetime))
+    }
+
+    /// Converts the timestamp into a datetime (this never fails).
+    pub fn to_datetime(self) -> NaiveDateTime {
+        let (mut secs, mut nanos) = (self.0 / NANOS_PER_SEC, self.0 % NANOS_PER_SEC);
+        if nanos < 0 {
+            secs -= 1;
+            nanos += NANOS_PER_SEC;
+        }
+        epoch() + Duration::seconds(secs) + Duration::nanoseconds(nanos)
+    }
+}
+
+impl TryFrom<NaiveDateTime> for Timestamp {
+    type Error = TimestampError;
+
+    fn try_from(datetime: NaiveDateTime) -> Result<Self, TimestampError> {
+        Self::from_datetime(&datetime)
+    }
+}
+
+impl From<Timestamp> for NaiveDateTime {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.to_datetime()
+    }
+}
+
+unsafe impl H5Type for Timestamp {
+    #[inline]
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::Integer(IntSize::U8)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::convert::TryFrom;
+    use std::mem;
+
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    use super::Timestamp;
+    use crate::h5type::{H5Type, IntSize, TypeDescriptor as TD};
+
+    fn dt(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32, ns: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap().and_hms_nano_opt(h, min, s, ns).unwrap()
+    }
+
+    #[test]
+    pub fn test_timestamp() {
+        assert_eq!(Timestamp::type_descriptor(), TD::Integer(IntSize::U8));
+        assert_eq!(mem::size_of::<Timestamp>(), 8);
+        assert_eq!(Timestamp::default().to_datetime(), dt(1970, 1, 1, 0, 0, 0, 0));
+
+        for &(datetime, nanos) in &[
+            (dt(1970, 1, 1, 0, 0, 1, 5), 1_000_000_005),
+            (dt(1969, 12, 31, 23, 59, 59, 999_999_999), -1),
+            (dt(1969, 12, 31, 23, 59, 58, 1), -1_999_999_999),
+            (dt(2019, 6, 1, 12, 30, 0, 123_456_789), 1_559_392_200_123_456_789),
+        ] {
+            let ts = Timestamp::from_datetime(&datetime).unwrap();
+            assert_eq!(ts.as_nanos(), nanos);
+            assert_eq!(ts, Timestamp::from_nanos(nanos));
+            assert_eq!(NaiveDateTime::from(ts), datetime);
+        }
+
+        Timestamp::from_nanos(i64::max_value()).to_datetime();
+        Timestamp::from_nanos(i64::min_value()).to_datetime();
+        assert!(Timestamp::try_from(dt(2262, 4, 11, 23, 47, 16, 854_775_807)).is_ok());
+        assert!(Timestamp::try_from(dt(2262, 4, 11, 23, 47, 16, 854_775_808)).is_err());
+        assert!(Timestamp::try_from(dt(1677, 9, 21, 0, 12, 43, 145_224_192)).is_ok());
+        assert!(Timestamp::try_from(dt(1677, 9, 21, 0, 12, 43, 145_224_191)).is_err());
+        let err = Timestamp::try_from(dt(3000, 1, 1, 0, 0, 0, 0)).unwrap_err();
+        assert_eq!(err.to_string(), "timestamp error: datetime out of range: 3000-01-01 00:00:00");
+    }
+}
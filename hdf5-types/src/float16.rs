@@ -0,0 +1,27 @@
+use half::f16;
+
+use crate::h5type::{FloatSize, H5Type, TypeDescriptor};
+
+/// Half-precision floats are stored as 16-bit IEEE 754 floats (compatible with NumPy's
+/// `float16` as stored by h5py).
+unsafe impl H5Type for f16 {
+    #[inline]
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::Float(FloatSize::U2)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::mem;
+
+    use half::f16;
+
+    use crate::h5type::{FloatSize, H5Type, TypeDescriptor as TD};
+
+    #[test]
+    pub fn test_f16() {
+        assert_eq!(f16::type_descriptor(), TD::Float(FloatSize::U2));
+        assert_eq!(f16::type_descriptor().size(), mem::size_of::<f16>());
+    }
+}
@@ -3,6 +3,7 @@ use std::os::raw::c_void;
 use std::ptr;
 
 use crate::array::{Array, VarLenArray};
+use crate::reference::REGION_REFERENCE_SIZE;
 use crate::string::{FixedAscii, FixedUnicode, VarLenAscii, VarLenUnicode};
 
 #[allow(non_camel_case_types)]
@@ -38,13 +39,16 @@ impl IntSize {
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum FloatSize {
+    U2 = 2,
     U4 = 4,
     U8 = 8,
 }
 
 impl FloatSize {
     pub fn from_int(size: usize) -> Option<FloatSize> {
-        if size == 4 {
+        if size == 2 {
+            Some(FloatSize::U2)
+        } else if size == 4 {
             Some(FloatSize::U4)
         } else if size == 8 {
             Some(FloatSize::U8)
@@ -138,6 +142,12 @@ impl CompoundType {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpaqueType {
+    pub size: usize,
+    pub tag: String,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum TypeDescriptor {
     Integer(IntSize),
@@ -152,6 +162,9 @@ pub enum TypeDescriptor {
     VarLenArray(Box<TypeDescriptor>),
     VarLenAscii,
     VarLenUnicode,
+    RegionReference,
+    Opaque(OpaqueType),
+    Bitfield(IntSize),
 }
 
 impl TypeDescriptor {
@@ -159,7 +172,7 @@ impl TypeDescriptor {
         use self::TypeDescriptor::*;
 
         match *self {
-            Integer(size) | Unsigned(size) => size as _,
+            Integer(size) | Unsigned(size) | Bitfield(size) => size as _,
             Float(size) => size as _,
             Boolean => 1,
             Enum(ref enum_type) => enum_type.size as _,
@@ -168,6 +181,8 @@ impl TypeDescriptor {
             FixedAscii(len) | FixedUnicode(len) => len,
             VarLenArray(_) => mem::size_of::<hvl_t>(),
             VarLenAscii | VarLenUnicode => mem::size_of::<*const u8>(),
+            RegionReference => REGION_REFERENCE_SIZE,
+            Opaque(ref opaque) => opaque.size,
         }
     }
 
@@ -179,7 +194,7 @@ impl TypeDescriptor {
                 compound.fields.iter().map(|f| f.ty.c_alignment()).max().unwrap_or(1)
             }
             FixedArray(ref ty, _) => ty.c_alignment(),
-            FixedAscii(_) | FixedUnicode(_) => 1,
+            FixedAscii(_) | FixedUnicode(_) | RegionReference | Opaque(_) => 1,
             VarLenArray(_) => mem::size_of::<usize>(),
             _ => self.size(),
         }
@@ -313,7 +328,7 @@ unsafe impl<T: Array<Item = I>, I: H5Type> H5Type for T {
     }
 }
 
-unsafe impl<T: Copy + H5Type> H5Type for VarLenArray<T> {
+unsafe impl<T: H5Type> H5Type for VarLenArray<T> {
     #[inline]
     fn type_descriptor() -> TypeDescriptor {
         TypeDescriptor::VarLenArray(Box::new(<T as H5Type>::type_descriptor()))
@@ -407,6 +422,12 @@ pub mod tests {
         type S = VarLenArray<u16>;
         assert_eq!(S::type_descriptor(), TD::VarLenArray(Box::new(u16::type_descriptor())));
         assert_eq!(mem::size_of::<VarLenArray<u8>>(), mem::size_of::<hvl_t>());
+        type N = VarLenArray<VarLenArray<f64>>;
+        assert_eq!(
+            N::type_descriptor(),
+            TD::VarLenArray(Box::new(TD::VarLenArray(Box::new(TD::Float(FloatSize::U8)))))
+        );
+        assert_eq!(N::type_descriptor().size(), mem::size_of::<hvl_t>());
     }
 
     #[test]
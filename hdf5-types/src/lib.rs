@@ -6,11 +6,24 @@
 extern crate quickcheck;
 
 mod array;
+#[cfg(feature = "complex")]
+mod complex;
+#[cfg(feature = "chrono")]
+mod datetime;
+#[cfg(feature = "half")]
+mod float16;
 mod h5type;
+mod opaque;
+mod reference;
 mod string;
 
 pub use self::array::{Array, VarLenArray};
+#[cfg(feature = "chrono")]
+pub use self::datetime::{Timestamp, TimestampError};
 pub use self::h5type::{
-    CompoundField, CompoundType, EnumMember, EnumType, FloatSize, H5Type, IntSize, TypeDescriptor,
+    CompoundField, CompoundType, EnumMember, EnumType, FloatSize, H5Type, IntSize, OpaqueType,
+    TypeDescriptor,
 };
+pub use self::opaque::{OpaqueBytes, OpaqueTag};
+pub use self::reference::RegionReference;
 pub use self::string::{FixedAscii, FixedUnicode, StringError, VarLenAscii, VarLenUnicode};
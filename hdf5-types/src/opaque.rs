@@ -0,0 +1,150 @@
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::mem;
+use std::ptr;
+use std::slice;
+
+use crate::array::Array;
+use crate::h5type::{H5Type, OpaqueType, TypeDescriptor};
+
+/// Tag describing the contents of opaque data (e.g. a serialization format).
+pub trait OpaqueTag: 'static {
+    /// The tag string; must be shorter than 256 bytes.
+    const TAG: &'static str;
+}
+
+/// Fixed-size blob of raw bytes stored as a tagged opaque HDF5 datatype.
+///
+/// The tag is provided at the type level via the `OpaqueTag` trait, so that data can only
+/// be read back into blobs with the same tag.
+#[repr(transparent)]
+pub struct OpaqueBytes<A: Array<Item = u8>, T: OpaqueTag> {
+    buf: A,
+    tag: PhantomData<T>,
+}
+
+impl<A: Array<Item = u8>, T: OpaqueTag> OpaqueBytes<A, T> {
+    /// Creates a zero-filled blob.
+    #[inline]
+    pub fn new() -> Self {
+        unsafe { Self { buf: mem::zeroed(), tag: PhantomData } }
+    }
+
+    /// Creates a blob from a byte slice, zero-padding it if it's shorter than the capacity.
+    ///
+    /// Returns `None` if the slice doesn't fit into the blob.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() > A::capacity() {
+            return None;
+        }
+        let mut blob = Self::new();
+        unsafe { ptr::copy_nonoverlapping(bytes.as_ptr(), blob.buf.as_mut_ptr(), bytes.len()) };
+        Some(blob)
+    }
+
+    #[inline]
+    pub fn capacity() -> usize {
+        A::capacity()
+    }
+
+    /// Returns the tag of the opaque datatype.
+    #[inline]
+    pub fn tag() -> &'static str {
+        T::TAG
+    }
+
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.buf.as_ptr(), A::capacity()) }
+    }
+
+    #[inline]
+    pub fn as_mut_bytes(&mut self) -> &mut [u8] {
+        unsafe { slice::from_raw_parts_mut(self.buf.as_mut_ptr(), A::capacity()) }
+    }
+}
+
+impl<A: Array<Item = u8>, T: OpaqueTag> Clone for OpaqueBytes<A, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        let mut blob = Self::new();
+        blob.as_mut_bytes().copy_from_slice(self.as_bytes());
+        blob
+    }
+}
+
+impl<A: Array<Item = u8>, T: OpaqueTag> Default for OpaqueBytes<A, T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Array<Item = u8>, T: OpaqueTag> PartialEq for OpaqueBytes<A, T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<A: Array<Item = u8>, T: OpaqueTag> Eq for OpaqueBytes<A, T> {}
+
+impl<A: Array<Item = u8>, T: OpaqueTag> AsRef<[u8]> for OpaqueBytes<A, T> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<A: Array<Item = u8>, T: OpaqueTag> Debug for OpaqueBytes<A, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "OpaqueBytes({:?}, ", T::TAG)?;
+        for b in self.as_bytes() {
+            write!(f, "{:02x}", b)?;
+        }
+        write!(f, ")")
+    }
+}
+
+unsafe impl<A: Array<Item = u8>, T: OpaqueTag> H5Type for OpaqueBytes<A, T> {
+    #[inline]
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::Opaque(OpaqueType { size: A::capacity(), tag: T::TAG.to_owned() })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::mem;
+
+    use super::{OpaqueBytes, OpaqueTag};
+    use crate::h5type::{H5Type, OpaqueType, TypeDescriptor as TD};
+
+    struct Protobuf;
+
+    impl OpaqueTag for Protobuf {
+        const TAG: &'static str = "protobuf";
+    }
+
+    type Blob = OpaqueBytes<[u8; 4], Protobuf>;
+
+    #[test]
+    pub fn test_opaque_bytes() {
+        assert_eq!(
+            Blob::type_descriptor(),
+            TD::Opaque(OpaqueType { size: 4, tag: "protobuf".into() })
+        );
+        assert_eq!(Blob::type_descriptor().size(), 4);
+        assert_eq!(mem::size_of::<Blob>(), 4);
+        assert_eq!(Blob::capacity(), 4);
+        assert_eq!(Blob::tag(), "protobuf");
+
+        assert_eq!(Blob::default().as_bytes(), &[0, 0, 0, 0]);
+        let blob = Blob::from_bytes(&[1, 2, 255]).unwrap();
+        assert_eq!(blob.as_bytes(), &[1, 2, 255, 0]);
+        assert_eq!(blob.clone(), blob);
+        assert_ne!(Blob::new(), blob);
+        assert!(Blob::from_bytes(&[0; 5]).is_none());
+        assert_eq!(format!("{:?}", blob), "OpaqueBytes(\"protobuf\", 0102ff00)");
+    }
+}
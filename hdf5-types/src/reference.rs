@@ -0,0 +1,78 @@
+use std::fmt::{self, Debug};
+
+use crate::h5type::{H5Type, TypeDescriptor};
+
+/// Size in bytes of a stored dataset region reference (`hdset_reg_ref_t`).
+pub const REGION_REFERENCE_SIZE: usize = 12;
+
+/// Dataset region reference: points to a selection within a dataset.
+///
+/// The reference itself is opaque; it has to be dereferenced via the file it
+/// belongs to in order to recover the target dataset and the selection.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RegionReference([u8; REGION_REFERENCE_SIZE]);
+
+impl RegionReference {
+    /// Creates a region reference from its raw byte representation.
+    pub fn from_bytes(bytes: [u8; REGION_REFERENCE_SIZE]) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns the raw byte representation of the reference.
+    pub fn as_bytes(&self) -> &[u8; REGION_REFERENCE_SIZE] {
+        &self.0
+    }
+
+    /// Returns true if the reference doesn't point anywhere (all bytes are zero).
+    pub fn is_null(&self) -> bool {
+        self.0.iter().all(|&b| b == 0)
+    }
+
+    #[doc(hidden)]
+    pub fn as_ptr(&self) -> *const u8 {
+        self.0.as_ptr()
+    }
+
+    #[doc(hidden)]
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.0.as_mut_ptr()
+    }
+}
+
+impl Debug for RegionReference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RegionReference(")?;
+        for b in &self.0 {
+            write!(f, "{:02x}", b)?;
+        }
+        write!(f, ")")
+    }
+}
+
+unsafe impl H5Type for RegionReference {
+    #[inline]
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::RegionReference
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::mem;
+
+    use super::{RegionReference, REGION_REFERENCE_SIZE};
+    use crate::h5type::{H5Type, TypeDescriptor as TD};
+
+    #[test]
+    pub fn test_region_reference() {
+        assert_eq!(RegionReference::type_descriptor(), TD::RegionReference);
+        assert_eq!(RegionReference::type_descriptor().size(), REGION_REFERENCE_SIZE);
+        assert_eq!(mem::size_of::<RegionReference>(), REGION_REFERENCE_SIZE);
+        assert!(RegionReference::default().is_null());
+        let r = RegionReference::from_bytes([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 255]);
+        assert!(!r.is_null());
+        assert_eq!(r.as_bytes()[11], 255);
+        assert_eq!(format!("{:?}", r), "RegionReference(0102030405060708090a0bff)");
+    }
+}
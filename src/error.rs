@@ -2,7 +2,9 @@ use std::cell::RefCell;
 use std::error::Error as StdError;
 use std::fmt;
 use std::ops::Index;
+use std::panic::{self, AssertUnwindSafe};
 use std::ptr;
+use std::sync::Arc;
 
 use lazy_static::lazy_static;
 use ndarray::ShapeError;
@@ -12,7 +14,7 @@ use parking_lot::Mutex;
 
 use hdf5_sys::h5e::{
     H5E_error2_t, H5Eclose_stack, H5Eget_current_stack, H5Eget_msg, H5Eprint2, H5Eset_auto2,
-    H5Ewalk2, H5E_DEFAULT, H5E_WALK_DOWNWARD,
+    H5Eset_current_stack, H5Ewalk2, H5E_DEFAULT, H5E_WALK_DOWNWARD,
 };
 
 use crate::internal_prelude::*;
@@ -23,6 +25,8 @@ pub struct ErrorFrame {
     func: String,
     major: String,
     minor: String,
+    file: String,
+    line: u32,
     description: String,
 }
 
@@ -33,6 +37,8 @@ impl ErrorFrame {
             func: func.into(),
             major: major.into(),
             minor: minor.into(),
+            file: String::new(),
+            line: 0,
             description: format!("{}(): {}", func, desc),
         }
     }
@@ -45,6 +51,31 @@ impl ErrorFrame {
         self.description.as_ref()
     }
 
+    /// Name of the library function that pushed this frame.
+    pub fn func(&self) -> &str {
+        self.func.as_ref()
+    }
+
+    /// Message of the major error class (e.g. "Dataset").
+    pub fn major(&self) -> &str {
+        self.major.as_ref()
+    }
+
+    /// Message of the minor error class (e.g. "Object not found").
+    pub fn minor(&self) -> &str {
+        self.minor.as_ref()
+    }
+
+    /// Name of the library source file where the error was raised.
+    pub fn file(&self) -> &str {
+        self.file.as_ref()
+    }
+
+    /// Line in the library source file where the error was raised.
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
     pub fn detail(&self) -> Option<String> {
         Some(format!("Error in {}(): {} [{}: {}]", self.func, self.desc, self.major, self.minor))
     }
@@ -60,14 +91,71 @@ impl Default for SilenceErrors {
     }
 }
 
+type ErrorHandler = Arc<dyn Fn(&ErrorStack) + Send + Sync>;
+
+// Lock order: the library lock, then `ERROR_HANDLER`, then `CUSTOM_HANDLER`; the library lock
+// has to come first since it may already be held by the current thread (e.g. in a batch).
 lazy_static! {
     static ref ERROR_HANDLER: Mutex<RefCell<usize>> = Mutex::default();
+    static ref CUSTOM_HANDLER: Mutex<Option<ErrorHandler>> = Mutex::default();
 }
 
 extern "C" fn default_error_handler(estack: hid_t, _cdata: *mut c_void) -> herr_t {
     unsafe { H5Eprint2(estack, ptr::null_mut()) }
 }
 
+extern "C" fn custom_error_handler(_estack: hid_t, _cdata: *mut c_void) -> herr_t {
+    let handler = CUSTOM_HANDLER.lock().clone();
+    if let Some(handler) = handler {
+        unsafe {
+            // copying the current stack clears it, so it has to be restored afterwards
+            let stack_id = H5Eget_current_stack();
+            if stack_id < 0 {
+                return -1;
+            }
+            if let Ok(Some(stack)) = ErrorStack::walk(stack_id) {
+                let _ = panic::catch_unwind(AssertUnwindSafe(|| handler(&stack)));
+            }
+            H5Eset_current_stack(stack_id);
+        }
+    }
+    0
+}
+
+fn install_error_handler() {
+    let func: extern "C" fn(hid_t, *mut c_void) -> herr_t =
+        if CUSTOM_HANDLER.lock().is_some() { custom_error_handler } else { default_error_handler };
+    unsafe { H5Eset_auto2(H5E_DEFAULT, Some(func), ptr::null_mut()) };
+}
+
+fn replace_error_handler(handler: Option<ErrorHandler>) {
+    h5lock!({
+        let guard = ERROR_HANDLER.lock();
+        let silenced = *guard.borrow() > 0;
+        *CUSTOM_HANDLER.lock() = handler;
+        if !silenced {
+            install_error_handler();
+        }
+    });
+}
+
+/// Routes errors reported by the HDF5 library to a closure instead of printing them to stderr.
+///
+/// The handler is called with the library error stack before the corresponding Rust error
+/// is returned (the error is still returned as usual). It replaces any previously set
+/// handler and is not called while errors are silenced via `silence_errors()`.
+pub fn set_error_handler<F>(handler: F)
+where
+    F: Fn(&ErrorStack) + Send + Sync + 'static,
+{
+    replace_error_handler(Some(Arc::new(handler)));
+}
+
+/// Restores the default behavior of printing library errors to stderr.
+pub fn reset_error_handler() {
+    replace_error_handler(None);
+}
+
 impl SilenceErrors {
     pub fn new() -> Self {
         Self::silence(true);
@@ -75,21 +163,23 @@ impl SilenceErrors {
     }
 
     fn silence(on: bool) {
-        let guard = ERROR_HANDLER.lock();
-        let counter = &mut *guard.borrow_mut();
-        if on {
-            *counter += 1;
-            if *counter == 1 {
-                h5lock!(H5Eset_auto2(H5E_DEFAULT, None, ptr::null_mut()));
-            }
-        } else {
-            if *counter > 0 {
-                *counter -= 1;
-            }
-            if *counter == 0 {
-                h5lock!(H5Eset_auto2(H5E_DEFAULT, Some(default_error_handler), ptr::null_mut()));
+        h5lock!({
+            let guard = ERROR_HANDLER.lock();
+            let counter = &mut *guard.borrow_mut();
+            if on {
+                *counter += 1;
+                if *counter == 1 {
+                    H5Eset_auto2(H5E_DEFAULT, None, ptr::null_mut());
+                }
+            } else {
+                if *counter > 0 {
+                    *counter -= 1;
+                }
+                if *counter == 0 {
+                    install_error_handler();
+                }
             }
-        }
+        });
     }
 }
 
@@ -99,6 +189,10 @@ impl Drop for SilenceErrors {
     }
 }
 
+/// Disables automatic printing of library errors until the returned guard is dropped.
+///
+/// Guards may be nested; once the last one is dropped, the error handler set via
+/// `set_error_handler()` (or the default one printing to stderr) is reinstated.
 pub fn silence_errors() -> SilenceErrors {
     SilenceErrors::new()
 }
@@ -131,6 +225,17 @@ struct CallbackData {
 impl ErrorStack {
     // This low-level function is not thread-safe and has to be synchronized by the user
     pub fn query() -> Result<Option<Self>> {
+        // known HDF5 bug: H5Eget_msg() may corrupt the current stack, so we copy it first
+        let stack_id = h5lock!(H5Eget_current_stack());
+        ensure!(stack_id >= 0, "failed to copy the current error stack");
+        h5lock!({
+            let stack = Self::walk(stack_id);
+            H5Eclose_stack(stack_id);
+            stack
+        })
+    }
+
+    fn walk(stack_id: hid_t) -> Result<Option<Self>> {
         extern "C" fn callback(
             _: c_uint, err_desc: *const H5E_error2_t, data: *mut c_void,
         ) -> herr_t {
@@ -143,7 +248,8 @@ impl ErrorStack {
                     let (desc, func) = (string_from_cstr(e.desc), string_from_cstr(e.func_name));
                     let major = get_h5_str(|m, s| H5Eget_msg(e.maj_num, ptr::null_mut(), m, s))?;
                     let minor = get_h5_str(|m, s| H5Eget_msg(e.min_num, ptr::null_mut(), m, s))?;
-                    Ok(ErrorFrame::new(&desc, &func, &major, &minor))
+                    let (file, line) = (string_from_cstr(e.file_name), e.line as u32);
+                    Ok(ErrorFrame { file, line, ..ErrorFrame::new(&desc, &func, &major, &minor) })
                 };
                 match closure(*err_desc) {
                     Ok(frame) => {
@@ -159,14 +265,7 @@ impl ErrorStack {
 
         let mut data = CallbackData { stack: Self::new(), err: None };
         let data_ptr: *mut c_void = &mut data as *mut _ as *mut _;
-
-        // known HDF5 bug: H5Eget_msg() may corrupt the current stack, so we copy it first
-        let stack_id = h5lock!(H5Eget_current_stack());
-        ensure!(stack_id >= 0, "failed to copy the current error stack");
-        h5lock!({
-            H5Ewalk2(stack_id, H5E_WALK_DOWNWARD, Some(callback), data_ptr);
-            H5Eclose_stack(stack_id);
-        });
+        h5lock!(H5Ewalk2(stack_id, H5E_WALK_DOWNWARD, Some(callback), data_ptr));
 
         match (data.err, data.stack.is_empty()) {
             (Some(err), _) => Err(err),
@@ -183,6 +282,11 @@ impl ErrorStack {
         self.frames.len()
     }
 
+    /// Returns all frames of the stack, starting from the outermost API call.
+    pub fn frames(&self) -> &[ErrorFrame] {
+        &self.frames
+    }
+
     pub fn push(&mut self, frame: ErrorFrame) {
         self.frames.push(frame);
         if !self.is_empty() {
@@ -218,6 +322,20 @@ impl ErrorStack {
     pub fn detail(&self) -> Option<String> {
         self.top().and_then(ErrorFrame::detail)
     }
+
+    /// Formats every frame of the stack on a separate line, along with source locations.
+    pub fn full_detail(&self) -> String {
+        let lines: Vec<_> = self
+            .frames
+            .iter()
+            .enumerate()
+            .map(|(i, frame)| {
+                let detail = frame.detail().unwrap_or_else(|| frame.description().into());
+                format!("#{:03}: {}:{} {}", i, frame.file, frame.line, detail)
+            })
+            .collect();
+        lines.join("\n")
+    }
 }
 
 /// The error type for HDF5-related functions.
@@ -248,6 +366,14 @@ impl Error {
             Error::HDF5(ref stack) => stack.description(),
         }
     }
+
+    /// Returns the captured library error stack, if any.
+    pub fn stack(&self) -> Option<&ErrorStack> {
+        match *self {
+            Error::Internal(_) => None,
+            Error::HDF5(ref stack) => Some(stack),
+        }
+    }
 }
 
 impl From<&str> for Error {
@@ -271,9 +397,15 @@ impl fmt::Debug for Error {
     }
 }
 
+/// The alternate form (`{:#}`) prints the full library error stack.
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str(self.description())
+        match *self {
+            Error::HDF5(ref stack) if f.alternate() && !stack.is_empty() => {
+                write!(f, "{}\n{}", stack.description(), stack.full_detail())
+            }
+            _ => f.write_str(self.description()),
+        }
     }
 }
 
@@ -353,11 +485,33 @@ pub mod tests {
              [Object atom: Unable to find atom information (already closed?)]"
         );
 
+        assert_eq!(stack.frames().len(), stack.len());
+        assert_eq!(stack[0].func(), "H5Pclose");
+        assert_eq!(stack[0].major(), "Property lists");
+        assert_eq!(stack[0].minor(), "Unable to free object");
+        assert!(stack[0].file().ends_with(".c"));
+        assert!(stack[0].line() > 0);
+
         let empty_stack = ErrorStack::new();
         assert!(empty_stack.is_empty());
         assert_eq!(empty_stack.len(), 0);
     }
 
+    #[test]
+    pub fn test_error_frames() {
+        let _e = silence_errors();
+        with_tmp_file(|file| {
+            let err = file.dataset("missing").unwrap_err();
+            let stack = err.stack().unwrap();
+            assert!(stack.frames().iter().any(|frame| frame.minor() == "Object not found"));
+            let full = format!("{:#}", err);
+            assert!(full.starts_with(&format!("{}\n#000: ", err)));
+            assert_eq!(full.lines().count(), stack.len() + 1);
+            assert!(Error::from("foo").stack().is_none());
+            assert_eq!(format!("{:#}", Error::from("foo")), "foo");
+        })
+    }
+
     #[test]
     pub fn test_h5call() {
         let _e = silence_errors();
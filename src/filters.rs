@@ -3,41 +3,66 @@ use crate::internal_prelude::*;
 
 use hdf5_sys::{
     h5p::{
-        H5Pcreate, H5Pget_filter2, H5Pget_nfilters, H5Pset_deflate, H5Pset_fletcher32,
-        H5Pset_scaleoffset, H5Pset_shuffle, H5Pset_szip,
+        H5Pcreate, H5Pget_filter2, H5Pget_nfilters, H5Pset_deflate, H5Pset_filter,
+        H5Pset_fletcher32, H5Pset_nbit, H5Pset_scaleoffset, H5Pset_shuffle, H5Pset_szip,
     },
     h5t::{H5Tget_class, H5T_FLOAT, H5T_INTEGER},
     h5z::{
         H5Z_filter_t, H5Zfilter_avail, H5Zget_filter_info, H5Z_FILTER_CONFIG_DECODE_ENABLED,
         H5Z_FILTER_CONFIG_ENCODE_ENABLED, H5Z_FILTER_DEFLATE, H5Z_FILTER_FLETCHER32,
-        H5Z_FILTER_SCALEOFFSET, H5Z_FILTER_SHUFFLE, H5Z_FILTER_SZIP, H5Z_SO_FLOAT_DSCALE,
-        H5Z_SO_INT, H5_SZIP_EC_OPTION_MASK, H5_SZIP_NN_OPTION_MASK,
+        H5Z_FILTER_NBIT, H5Z_FILTER_SCALEOFFSET, H5Z_FILTER_SHUFFLE, H5Z_FILTER_SZIP,
+        H5Z_FLAG_MANDATORY, H5Z_SO_FLOAT_DSCALE, H5Z_SO_INT, H5_SZIP_EC_OPTION_MASK,
+        H5_SZIP_NN_OPTION_MASK,
     },
 };
 
+/// Registered identifier of the Zstandard filter (provided by an external plugin).
+pub const H5Z_FILTER_ZSTD: H5Z_filter_t = 32015;
+
+/// Registered identifier of the Blosc filter (provided by an external plugin).
+pub const H5Z_FILTER_BLOSC: H5Z_filter_t = 32001;
+
 /// Returns `true` if gzip filter is available.
 pub fn gzip_available() -> bool {
     h5lock!(H5Zfilter_avail(H5Z_FILTER_DEFLATE) == 1)
 }
 
 /// Returns `true` if szip filter is available.
+///
+/// The filter may be built with the decoder only, in which case szip-compressed datasets
+/// can be read but creating them fails with an "Encoding is not enabled" error.
 pub fn szip_available() -> bool {
     h5lock!(H5Zfilter_avail(H5Z_FILTER_SZIP) == 1)
 }
 
+/// Returns `true` if zstd filter is available (requires the zstd filter plugin).
+pub fn zstd_available() -> bool {
+    h5lock!(H5Zfilter_avail(H5Z_FILTER_ZSTD) == 1)
+}
+
 /// HDF5 filters and compression options.
 #[derive(Clone, PartialEq, Debug)]
 pub struct Filters {
     gzip: Option<u8>,
-    szip: Option<(bool, u8)>,
+    szip: Option<(SzipCoding, u32)>,
+    zstd: Option<i32>,
     shuffle: bool,
     fletcher32: bool,
     scale_offset: Option<u32>,
+    nbit: bool,
 }
 
 impl Default for Filters {
     fn default() -> Self {
-        Self { gzip: None, szip: None, shuffle: false, fletcher32: false, scale_offset: None }
+        Self {
+            gzip: None,
+            szip: None,
+            zstd: None,
+            shuffle: false,
+            fletcher32: false,
+            scale_offset: None,
+            nbit: false,
+        }
     }
 }
 
@@ -63,12 +88,10 @@ impl Filters {
         self.gzip
     }
 
-    /// Enable szip compression with a specified method (EC, NN) and level (0-32).
-    ///
-    /// If `nn` if set to `true` (default), the nearest neighbor method is used, otherwise
-    /// the method is set to entropy coding.
-    pub fn szip(&mut self, nn: bool, level: u8) -> &mut Self {
-        self.szip = Some((nn, level));
+    /// Enable szip compression with a specified coding method and number of pixels per
+    /// block (an even number up to 32).
+    pub fn szip(&mut self, coding: SzipCoding, pixels_per_block: u32) -> &mut Self {
+        self.szip = Some((coding, pixels_per_block));
         self
     }
 
@@ -80,12 +103,29 @@ impl Filters {
 
     /// Get the current settings for szip filter.
     ///
-    /// Returns a tuple `(nn, level)`, where `nn` indicates whether the nearest neighbor
-    /// method is used and `level` is the associated compression level.
-    pub fn get_szip(&self) -> Option<(bool, u8)> {
+    /// Returns a tuple `(coding, pixels_per_block)`.
+    pub fn get_szip(&self) -> Option<(SzipCoding, u32)> {
         self.szip
     }
 
+    /// Enable zstd compression with a specified level (negative levels down to -131072
+    /// trade compression ratio for speed, maximum level is 22).
+    pub fn zstd(&mut self, level: i32) -> &mut Self {
+        self.zstd = Some(level);
+        self
+    }
+
+    /// Disable zstd compression.
+    pub fn no_zstd(&mut self) -> &mut Self {
+        self.zstd = None;
+        self
+    }
+
+    /// Get the current settings for zstd filter.
+    pub fn get_zstd(&self) -> Option<i32> {
+        self.zstd
+    }
+
     /// Enable or disable shuffle filter.
     pub fn shuffle(&mut self, shuffle: bool) -> &mut Self {
         self.shuffle = shuffle;
@@ -125,15 +165,27 @@ impl Filters {
         self.scale_offset
     }
 
+    /// Enable or disable n-bit filter (packs integers according to the precision and offset
+    /// of the stored datatype).
+    pub fn nbit(&mut self, nbit: bool) -> &mut Self {
+        self.nbit = nbit;
+        self
+    }
+
+    /// Get the current settings for n-bit filter.
+    pub fn get_nbit(&self) -> bool {
+        self.nbit
+    }
+
     /// Enable gzip filter with default settings (compression level 4).
     pub fn gzip_default(&mut self) -> &mut Self {
         self.gzip = Some(4);
         self
     }
 
-    /// Enable szip filter with default settings (NN method, compression level 8).
+    /// Enable szip filter with default settings (NN method, 8 pixels per block).
     pub fn szip_default(&mut self) -> &mut Self {
-        self.szip = Some((true, 8));
+        self.szip = Some((SzipCoding::NearestNeighbor, 8));
         self
     }
 
@@ -141,14 +193,20 @@ impl Filters {
     pub fn has_filters(&self) -> bool {
         self.gzip.is_some()
             || self.szip.is_some()
+            || self.zstd.is_some()
             || self.shuffle
             || self.fletcher32
             || self.scale_offset.is_some()
+            || self.nbit
     }
 
     /// Verify whether the filters configuration is valid.
     pub fn validate(&self) -> Result<()> {
-        if self.gzip.is_some() && self.szip.is_some() {
+        let n_compression = [self.gzip.is_some(), self.szip.is_some(), self.zstd.is_some()]
+            .iter()
+            .filter(|&&x| x)
+            .count();
+        if n_compression > 1 {
             fail!("Cannot specify two compression options at once.")
         }
         if let Some(level) = self.gzip {
@@ -160,6 +218,12 @@ impl Filters {
                 "Invalid pixels per block for szip compression, expected even 0-32 integer."
             );
         }
+        if let Some(level) = self.zstd {
+            ensure!(
+                (-131_072..=22).contains(&level),
+                "Invalid level for zstd compression, expected -131072-22 integer."
+            );
+        }
         if let Some(offset) = self.scale_offset {
             ensure!(
                 offset <= c_int::max_value() as _,
@@ -167,71 +231,66 @@ impl Filters {
                 c_int::max_value()
             );
         }
+        if self.scale_offset.is_some() && self.nbit {
+            fail!("Cannot use scale-offset filter with n-bit filter.");
+        }
         if self.scale_offset.is_some() && self.fletcher32 {
             fail!("Cannot use lossy scale-offset filter with fletcher32.");
         }
         Ok(())
     }
 
-    #[doc(hidden)]
-    pub fn from_dcpl(dcpl: &PropertyList) -> Result<Self> {
-        let mut filters = Self::default();
-        h5lock!({
-            let id = dcpl.id();
-            let n_filters: c_int = h5try!(H5Pget_nfilters(id));
-
-            for idx in 0..n_filters {
-                let flags: *mut c_uint = &mut 0;
-                let n_elements: *mut size_t = &mut 16;
-
-                let mut values: Vec<c_uint> = Vec::with_capacity(16);
-                values.set_len(16);
-
-                let mut name: Vec<c_char> = Vec::with_capacity(256);
-                name.set_len(256);
-
-                let filter_config: *mut c_uint = &mut 0;
+    /// Creates filter settings from a filter pipeline as returned by
+    /// `Dataset::filter_pipeline()`.
+    ///
+    /// Since the filters are always applied in a fixed order (fletcher32, scale-offset, n-bit,
+    /// shuffle, compression), pipelines listing them in a different order (or more than once)
+    /// can't be represented and are rejected; Blosc and user-defined filters are not supported.
+    pub fn from_pipeline(pipeline: &[Filter]) -> Result<Self> {
+        // Position of each filter in the pipeline created by `to_dcpl()`.
+        let position = |filter: &Filter| match *filter {
+            Filter::Fletcher32 => 0,
+            Filter::ScaleOffset(_) => 1,
+            Filter::NBit => 2,
+            Filter::Shuffle => 3,
+            _ => 4,
+        };
+        let positions: Vec<_> = pipeline.iter().map(position).collect();
+        let filters = Self::from_filters(pipeline)?;
+        ensure!(
+            positions.windows(2).all(|w| w[0] < w[1]),
+            "Unsupported filter order: {:?} (expected fletcher32, scale-offset, n-bit, shuffle \
+             and compression, each at most once)",
+            pipeline
+        );
+        Ok(filters)
+    }
 
-                let code = H5Pget_filter2(
-                    id,
-                    idx as _,
-                    flags,
-                    n_elements,
-                    values.as_mut_ptr(),
-                    256,
-                    name.as_mut_ptr(),
-                    filter_config,
-                );
-                name.push(0);
-
-                match code {
-                    H5Z_FILTER_DEFLATE => {
-                        filters.gzip(values[0] as _);
-                    }
-                    H5Z_FILTER_SZIP => {
-                        let nn = match values[0] {
-                            v if v & H5_SZIP_EC_OPTION_MASK != 0 => false,
-                            v if v & H5_SZIP_NN_OPTION_MASK != 0 => true,
-                            _ => fail!("Unknown szip method: {:?}", values[0]),
-                        };
-                        filters.szip(nn, values[1] as _);
-                    }
-                    H5Z_FILTER_SHUFFLE => {
-                        filters.shuffle(true);
-                    }
-                    H5Z_FILTER_FLETCHER32 => {
-                        filters.fletcher32(true);
-                    }
-                    H5Z_FILTER_SCALEOFFSET => {
-                        filters.scale_offset(values[1]);
-                    }
-                    _ => fail!("Unsupported filter: {:?}", code),
-                };
-            }
+    /// Collects the settings of the given filters regardless of their order.
+    fn from_filters(pipeline: &[Filter]) -> Result<Self> {
+        let mut filters = Self::default();
+        for filter in pipeline.iter().cloned() {
+            match filter {
+                Filter::Gzip(level) => filters.gzip(level),
+                Filter::Szip { options_mask, pixels_per_block } => {
+                    filters.szip(options_mask, pixels_per_block)
+                }
+                Filter::Zstd(level) => filters.zstd(level),
+                Filter::Shuffle => filters.shuffle(true),
+                Filter::Fletcher32 => filters.fletcher32(true),
+                Filter::ScaleOffset(factor) => filters.scale_offset(factor),
+                Filter::NBit => filters.nbit(true),
+                Filter::Blosc { .. } => fail!("Unsupported filter: {:?}", H5Z_FILTER_BLOSC),
+                Filter::User { id, .. } => fail!("Unsupported filter: {:?}", id),
+            };
+        }
+        filters.validate().and(Ok(filters))
+    }
 
-            Ok(())
-        })
-        .and(filters.validate().and(Ok(filters)))
+    #[doc(hidden)]
+    pub fn from_dcpl(dcpl: &PropertyList) -> Result<Self> {
+        // Pipelines of datasets created elsewhere may use a different order of filters.
+        Self::from_filters(&Filter::from_dcpl(dcpl)?)
     }
 
     fn ensure_available(&self, name: &str, code: H5Z_filter_t) -> Result<()> {
@@ -270,21 +329,29 @@ impl Filters {
             // scale-offset
             if let Some(offset) = self.scale_offset {
                 self.ensure_available("scaleoffset", H5Z_FILTER_SCALEOFFSET)?;
-                match H5Tget_class(datatype.id()) {
-                    H5T_INTEGER => {
-                        H5Pset_scaleoffset(id, H5Z_SO_INT, offset as _);
-                    }
+                let scale_type = match H5Tget_class(datatype.id()) {
+                    H5T_INTEGER => H5Z_SO_INT,
                     H5T_FLOAT => {
                         ensure!(
                             offset > 0,
                             "Can only use positive scale-offset factor with floats"
                         );
-                        H5Pset_scaleoffset(id, H5Z_SO_FLOAT_DSCALE, offset as _);
+                        H5Z_SO_FLOAT_DSCALE
                     }
                     _ => {
                         fail!("Can only use scale/offset with integer/float datatypes.");
                     }
-                }
+                };
+                h5try!(H5Pset_scaleoffset(id, scale_type, offset as _));
+            }
+
+            // n-bit
+            if self.nbit {
+                self.ensure_available("nbit", H5Z_FILTER_NBIT)?;
+                match H5Tget_class(datatype.id()) {
+                    H5T_INTEGER => h5try!(H5Pset_nbit(id)),
+                    _ => fail!("Can only use n-bit filter with integer datatypes."),
+                };
             }
 
             // shuffle
@@ -297,10 +364,19 @@ impl Filters {
             if let Some(level) = self.gzip {
                 self.ensure_available("gzip", H5Z_FILTER_DEFLATE)?;
                 h5try!(H5Pset_deflate(id, c_uint::from(level)));
-            } else if let Some((nn, pixels_per_block)) = self.szip {
+            } else if let Some((coding, pixels_per_block)) = self.szip {
                 self.ensure_available("szip", H5Z_FILTER_SZIP)?;
-                let options = if nn { H5_SZIP_NN_OPTION_MASK } else { H5_SZIP_EC_OPTION_MASK };
-                h5try!(H5Pset_szip(id, options, c_uint::from(pixels_per_block)));
+                h5try!(H5Pset_szip(id, coding.into(), pixels_per_block as _));
+            } else if let Some(level) = self.zstd {
+                self.ensure_available("zstd", H5Z_FILTER_ZSTD)?;
+                let values = [level as c_uint];
+                h5try!(H5Pset_filter(
+                    id,
+                    H5Z_FILTER_ZSTD,
+                    H5Z_FLAG_MANDATORY,
+                    values.len() as _,
+                    values.as_ptr()
+                ));
             }
 
             Ok(plist)
@@ -308,9 +384,141 @@ impl Filters {
     }
 }
 
+/// Coding method of the szip filter.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SzipCoding {
+    /// Entropy coding.
+    Entropy,
+    /// Nearest neighbor coding (preprocessing followed by entropy coding).
+    NearestNeighbor,
+}
+
+impl From<SzipCoding> for c_uint {
+    fn from(coding: SzipCoding) -> Self {
+        match coding {
+            SzipCoding::Entropy => H5_SZIP_EC_OPTION_MASK,
+            SzipCoding::NearestNeighbor => H5_SZIP_NN_OPTION_MASK,
+        }
+    }
+}
+
+/// A single filter in the filter pipeline of a dataset, along with its parameters.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Filter {
+    /// Gzip compression with a given level.
+    Gzip(u8),
+    /// Szip compression with a given coding method and number of pixels per block.
+    Szip { options_mask: SzipCoding, pixels_per_block: u32 },
+    /// Zstd compression with a given level.
+    Zstd(i32),
+    /// Shuffle filter.
+    Shuffle,
+    /// Fletcher32 checksum.
+    Fletcher32,
+    /// N-bit filter.
+    NBit,
+    /// Scale-offset filter with a given factor; for integers, it's the minimum number of bits
+    /// (0 means automatic), for floats it's the number of decimal digits to keep (the scale
+    /// type is determined by the class of the datatype).
+    ScaleOffset(u32),
+    /// Blosc compression (as stored by the Blosc filter plugin, e.g. via PyTables or h5py);
+    /// `shuffle` and `compressor` are the raw Blosc codes.
+    Blosc { level: u8, shuffle: u8, compressor: u8 },
+    /// Any other filter, identified by its registered ID, with its client data values.
+    User { id: H5Z_filter_t, cd_values: Vec<c_uint> },
+}
+
+impl Filter {
+    /// Returns the registered ID of the filter.
+    pub fn id(&self) -> H5Z_filter_t {
+        match *self {
+            Filter::Gzip(_) => H5Z_FILTER_DEFLATE,
+            Filter::Szip { .. } => H5Z_FILTER_SZIP,
+            Filter::Zstd(_) => H5Z_FILTER_ZSTD,
+            Filter::Shuffle => H5Z_FILTER_SHUFFLE,
+            Filter::Fletcher32 => H5Z_FILTER_FLETCHER32,
+            Filter::ScaleOffset(_) => H5Z_FILTER_SCALEOFFSET,
+            Filter::NBit => H5Z_FILTER_NBIT,
+            Filter::Blosc { .. } => H5Z_FILTER_BLOSC,
+            Filter::User { id, .. } => id,
+        }
+    }
+
+    fn from_raw(id: H5Z_filter_t, values: &[c_uint]) -> Result<Self> {
+        let value = |i: usize| values.get(i).cloned().unwrap_or(0);
+        Ok(match id {
+            H5Z_FILTER_DEFLATE => Filter::Gzip(value(0) as _),
+            H5Z_FILTER_SZIP => {
+                let options_mask = match value(0) {
+                    v if v & H5_SZIP_EC_OPTION_MASK != 0 => SzipCoding::Entropy,
+                    v if v & H5_SZIP_NN_OPTION_MASK != 0 => SzipCoding::NearestNeighbor,
+                    _ => fail!("Unknown szip method: {:?}", value(0)),
+                };
+                Filter::Szip { options_mask, pixels_per_block: value(1) }
+            }
+            H5Z_FILTER_ZSTD => Filter::Zstd(value(0) as _),
+            H5Z_FILTER_SHUFFLE => Filter::Shuffle,
+            H5Z_FILTER_FLETCHER32 => Filter::Fletcher32,
+            H5Z_FILTER_NBIT => Filter::NBit,
+            H5Z_FILTER_SCALEOFFSET => Filter::ScaleOffset(value(1)),
+            // The first four values are reserved for the filter itself (version, type size etc).
+            H5Z_FILTER_BLOSC if values.len() >= 7 => Filter::Blosc {
+                level: value(4) as _,
+                shuffle: value(5) as _,
+                compressor: value(6) as _,
+            },
+            _ => Filter::User { id, cd_values: values.to_vec() },
+        })
+    }
+
+    /// Reads the filter pipeline from a dataset creation property list.
+    #[doc(hidden)]
+    pub fn from_dcpl(dcpl: &PropertyList) -> Result<Vec<Self>> {
+        h5lock!({
+            let id = dcpl.id();
+            let n_filters: c_int = h5try!(H5Pget_nfilters(id));
+            let mut pipeline = Vec::with_capacity(n_filters as _);
+
+            for idx in 0..n_filters {
+                let mut flags: c_uint = 0;
+                let mut n_elements: size_t = 16;
+                let mut values: Vec<c_uint> = vec![0; n_elements];
+                let mut name: Vec<c_char> = vec![0; 257];
+                let mut filter_config: c_uint = 0;
+
+                let mut get_filter = |n_elements: &mut size_t, values: &mut [c_uint]| {
+                    h5check(H5Pget_filter2(
+                        id,
+                        idx as _,
+                        &mut flags,
+                        n_elements,
+                        values.as_mut_ptr(),
+                        256,
+                        name.as_mut_ptr(),
+                        &mut filter_config,
+                    ))
+                };
+
+                let mut code = get_filter(&mut n_elements, &mut values)?;
+                if n_elements > values.len() {
+                    // The parameters didn't fit, so retry with a buffer of the reported size.
+                    values.resize(n_elements, 0);
+                    code = get_filter(&mut n_elements, &mut values)?;
+                }
+                values.truncate(n_elements);
+                pipeline.push(Self::from_raw(code, &values)?);
+            }
+
+            Ok(pipeline)
+        })
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
-    use super::{gzip_available, szip_available};
+    use ndarray::Array2;
+
+    use super::{gzip_available, szip_available, zstd_available};
     use crate::internal_prelude::*;
 
     fn make_filters<T: H5Type>(filters: &Filters) -> Result<Filters> {
@@ -325,6 +533,8 @@ pub mod tests {
 
     #[test]
     pub fn test_szip() {
+        use super::SzipCoding::{Entropy as EC, NearestNeighbor as NN};
+
         let _e = silence_errors();
 
         if !szip_available() {
@@ -334,24 +544,28 @@ pub mod tests {
             );
         } else {
             assert!(Filters::new().get_szip().is_none());
-            assert_eq!(Filters::new().szip(false, 4).get_szip(), Some((false, 4)));
-            assert!(Filters::new().szip(false, 4).no_szip().get_szip().is_none());
-            assert_eq!(Filters::new().szip_default().get_szip(), Some((true, 8)));
+            assert_eq!(Filters::new().szip(EC, 4).get_szip(), Some((EC, 4)));
+            assert!(Filters::new().szip(EC, 4).no_szip().get_szip().is_none());
+            assert_eq!(Filters::new().szip_default().get_szip(), Some((NN, 8)));
 
             check_roundtrip::<u32>(Filters::new().no_szip());
-            check_roundtrip::<u32>(Filters::new().szip(false, 4));
-            check_roundtrip::<u32>(Filters::new().szip(true, 4));
+            check_roundtrip::<u32>(Filters::new().szip(EC, 4));
+            check_roundtrip::<u32>(Filters::new().szip(NN, 4));
 
             check_roundtrip::<f32>(Filters::new().no_szip());
-            check_roundtrip::<f32>(Filters::new().szip(false, 4));
-            check_roundtrip::<f32>(Filters::new().szip(true, 4));
+            check_roundtrip::<f32>(Filters::new().szip(EC, 4));
+            check_roundtrip::<f32>(Filters::new().szip(NN, 4));
 
             assert_err!(
-                make_filters::<u32>(&Filters::new().szip(false, 1)),
+                make_filters::<u32>(&Filters::new().szip(EC, 1)),
                 "Invalid pixels per block for szip compression"
             );
             assert_err!(
-                make_filters::<u32>(&Filters::new().szip(true, 34)),
+                make_filters::<u32>(&Filters::new().szip(NN, 34)),
+                "Invalid pixels per block for szip compression"
+            );
+            assert_err!(
+                make_filters::<u32>(&Filters::new().szip(EC, 1 << 16)),
                 "Invalid pixels per block for szip compression"
             );
         }
@@ -389,6 +603,43 @@ pub mod tests {
         }
     }
 
+    #[test]
+    pub fn test_zstd() {
+        let _e = silence_errors();
+
+        assert!(Filters::new().get_zstd().is_none());
+        assert_eq!(Filters::new().zstd(3).get_zstd(), Some(3));
+        assert!(Filters::new().zstd(3).no_zstd().get_zstd().is_none());
+        assert!(Filters::new().zstd(-5).has_filters());
+
+        if !zstd_available() {
+            assert_err!(make_filters::<u32>(&Filters::new().zstd(3)), "Filter not available: zstd");
+        } else {
+            check_roundtrip::<u32>(Filters::new().no_zstd());
+            check_roundtrip::<u32>(Filters::new().zstd(3));
+            check_roundtrip::<f64>(Filters::new().zstd(-5));
+            check_roundtrip::<f64>(Filters::new().zstd(22).shuffle(true));
+
+            assert_err!(
+                make_filters::<u32>(&Filters::new().zstd(3).gzip_default()),
+                "Cannot specify two compression options at once"
+            );
+            assert_err!(
+                make_filters::<u32>(&Filters::new().zstd(23)),
+                "Invalid level for zstd compression"
+            );
+
+            with_tmp_file(|file| {
+                let arr = Array2::from_shape_fn((200, 50), |(i, j)| (i / 10 + j % 5) as f64);
+                let ds = file.new_dataset::<f64>().zstd(9).create("zstd", (200, 50)).unwrap();
+                ds.write(&arr).unwrap();
+                assert_eq!(ds.filters().get_zstd(), Some(9));
+                assert!(ds.storage_size() < (arr.len() * 8) as u64);
+                assert_eq!(ds.read_2d::<f64>().unwrap(), arr);
+            });
+        }
+    }
+
     #[test]
     pub fn test_shuffle() {
         assert!(!Filters::new().get_shuffle());
@@ -442,6 +693,92 @@ pub mod tests {
             make_filters::<u32>(&Filters::new().scale_offset(0).fletcher32(true)),
             "Cannot use lossy scale-offset filter with fletcher32"
         );
+        assert_err!(
+            make_filters::<bool>(&Filters::new().scale_offset(0)),
+            "Can only use scale/offset with integer/float datatypes"
+        );
+
+        with_tmp_file(|file| {
+            let arr = Array2::from_shape_fn((100, 40), |(i, j)| 10_000 + (i * 7 + j) as i32 % 300);
+            let mut builder = file.new_dataset::<i32>();
+            builder.scale_offset(0).shuffle(true);
+            if gzip_available() {
+                builder.gzip(5);
+            }
+            let ds = builder.create("int", (100, 40)).unwrap();
+            ds.write(&arr).unwrap();
+            assert_eq!(ds.read_2d::<i32>().unwrap(), arr);
+            assert!(ds.storage_size() < (arr.len() * 4) as u64);
+            let pipeline = ds.filter_pipeline().unwrap();
+            assert_eq!(pipeline[..2], [Filter::ScaleOffset(0), Filter::Shuffle]);
+            assert_eq!(pipeline.len(), if gzip_available() { 3 } else { 2 });
+
+            let ds = file.new_dataset::<f64>().scale_offset(2).create("float", 3).unwrap();
+            ds.write(&[1.234, -5.678, 9.]).unwrap();
+            // Values are stored as `round((x - min) * 10^2)`, so they're within 0.5 * 10^-2.
+            let values = ds.read_raw::<f64>().unwrap();
+            for (x, y) in values.iter().zip(&[1.234, -5.678, 9.]) {
+                assert!((x - y).abs() <= 0.005);
+            }
+            assert_eq!(ds.filter_pipeline().unwrap(), vec![Filter::ScaleOffset(2)]);
+            assert_err!(
+                file.new_dataset::<bool>().scale_offset(0).create("bool", 3),
+                "Can only use scale/offset with integer/float datatypes"
+            );
+        });
+    }
+
+    #[test]
+    pub fn test_nbit() {
+        let _e = silence_errors();
+
+        assert!(!Filters::new().get_nbit());
+        assert!(Filters::new().nbit(true).get_nbit());
+        assert!(!Filters::new().nbit(true).nbit(false).get_nbit());
+
+        check_roundtrip::<u16>(Filters::new().nbit(false));
+        check_roundtrip::<u16>(Filters::new().nbit(true));
+        check_roundtrip::<i64>(Filters::new().nbit(true).shuffle(true));
+
+        assert_err!(
+            make_filters::<f32>(&Filters::new().nbit(true)),
+            "Can only use n-bit filter with integer datatypes"
+        );
+        assert_err!(
+            make_filters::<u16>(&Filters::new().nbit(true).scale_offset(0)),
+            "Cannot use scale-offset filter with n-bit filter"
+        );
+
+        with_tmp_file(|file| {
+            let arr = Array2::from_shape_fn((64, 64), |(i, j)| ((i * 64 + j) % 4096) as u16);
+            let ds = file.new_dataset::<u16>().precision(12, 0).nbit(true);
+            let ds = ds.create("nbit", (64, 64)).unwrap();
+            ds.write(&arr).unwrap();
+            assert_eq!(ds.read_2d::<u16>().unwrap(), arr);
+            assert_eq!(ds.filter_pipeline().unwrap(), vec![Filter::NBit]);
+            let dtype = ds.dtype().unwrap();
+            assert_eq!((dtype.size(), dtype.precision(), dtype.offset()), (2, 12, 0));
+            assert!(ds.storage_size() < (arr.len() * 2) as u64);
+
+            let ds = file.new_dataset::<u32>().precision(8, 4).nbit(true).create("offset", 3);
+            let ds = ds.unwrap();
+            ds.write(&[0u32, 17, 255]).unwrap();
+            assert_eq!(ds.read_raw::<u32>().unwrap(), vec![0, 17, 255]);
+            assert_eq!(ds.dtype().unwrap().offset(), 4);
+
+            assert_err!(
+                file.new_dataset::<u16>().precision(17, 0).create("large", 1),
+                "Invalid precision 17 with offset 0 for a 16-bit integer datatype"
+            );
+            assert_err!(
+                file.new_dataset::<u16>().precision(12, 5).create("offset2", 1),
+                "Invalid precision 12 with offset 5 for a 16-bit integer datatype"
+            );
+            assert_err!(
+                file.new_dataset::<f32>().precision(12, 0).create("float", 1),
+                "Precision can only be set for integer datatypes"
+            );
+        });
     }
 
     #[test]
@@ -465,5 +802,6 @@ pub mod tests {
         assert_eq!(Filters::default().fletcher32(true).has_filters(), true);
         assert_eq!(Filters::default().shuffle(true).has_filters(), true);
         assert_eq!(Filters::default().scale_offset(2).has_filters(), true);
+        assert_eq!(Filters::default().nbit(true).has_filters(), true);
     }
 }
@@ -8,6 +8,8 @@ use lazy_static::lazy_static;
 use hdf5_sys::h5fd::H5FD_direct_init;
 #[cfg(h5_have_parallel)]
 use hdf5_sys::h5fd::H5FD_mpio_init;
+#[cfg(feature = "ros3")]
+use hdf5_sys::h5fd::H5FD_ros3_init;
 use hdf5_sys::h5fd::{
     H5FD_core_init, H5FD_family_init, H5FD_log_init, H5FD_multi_init, H5FD_sec2_init,
     H5FD_stdio_init,
@@ -350,6 +352,16 @@ lazy_static! {
     pub static ref H5FD_DIRECT: hid_t = H5I_INVALID_HID;
 }
 
+// Read-only S3 VFD
+#[cfg(feature = "ros3")]
+lazy_static! {
+    pub static ref H5FD_ROS3: hid_t = unsafe { h5lock!(H5FD_ros3_init()) };
+}
+#[cfg(not(feature = "ros3"))]
+lazy_static! {
+    pub static ref H5FD_ROS3: hid_t = H5I_INVALID_HID;
+}
+
 #[cfg(target_os = "windows")]
 lazy_static! {
     pub static ref H5FD_WINDOWS: hid_t = *H5FD_SEC2;
@@ -100,6 +100,8 @@ impl Handle {
             }
             // must invalidate all linked IDs because the library reuses them internally
             if !self.is_valid_user_id() && !self.is_valid_id() {
+                #[cfg(feature = "io-stats")]
+                crate::hl::dataset::forget_io_stats(self.id());
                 self.invalidate();
             }
         })
@@ -0,0 +1,52 @@
+//! Asynchronous reading and writing of datasets via tokio.
+
+use ndarray::Array;
+use tokio::task;
+
+use crate::internal_prelude::*;
+
+/// Runs a blocking closure on the tokio blocking thread pool.
+async fn spawn_blocking<T, F>(func: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    task::spawn_blocking(func).await.map_err(|err| format!("Blocking task failed: {}", err))?
+}
+
+/// Asynchronous I/O, enabled via the `tokio` feature.
+///
+/// The blocking library calls are run on the tokio blocking thread pool, so the async
+/// runtime isn't stalled while they're in progress. Note that all calls into the library
+/// still go through the global lock, so concurrent reads and writes are serialized.
+impl Dataset {
+    /// Reads the dataset into an n-dimensional array, see `read()`.
+    pub async fn read_async<T, D>(&self) -> Result<Array<T, D>>
+    where
+        T: H5Type + Send + 'static,
+        D: ndarray::Dimension + Send + 'static,
+    {
+        let ds = self.clone();
+        spawn_blocking(move || ds.read::<T, D>()).await
+    }
+
+    /// Reads the dataset into a vector in memory order, see `read_raw()`.
+    pub async fn read_raw_async<T>(&self) -> Result<Vec<T>>
+    where
+        T: H5Type + Send + 'static,
+    {
+        let ds = self.clone();
+        spawn_blocking(move || ds.read_raw::<T>()).await
+    }
+
+    /// Writes an n-dimensional array into the dataset, see `write()`; the array is moved
+    /// into the blocking task.
+    pub async fn write_async<T, D>(&self, arr: Array<T, D>) -> Result<()>
+    where
+        T: H5Type + Send + 'static,
+        D: ndarray::Dimension + Send + 'static,
+    {
+        let ds = self.clone();
+        spawn_blocking(move || ds.write(&arr)).await
+    }
+}
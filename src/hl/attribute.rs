@@ -0,0 +1,542 @@
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::slice;
+
+use ndarray::{ArrayView, ArrayView0};
+
+use hdf5_types::VarLenUnicode;
+
+use hdf5_sys::{
+    h5a::{
+        H5A_info_t, H5A_operator2_t, H5Acreate2, H5Adelete, H5Aexists, H5Aget_name, H5Aiterate2,
+        H5Aopen, H5Aread, H5Awrite,
+    },
+    h5d::H5Dvlen_reclaim,
+    h5p::{H5Pset_attr_creation_order, H5P_CRT_ORDER_INDEXED, H5P_CRT_ORDER_TRACKED},
+};
+
+use crate::internal_prelude::*;
+
+/// Represents the HDF5 attribute object.
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct Attribute(Handle);
+
+impl ObjectClass for Attribute {
+    const NAME: &'static str = "attribute";
+    const VALID_TYPES: &'static [H5I_type_t] = &[H5I_ATTR];
+
+    fn from_handle(handle: Handle) -> Self {
+        Self(handle)
+    }
+
+    fn handle(&self) -> &Handle {
+        &self.0
+    }
+
+    fn short_repr(&self) -> Option<String> {
+        Some(format!("\"{}\"", self.attr_name()))
+    }
+}
+
+impl Debug for Attribute {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.debug_fmt(f)
+    }
+}
+
+impl Deref for Attribute {
+    type Target = Container;
+
+    fn deref(&self) -> &Container {
+        unsafe { self.transmute() }
+    }
+}
+
+impl Attribute {
+    /// Returns the name of the attribute (note that `name()` returns the name of the
+    /// object the attribute is attached to).
+    pub fn attr_name(&self) -> String {
+        h5lock!(get_h5_str(|m, s| H5Aget_name(self.id(), s, m)).unwrap_or_else(|_| "".into()))
+    }
+
+    pub(crate) fn attr_names(
+        obj: &Location, index: IndexType, order: IterationOrder,
+    ) -> Result<Vec<String>> {
+        extern "C" fn attributes_callback(
+            _id: hid_t, name: *const c_char, _info: *const H5A_info_t, op_data: *mut c_void,
+        ) -> herr_t {
+            let other_data: &mut Vec<String> = unsafe { &mut *(op_data as *mut Vec<String>) };
+
+            other_data.push(string_from_cstr(name));
+
+            0 // Continue iteration
+        }
+
+        ensure!(obj.id_type() != H5I_ATTR, "Attributes cannot have attributes");
+        let callback_fn: H5A_operator2_t = Some(attributes_callback);
+        let iteration_position: *mut hsize_t = &mut { 0 as u64 };
+        let mut result: Vec<String> = Vec::new();
+        let other_data: *mut c_void = &mut result as *mut _ as *mut c_void;
+
+        h5call!(H5Aiterate2(
+            obj.id(),
+            index.into(),
+            order.into(),
+            iteration_position,
+            callback_fn,
+            other_data
+        ))?;
+
+        Ok(result)
+    }
+}
+
+/// Builder used to create attributes attached to an object.
+#[derive(Clone)]
+pub struct AttributeBuilder<T> {
+    packed: bool,
+    parent: Result<Handle>,
+    phantom: PhantomData<T>,
+}
+
+impl<T: H5Type> AttributeBuilder<T> {
+    /// Create a new attribute builder and bind it to the parent object.
+    pub fn new(parent: &Location) -> Self {
+        h5lock!({
+            // Store the reference to the parent handle and try to increase its reference count.
+            let handle = Handle::try_new(parent.id());
+            if let Ok(ref handle) = handle {
+                handle.incref();
+            }
+
+            Self { packed: false, parent: handle, phantom: PhantomData }
+        })
+    }
+
+    pub fn packed(&mut self, packed: bool) -> &mut Self {
+        self.packed = packed;
+        self
+    }
+
+    /// Create the attribute and attach it to the parent object.
+    pub fn create<D: Dimension>(&self, name: &str, shape: D) -> Result<Attribute> {
+        let type_descriptor = if self.packed {
+            <T as H5Type>::type_descriptor().to_packed_repr()
+        } else {
+            <T as H5Type>::type_descriptor().to_c_repr()
+        };
+        h5lock!({
+            let datatype = Datatype::from_descriptor(&type_descriptor)?;
+            let parent = try_ref_clone!(self.parent);
+            ensure!(get_id_type(parent.id()) != H5I_ATTR, "Attributes cannot have attributes");
+
+            let dataspace = Dataspace::try_new(&shape, false)?;
+            let name = to_cstring(name)?;
+            Attribute::from_id(h5try!(H5Acreate2(
+                parent.id(),
+                name.as_ptr(),
+                datatype.id(),
+                dataspace.id(),
+                H5P_DEFAULT,
+                H5P_DEFAULT
+            )))
+        })
+    }
+}
+
+/// Enables or disables tracking and indexing of attribute creation order in an object
+/// creation property list.
+pub(crate) fn set_attr_creation_order(plist_id: hid_t, tracked: bool) -> Result<()> {
+    let flags = if tracked { H5P_CRT_ORDER_TRACKED | H5P_CRT_ORDER_INDEXED } else { 0 };
+    h5call!(H5Pset_attr_creation_order(plist_id, flags)).and(Ok(()))
+}
+
+/// Copies an attribute as stored (without type conversion) to another object.
+pub(crate) fn copy_attr(attr: &Attribute, dest: &Location, name: &str) -> Result<()> {
+    h5lock!({
+        let dtype = attr.dtype()?;
+        let space = attr.space()?;
+        let c_name = to_cstring(name)?;
+        let copy = Attribute::from_id(h5try!(H5Acreate2(
+            dest.id(),
+            c_name.as_ptr(),
+            dtype.id(),
+            space.id(),
+            H5P_DEFAULT,
+            H5P_DEFAULT
+        )))?;
+        let mut buf = vec![0_u8; space.size() * dtype.size()];
+        h5try!(H5Aread(attr.id(), dtype.id(), buf.as_mut_ptr() as *mut _));
+        let written = h5call!(H5Awrite(copy.id(), dtype.id(), buf.as_ptr() as *const _));
+        h5try!(H5Dvlen_reclaim(dtype.id(), space.id(), H5P_DEFAULT, buf.as_mut_ptr() as *mut _));
+        written.and(Ok(()))
+    })
+}
+
+/// A value of an attribute written via `Location::write_attr_values()`.
+///
+/// Strings are stored as variable-length UTF-8 strings, arrays are one-dimensional.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AttrValue {
+    Integer(i64),
+    Float(f64),
+    String(String),
+    IntegerArray(Vec<i64>),
+    FloatArray(Vec<f64>),
+    StringArray(Vec<String>),
+}
+
+impl From<i64> for AttrValue {
+    fn from(value: i64) -> Self {
+        AttrValue::Integer(value)
+    }
+}
+
+impl From<f64> for AttrValue {
+    fn from(value: f64) -> Self {
+        AttrValue::Float(value)
+    }
+}
+
+impl From<&str> for AttrValue {
+    fn from(value: &str) -> Self {
+        AttrValue::String(value.into())
+    }
+}
+
+impl From<String> for AttrValue {
+    fn from(value: String) -> Self {
+        AttrValue::String(value)
+    }
+}
+
+impl From<Vec<i64>> for AttrValue {
+    fn from(value: Vec<i64>) -> Self {
+        AttrValue::IntegerArray(value)
+    }
+}
+
+impl From<Vec<f64>> for AttrValue {
+    fn from(value: Vec<f64>) -> Self {
+        AttrValue::FloatArray(value)
+    }
+}
+
+impl From<Vec<String>> for AttrValue {
+    fn from(value: Vec<String>) -> Self {
+        AttrValue::StringArray(value)
+    }
+}
+
+fn to_varlen_unicode(s: &str) -> Result<VarLenUnicode> {
+    s.parse().map_err(|err| format!("Invalid string {:?}: {}", s, err).into())
+}
+
+impl Location {
+    /// Instantiates a new attribute builder.
+    pub fn new_attr<T: H5Type>(&self) -> AttributeBuilder<T> {
+        AttributeBuilder::<T>::new(self)
+    }
+
+    /// Opens an existing attribute attached to the object.
+    pub fn attr(&self, name: &str) -> Result<Attribute> {
+        let name = to_cstring(name)?;
+        Attribute::from_id(h5try!(H5Aopen(self.id(), name.as_ptr(), H5P_DEFAULT)))
+    }
+
+    /// Checks if an attribute with a given name is attached to the object.
+    pub fn attr_exists(&self, name: &str) -> bool {
+        let _e = silence_errors();
+        (|| -> Result<bool> {
+            let name = to_cstring(name)?;
+            Ok(h5call!(H5Aexists(self.id(), name.as_ptr()))? > 0)
+        })()
+        .unwrap_or(false)
+    }
+
+    /// Writes a scalar attribute, creating it if it doesn't exist.
+    ///
+    /// An existing attribute with the same name is written in place if it's a scalar of the
+    /// same datatype, otherwise it's deleted and recreated (like `obj.attrs[name] = value`
+    /// in h5py).
+    pub fn set_attr<T: H5Type>(&self, name: &str, value: &T) -> Result<()> {
+        self.set_attr_array(name, ArrayView0::from_shape((), slice::from_ref(value))?)
+    }
+
+    /// Writes an n-dimensional array attribute, creating it if it doesn't exist.
+    ///
+    /// An existing attribute with the same name is written in place if it has the same
+    /// shape and datatype, otherwise it's deleted and recreated. Zero-dimensional views are
+    /// stored as scalar attributes.
+    pub fn set_attr_array<'b, A, T, D>(&self, name: &str, arr: A) -> Result<()>
+    where
+        A: Into<ArrayView<'b, T, D>>,
+        T: H5Type,
+        D: ndarray::Dimension,
+    {
+        ensure!(self.id_type() != H5I_ATTR, "Attributes cannot have attributes");
+        let view = arr.into();
+        let shape = view.shape().to_vec();
+        let c_name = to_cstring(name)?;
+        if h5call!(H5Aexists(self.id(), c_name.as_ptr()))? > 0 {
+            {
+                let attr = self.attr(name)?;
+                let dtype = Datatype::from_descriptor(&T::type_descriptor().to_c_repr())?;
+                if attr.shape() == shape && attr.dtype()? == dtype {
+                    return attr.write(view);
+                }
+            }
+            h5try!(H5Adelete(self.id(), c_name.as_ptr()));
+        }
+        self.new_attr::<T>().create(name, shape)?.write(view)
+    }
+
+    /// Writes multiple scalar attributes of the same type, see `set_attr()`.
+    ///
+    /// Attributes are written one by one in alphanumeric order of their names; if one of
+    /// them can't be written, the error names it and the attributes written before it are
+    /// left in place (the remaining ones are not written).
+    pub fn write_attrs<T: H5Type>(&self, attrs: &HashMap<String, T>) -> Result<()> {
+        let mut names: Vec<_> = attrs.keys().collect();
+        names.sort();
+        for name in names {
+            self.set_attr(name, &attrs[name])
+                .map_err(|err| format!("Cannot write attribute {:?}: {}", name, err))?;
+        }
+        Ok(())
+    }
+
+    /// Writes multiple attributes of possibly different types, see `write_attrs()`.
+    pub fn write_attr_values(&self, attrs: &HashMap<String, AttrValue>) -> Result<()> {
+        let mut names: Vec<_> = attrs.keys().collect();
+        names.sort();
+        for name in names {
+            self.set_attr_value(name, &attrs[name])
+                .map_err(|err| format!("Cannot write attribute {:?}: {}", name, err))?;
+        }
+        Ok(())
+    }
+
+    fn set_attr_value(&self, name: &str, value: &AttrValue) -> Result<()> {
+        match value {
+            AttrValue::Integer(v) => self.set_attr(name, v),
+            AttrValue::Float(v) => self.set_attr(name, v),
+            AttrValue::String(v) => self.set_attr(name, &to_varlen_unicode(v)?),
+            AttrValue::IntegerArray(v) => self.set_attr_array(name, v.as_slice()),
+            AttrValue::FloatArray(v) => self.set_attr_array(name, v.as_slice()),
+            AttrValue::StringArray(v) => {
+                let strings = v.iter().map(|s| to_varlen_unicode(s)).collect::<Result<Vec<_>>>()?;
+                self.set_attr_array(name, strings.as_slice())
+            }
+        }
+    }
+
+    /// Returns names of all the attributes attached to the object, in alphanumeric order.
+    ///
+    /// Only the attributes of the object itself are listed; e.g., the attributes attached to
+    /// a committed datatype of a dataset are not included.
+    pub fn attr_names(&self) -> Result<Vec<String>> {
+        Attribute::attr_names(self, IndexType::Name, IterationOrder::Increasing)
+    }
+
+    /// Iterates over all attributes attached to the object in a given order, yielding their
+    /// names along with the opened attributes.
+    ///
+    /// Iterating in creation order requires the object to track attribute creation order.
+    pub fn iter_attrs(
+        &self, index: IndexType, order: IterationOrder,
+    ) -> Result<impl Iterator<Item = (String, Attribute)>> {
+        let names = Attribute::attr_names(self, index, order)?;
+        let attrs: Result<Vec<_>> =
+            names.into_iter().map(|name| self.attr(&name).map(|attr| (name, attr))).collect();
+        attrs.map(Vec::into_iter)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::collections::HashMap;
+
+    use ndarray::arr2;
+
+    use hdf5_types::VarLenUnicode;
+
+    use super::AttrValue;
+    use crate::internal_prelude::*;
+
+    #[test]
+    pub fn test_create_open() {
+        with_tmp_file(|file| {
+            let attr = file.new_attr::<u32>().create("foo", (2, 3)).unwrap();
+            assert_eq!(attr.shape(), vec![2, 3]);
+            assert_eq!(attr.attr_name(), "foo");
+            assert_eq!(attr.name(), "/");
+            assert_eq!(format!("{:?}", attr), "<HDF5 attribute: \"foo\">");
+            attr.write(&arr2(&[[1, 2, 3], [4, 5, 6]])).unwrap();
+            let attr = file.attr("foo").unwrap();
+            assert_eq!(attr.read_2d::<u32>().unwrap(), arr2(&[[1, 2, 3], [4, 5, 6]]));
+            assert_eq!(attr.dtype().unwrap(), Datatype::from_type::<u32>().unwrap());
+            assert_err!(file.new_attr::<u32>().create("foo", 1), "unable to create attribute");
+            assert_err_re!(file.attr("bar"), "unable to (open|load) attribute");
+            assert_err!(attr.new_attr::<u8>().create("bar", 1), "cannot have attributes");
+        })
+    }
+
+    #[test]
+    pub fn test_attr_exists() {
+        with_tmp_file(|file| {
+            assert!(!file.attr_exists("foo"));
+            file.new_attr::<u32>().create("foo", 2).unwrap();
+            assert!(file.attr_exists("foo"));
+            assert!(!file.attr_exists("bar"));
+            let ds = file.new_dataset::<u8>().create("ds", ()).unwrap();
+            assert!(!ds.attr_exists("foo"));
+            ds.set_attr("foo", &1_u8).unwrap();
+            assert!(ds.attr_exists("foo"));
+        })
+    }
+
+    #[test]
+    pub fn test_set_attr() {
+        with_tmp_file(|file| {
+            file.set_attr("foo", &1_u32).unwrap();
+            assert!(file.attr("foo").unwrap().is_scalar());
+            assert_eq!(file.attr("foo").unwrap().read_scalar::<u32>().unwrap(), 1);
+
+            // Same shape and type: written in place.
+            file.set_attr("foo", &2_u32).unwrap();
+            assert_eq!(file.attr("foo").unwrap().read_scalar::<u32>().unwrap(), 2);
+
+            // Different type: recreated.
+            file.set_attr("foo", &-1.5_f64).unwrap();
+            assert!(file.attr("foo").unwrap().is_scalar());
+            assert_eq!(
+                file.attr("foo").unwrap().dtype().unwrap(),
+                Datatype::from_type::<f64>().unwrap()
+            );
+            assert_eq!(file.attr("foo").unwrap().read_scalar::<f64>().unwrap(), -1.5);
+
+            // Scalar replaced with an array and back, keeping the distinction.
+            file.set_attr_array("foo", &arr2(&[[1_i16, 2], [3, 4]])).unwrap();
+            assert_eq!(file.attr("foo").unwrap().shape(), vec![2, 2]);
+            assert_eq!(
+                file.attr("foo").unwrap().read_2d::<i16>().unwrap(),
+                arr2(&[[1, 2], [3, 4]])
+            );
+            file.set_attr_array("foo", &[5_i16]).unwrap();
+            assert!(!file.attr("foo").unwrap().is_scalar());
+            assert_eq!(file.attr("foo").unwrap().shape(), vec![1]);
+            assert_eq!(file.attr("foo").unwrap().read_raw::<i16>().unwrap(), vec![5]);
+            file.set_attr("foo", &6_i16).unwrap();
+            assert!(file.attr("foo").unwrap().is_scalar());
+            assert_eq!(file.attr("foo").unwrap().read_scalar::<i16>().unwrap(), 6);
+
+            assert_eq!(file.attr_names().unwrap(), vec!["foo"]);
+            let attr = file.attr("foo").unwrap();
+            assert_err!(attr.set_attr("bar", &1), "cannot have attributes");
+        })
+    }
+
+    #[test]
+    pub fn test_write_attrs() {
+        with_tmp_file(|file| {
+            let mut attrs = HashMap::new();
+            attrs.insert("a".to_owned(), 1_u16);
+            attrs.insert("b".to_owned(), 2_u16);
+            file.write_attrs(&attrs).unwrap();
+            assert_eq!(file.attr_names().unwrap(), vec!["a", "b"]);
+            assert_eq!(file.attr("b").unwrap().read_scalar::<u16>().unwrap(), 2);
+
+            let ds = file.new_dataset::<u8>().create("ds", ()).unwrap();
+            let mut attrs = HashMap::new();
+            attrs.insert("count".to_owned(), AttrValue::from(3_i64));
+            attrs.insert("scale".to_owned(), AttrValue::from(0.25_f64));
+            attrs.insert("unit".to_owned(), AttrValue::from("m/s"));
+            attrs.insert("range".to_owned(), AttrValue::from(vec![-1_i64, 1]));
+            attrs.insert("weights".to_owned(), AttrValue::from(vec![0.5_f64, 1.5, 2.5]));
+            attrs.insert("tags".to_owned(), AttrValue::from(vec!["x".to_owned(), "y".to_owned()]));
+            ds.write_attr_values(&attrs).unwrap();
+
+            assert_eq!(ds.attr_names().unwrap().len(), 6);
+            assert_eq!(ds.attr("count").unwrap().read_scalar::<i64>().unwrap(), 3);
+            assert_eq!(ds.attr("scale").unwrap().read_scalar::<f64>().unwrap(), 0.25);
+            let unit = ds.attr("unit").unwrap().read_scalar::<VarLenUnicode>().unwrap();
+            assert_eq!(unit.as_str(), "m/s");
+            assert_eq!(ds.attr("range").unwrap().read_raw::<i64>().unwrap(), vec![-1, 1]);
+            assert_eq!(ds.attr("weights").unwrap().read_raw::<f64>().unwrap(), vec![0.5, 1.5, 2.5]);
+            let tags = ds.attr("tags").unwrap().read_raw::<VarLenUnicode>().unwrap();
+            assert_eq!(tags.iter().map(|s| s.as_str()).collect::<Vec<_>>(), vec!["x", "y"]);
+
+            // Attributes preceding the failing one are kept, the following ones are not written.
+            let mut attrs = HashMap::new();
+            attrs.insert("first".to_owned(), AttrValue::from(1_i64));
+            attrs.insert("second".to_owned(), AttrValue::from("a\0b"));
+            attrs.insert("third".to_owned(), AttrValue::from(3_i64));
+            assert_err!(file.write_attr_values(&attrs), "Cannot write attribute \"second\"");
+            assert!(file.attr_exists("first"));
+            assert!(!file.attr_exists("second"));
+            assert!(!file.attr_exists("third"));
+        })
+    }
+
+    #[test]
+    pub fn test_iter_attrs() {
+        with_tmp_file(|file| {
+            let ds =
+                file.new_dataset::<u8>().track_attr_creation_order(true).create("ds", 1).unwrap();
+            assert!(ds.attr_names().unwrap().is_empty());
+            ds.new_attr::<u32>().create("c", ()).unwrap().write_scalar(&1).unwrap();
+            ds.new_attr::<f64>().create("a", 2).unwrap().write(&[2., 3.]).unwrap();
+            ds.new_attr::<i8>().create("b", ()).unwrap().write_scalar(&-4).unwrap();
+            assert_eq!(ds.attr_names().unwrap(), vec!["a", "b", "c"]);
+
+            let attrs: Vec<_> = ds
+                .iter_attrs(IndexType::CreationOrder, IterationOrder::Increasing)
+                .unwrap()
+                .collect();
+            let names: Vec<_> = attrs.iter().map(|(name, _)| name.as_str()).collect();
+            assert_eq!(names, vec!["c", "a", "b"]);
+            assert_eq!(attrs[0].1.read_scalar::<u32>().unwrap(), 1);
+            assert_eq!(attrs[1].1.read_raw::<f64>().unwrap(), vec![2., 3.]);
+            assert_eq!(attrs[2].1.read_scalar::<i8>().unwrap(), -4);
+
+            let names: Vec<_> = ds
+                .iter_attrs(IndexType::Name, IterationOrder::Decreasing)
+                .unwrap()
+                .map(|(name, attr)| {
+                    assert_eq!(attr.attr_name(), name);
+                    name
+                })
+                .collect();
+            assert_eq!(names, vec!["c", "b", "a"]);
+
+            // Copy all attributes to another object generically.
+            let group = file.create_group("copy").unwrap();
+            for (name, attr) in ds.iter_attrs(IndexType::Name, IterationOrder::Increasing).unwrap()
+            {
+                let dtype = attr.dtype().unwrap();
+                assert!(dtype.is::<u32>() || dtype.is::<f64>() || dtype.is::<i8>());
+                if dtype.is::<u32>() {
+                    let value = attr.read_scalar::<u32>().unwrap();
+                    group
+                        .new_attr::<u32>()
+                        .create(&name, ())
+                        .unwrap()
+                        .write_scalar(&value)
+                        .unwrap();
+                }
+            }
+            assert_eq!(group.attr_names().unwrap(), vec!["c"]);
+
+            // Creation order is not tracked by default.
+            assert!(group
+                .iter_attrs(IndexType::CreationOrder, IterationOrder::Increasing)
+                .is_err());
+            let attr = ds.attr("a").unwrap();
+            assert_err!(attr.attr_names(), "cannot have attributes");
+        })
+    }
+}
@@ -1,19 +1,164 @@
 use std::fmt::{self, Debug};
 use std::mem;
 use std::ops::Deref;
+use std::ptr;
+#[cfg(feature = "io-stats")]
+use std::time::{Duration, Instant};
 
 use ndarray::{Array, Array1, Array2, ArrayD, ArrayView, ArrayView1};
 use ndarray::{SliceInfo, SliceOrIndex};
+use num_traits::Float;
 
 use hdf5_sys::h5a::{H5Aget_space, H5Aget_storage_size, H5Aget_type, H5Aread, H5Awrite};
-use hdf5_sys::h5d::{H5Dget_space, H5Dget_storage_size, H5Dget_type, H5Dread, H5Dwrite};
-
+use hdf5_sys::h5d::{
+    H5Dget_space, H5Dget_storage_size, H5Dget_type, H5Dread, H5Dvlen_reclaim, H5Dwrite,
+};
+use hdf5_sys::h5t::{
+    H5T_class_t, H5Tcreate, H5Tget_class, H5Tget_member_index, H5Tget_member_type, H5Tinsert,
+};
+use hdf5_types::{IntSize, TypeDescriptor};
+
+use crate::hl::plist::dataset_transfer::DatasetTransfer;
 use crate::internal_prelude::*;
 
+fn contains_enum(desc: &TypeDescriptor) -> bool {
+    match *desc {
+        TypeDescriptor::Enum(_) | TypeDescriptor::Boolean => true,
+        TypeDescriptor::Compound(ref tp) => tp.fields.iter().any(|f| contains_enum(&f.ty)),
+        TypeDescriptor::FixedArray(ref ty, _) => contains_enum(ty),
+        _ => false,
+    }
+}
+
+/// Checks that all enum values in a buffer of `count` elements are valid enum members
+/// (and that all booleans are either 0 or 1); the error names the index of the first
+/// offending element in the buffer.
+unsafe fn check_enum_values(desc: &TypeDescriptor, buf: *const u8, count: usize) -> Result<()> {
+    let size = desc.size();
+    for i in 0..count {
+        if let Err(err) = check_enum_value(desc, buf.add(i * size)) {
+            fail!("{} (at element {})", err, i);
+        }
+    }
+    Ok(())
+}
+
+unsafe fn check_enum_value(desc: &TypeDescriptor, ptr: *const u8) -> Result<()> {
+    match *desc {
+        TypeDescriptor::Enum(ref tp) => {
+            let value = match (tp.size, tp.signed) {
+                (IntSize::U1, true) => i64::from(ptr::read_unaligned(ptr as *const i8)) as u64,
+                (IntSize::U2, true) => i64::from(ptr::read_unaligned(ptr as *const i16)) as u64,
+                (IntSize::U4, true) => i64::from(ptr::read_unaligned(ptr as *const i32)) as u64,
+                (IntSize::U8, true) => ptr::read_unaligned(ptr as *const i64) as u64,
+                (IntSize::U1, false) => u64::from(ptr::read_unaligned(ptr as *const u8)),
+                (IntSize::U2, false) => u64::from(ptr::read_unaligned(ptr as *const u16)),
+                (IntSize::U4, false) => u64::from(ptr::read_unaligned(ptr as *const u32)),
+                (IntSize::U8, false) => ptr::read_unaligned(ptr as *const u64),
+            };
+            if !tp.members.iter().any(|m| m.value == value) {
+                let value = if tp.signed { (value as i64).to_string() } else { value.to_string() };
+                fail!("Invalid enum value: {} does not match any of the enum members", value);
+            }
+        }
+        TypeDescriptor::Boolean => {
+            let value = ptr::read(ptr);
+            if value > 1 {
+                fail!("Invalid boolean value: {} (expected 0 or 1)", value);
+            }
+        }
+        TypeDescriptor::Compound(ref tp) => {
+            for field in &tp.fields {
+                check_enum_value(&field.ty, ptr.add(field.offset))?;
+            }
+        }
+        TypeDescriptor::FixedArray(ref ty, n) => {
+            for j in 0..n {
+                check_enum_value(ty, ptr.add(j * ty.size()))?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Replaces the error reported by the library when data fails fletcher32 verification
+/// with a more descriptive one.
+fn checksum_error(err: Error) -> Error {
+    if let Error::HDF5(ref stack) = err {
+        if (0..stack.len()).any(|i| stack[i].desc().contains("Fletcher32 checksum")) {
+            return format!("fletcher32 checksum failed: {}", stack.description()).into();
+        }
+    }
+    err
+}
+
+/// Records a transfer of the selected elements that took `elapsed` time in the dataset
+/// I/O statistics.
+#[cfg(feature = "io-stats")]
+fn record_io(
+    obj: &Container, dtype: &Datatype, fspace: Option<&Dataspace>, mspace: Option<&Dataspace>,
+    elapsed: Duration, write: bool,
+) {
+    if !obj.is_attr() {
+        let count = match mspace.or(fspace) {
+            Some(space) => space.selection_size(),
+            None => obj.space().map(|space| space.size()).unwrap_or(0),
+        };
+        crate::hl::dataset::record_io(obj.id(), count * dtype.size(), elapsed, write);
+    }
+}
+
+/// Returns the in-memory datatype for `T`; unsigned integers are mapped to bitfields of the
+/// same size if the stored datatype is a bitfield, so that the raw bits are transferred, and
+/// nested arrays are mapped to a single array if the stored datatype is multi-dimensional.
+fn mem_dtype<T: H5Type>(file_dtype: &Datatype) -> Result<Datatype> {
+    let desc = T::type_descriptor();
+    if let Some(mem_dtype) = Datatype::nested_array_like(&desc, file_dtype)? {
+        return Ok(mem_dtype);
+    }
+    if let TypeDescriptor::Unsigned(size) = desc {
+        if let Ok(TypeDescriptor::Bitfield(file_size)) = file_dtype.to_descriptor() {
+            if file_size == size {
+                return Datatype::from_descriptor(&TypeDescriptor::Bitfield(size));
+            }
+        }
+    }
+    Datatype::from_descriptor(&desc)
+}
+
+/// Returns the stored datatype of a compound field along with the in-memory datatype for
+/// `F` and a compound memory datatype containing just that field (represented as `F`).
+fn field_dtypes<F: H5Type>(
+    file_dtype: &Datatype, name: &str,
+) -> Result<(Datatype, Datatype, Datatype)> {
+    let c_name = to_cstring(name)?;
+    let field_dtype = h5lock!({
+        ensure!(
+            H5Tget_class(file_dtype.id()) == H5T_class_t::H5T_COMPOUND,
+            "Cannot access field {:?}: not a compound datatype",
+            name
+        );
+        let index = H5Tget_member_index(file_dtype.id(), c_name.as_ptr());
+        ensure!(index >= 0, "Field {:?} does not exist", name);
+        Datatype::from_id(h5try!(H5Tget_member_type(file_dtype.id(), index as _)))
+    })?;
+    let field_mem_dtype = mem_dtype::<F>(&field_dtype)?;
+    let mem_dtype = h5lock!({
+        let id = h5try!(H5Tcreate(H5T_class_t::H5T_COMPOUND, mem::size_of::<F>()));
+        let mem_dtype = Datatype::from_id(id)?;
+        h5try!(H5Tinsert(id, c_name.as_ptr(), 0, field_mem_dtype.id()));
+        Ok(mem_dtype)
+    })?;
+    Ok((field_dtype, field_mem_dtype, mem_dtype))
+}
+
 #[derive(Debug)]
 pub struct Reader<'a> {
     obj: &'a Container,
     conv: Conversion,
+    policy: ConversionPolicy,
+    dxpl: Option<DatasetTransfer>,
 }
 
 impl<'a> Reader<'a> {
@@ -21,7 +166,7 @@ impl<'a> Reader<'a> {
     ///
     /// Any conversions (including hard/soft) are allowed by default.
     pub fn new(obj: &'a Container) -> Self {
-        Self { obj, conv: Conversion::Soft }
+        Self { obj, conv: Conversion::Soft, policy: ConversionPolicy::default(), dxpl: None }
     }
 
     /// Set maximum allowed conversion level.
@@ -36,21 +181,71 @@ impl<'a> Reader<'a> {
         self
     }
 
+    /// Set the policy restricting lossy conversions of the stored element type (by default,
+    /// any conversion is allowed).
+    pub fn policy(mut self, policy: ConversionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Set the data transfer property list used for reading datasets.
+    pub fn transfer_plist(mut self, dxpl: &DatasetTransfer) -> Self {
+        self.dxpl = Some(dxpl.clone());
+        self
+    }
+
+    /// A short alias for `transfer_plist()`.
+    pub fn dxpl(self, dxpl: &DatasetTransfer) -> Self {
+        self.transfer_plist(dxpl)
+    }
+
     fn read_into_buf<T: H5Type>(
         &self, buf: *mut T, fspace: Option<&Dataspace>, mspace: Option<&Dataspace>,
     ) -> Result<()> {
         let file_dtype = self.obj.dtype()?;
-        let mem_dtype = Datatype::from_type::<T>()?;
-        file_dtype.ensure_convertible(&mem_dtype, self.conv)?;
+        self.policy.ensure_allowed(&file_dtype, &T::type_descriptor())?;
+        let mem_dtype = mem_dtype::<T>(&file_dtype)?;
+        self.read_into_buf_as(&file_dtype, &mem_dtype, &T::type_descriptor(), buf, fspace, mspace)
+    }
+
+    /// Reads data into `buf` using the given memory datatype; `desc` describes the values
+    /// stored in the buffer (which may only be a part of the memory datatype's layout).
+    fn read_into_buf_as<T>(
+        &self, file_dtype: &Datatype, mem_dtype: &Datatype, desc: &TypeDescriptor, buf: *mut T,
+        fspace: Option<&Dataspace>, mspace: Option<&Dataspace>,
+    ) -> Result<()> {
+        file_dtype.ensure_convertible(mem_dtype, self.conv)?;
         let (obj_id, tp_id) = (self.obj.id(), mem_dtype.id());
 
         let fspace_id = fspace.map_or(H5S_ALL, |f| f.id());
         let mspace_id = mspace.map_or(H5S_ALL, |m| m.id());
+        let dxpl_id = self.dxpl.as_ref().map_or(H5P_DEFAULT, |p| p.id());
 
-        if self.obj.is_attr() {
-            h5try!(H5Aread(obj_id, tp_id, buf as *mut _));
+        #[cfg(feature = "io-stats")]
+        let start = Instant::now();
+        let result = if self.obj.is_attr() {
+            h5call!(H5Aread(obj_id, tp_id, buf as *mut _))
         } else {
-            h5try!(H5Dread(obj_id, tp_id, mspace_id, fspace_id, H5P_DEFAULT, buf as *mut _));
+            h5call!(H5Dread(obj_id, tp_id, mspace_id, fspace_id, dxpl_id, buf as *mut _))
+        };
+        result.map_err(checksum_error)?;
+        #[cfg(feature = "io-stats")]
+        record_io(self.obj, mem_dtype, fspace, mspace, start.elapsed(), false);
+
+        // Stored enum values that don't match any variant must never reach the caller.
+        if contains_enum(desc) {
+            let count = match mspace {
+                Some(mspace) => mspace.selection_size(),
+                None => self.obj.space()?.size(),
+            };
+            if let Err(err) = unsafe { check_enum_values(desc, buf as *const u8, count) } {
+                // The buffer is never handed out, so any variable-length data that the
+                // library has allocated while reading has to be released here.
+                let space = self.obj.space()?;
+                let space_id = mspace.or(fspace).map_or(space.id(), |s| s.id());
+                h5lock!(H5Dvlen_reclaim(tp_id, space_id, dxpl_id, buf as *mut _));
+                return Err(err);
+            }
         }
         Ok(())
     }
@@ -60,6 +255,10 @@ impl<'a> Reader<'a> {
     /// the slice, after singleton dimensions are dropped.
     /// Use the multi-dimensional slice macro `s![]` from `ndarray` to conveniently create
     /// a multidimensional slice.
+    ///
+    /// Point selections (`Selection::Points`) are not accepted here since the dimensionality
+    /// of the result is determined by the slice; use `read_selection()` to read them into
+    /// a 1-dimensional array in the order the points are listed.
     pub fn read_slice<T, S, D>(&self, slice: &SliceInfo<S, D>) -> Result<Array<T, D>>
     where
         T: H5Type,
@@ -127,21 +326,44 @@ impl<'a> Reader<'a> {
             let mspace = Dataspace::try_new(&out_shape, false)?;
             let size = out_shape.iter().product();
             let mut vec = Vec::with_capacity(size);
+            self.read_into_buf(vec.as_mut_ptr(), Some(&fspace), Some(&mspace))?;
             unsafe {
                 vec.set_len(size);
             }
 
-            self.read_into_buf(vec.as_mut_ptr(), Some(&fspace), Some(&mspace))?;
-
             let arr = ArrayD::from_shape_vec(reduced_shape, vec)?;
             Ok(arr.into_dimensionality()?)
         }
     }
 
+    /// Reads the elements included in the selection into a 1-dimensional array
+    /// (in the order the elements are traversed by HDF5; for point selections, this
+    /// is the order in which the points are listed).
+    pub fn read_selection<T, S>(&self, selection: S) -> Result<Array1<T>>
+    where
+        T: H5Type,
+        S: Into<Selection>,
+    {
+        ensure!(!self.obj.is_attr(), "selections cannot be used on attribute datasets");
+        let fspace = self.obj.space()?;
+        fspace.select(&selection.into())?;
+        let size = fspace.selection_size();
+        let mspace = Dataspace::try_new(size, false)?;
+        let mut vec = Vec::with_capacity(size);
+        self.read_into_buf(vec.as_mut_ptr(), Some(&fspace), Some(&mspace))?;
+        unsafe {
+            vec.set_len(size);
+        }
+        Ok(Array1::from(vec))
+    }
+
     /// Reads a dataset/attribute into an n-dimensional array.
     ///
     /// If the array has a fixed number of dimensions, it must match the dimensionality
     /// of the dataset/attribute.
+    ///
+    /// The element type may differ from the stored one (e.g. `f32` for a stored `f64`), in
+    /// which case the values are converted by the library, subject to the conversion level.
     pub fn read<T: H5Type, D: ndarray::Dimension>(&self) -> Result<Array<T, D>> {
         let shape = self.obj.get_shape()?;
         if let Some(ndim) = D::NDIM {
@@ -157,10 +379,107 @@ impl<'a> Reader<'a> {
     pub fn read_raw<T: H5Type>(&self) -> Result<Vec<T>> {
         let size = self.obj.space()?.size();
         let mut vec = Vec::with_capacity(size);
+        self.read_into_buf(vec.as_mut_ptr(), None, None)?;
         unsafe {
             vec.set_len(size);
         }
-        self.read_into_buf(vec.as_mut_ptr(), None, None).map(|_| vec)
+        Ok(vec)
+    }
+
+    /// Reads a single field of a compound dataset/attribute into an n-dimensional array.
+    ///
+    /// The memory datatype only contains the requested field, so the library transfers
+    /// just that field and no other fields are ever materialized in memory.
+    pub fn read_field<F: H5Type, D: ndarray::Dimension>(&self, name: &str) -> Result<Array<F, D>> {
+        let shape = self.obj.get_shape()?;
+        if let Some(ndim) = D::NDIM {
+            let obj_ndim = shape.ndim();
+            ensure!(obj_ndim == ndim, "ndim mismatch: expected {}, got {}", ndim, obj_ndim);
+        }
+        let file_dtype = self.obj.dtype()?;
+        let (field_dtype, field_mem_dtype, mem_dtype) = field_dtypes::<F>(&file_dtype, name)?;
+        field_dtype
+            .ensure_convertible(&field_mem_dtype, self.conv)
+            .and_then(|_| self.policy.ensure_allowed(&field_dtype, &F::type_descriptor()))
+            .map_err(|err| format!("Cannot read field {:?}: {}", name, err))?;
+
+        let size = shape.size();
+        let mut vec = Vec::with_capacity(size);
+        let desc = F::type_descriptor();
+        self.read_into_buf_as(&file_dtype, &mem_dtype, &desc, vec.as_mut_ptr(), None, None)?;
+        unsafe {
+            vec.set_len(size);
+        }
+        let arr = ArrayD::from_shape_vec(shape, vec)?;
+        Ok(arr.into_dimensionality()?)
+    }
+
+    /// Reads a dataset/attribute into a caller-provided buffer in memory order, without
+    /// allocating.
+    ///
+    /// The length of `buf` must be equal to the number of elements in the dataset/attribute.
+    /// If reading fails, the buffer may have been partially overwritten.
+    pub fn read_into<T: H5Type + Copy>(&self, buf: &mut [T]) -> Result<()> {
+        let size = self.obj.space()?.size();
+        ensure!(
+            buf.len() == size,
+            "length mismatch when reading: memory = {}, source = {}",
+            buf.len(),
+            size
+        );
+        self.read_into_slice(buf, None, None)
+    }
+
+    /// Reads a slice of the dataset into a caller-provided buffer in memory order, without
+    /// allocating.
+    ///
+    /// The length of `buf` must be equal to the number of elements selected by `slice`.
+    /// If reading fails, the buffer may have been partially overwritten.
+    pub fn read_slice_into<T, S, D>(&self, buf: &mut [T], slice: &SliceInfo<S, D>) -> Result<()>
+    where
+        T: H5Type + Copy,
+        S: AsRef<[SliceOrIndex]>,
+        D: ndarray::Dimension,
+    {
+        ensure!(!self.obj.is_attr(), "slicing cannot be used on attribute datasets");
+        let fspace = self.obj.space()?;
+        let (obj_ndim, slice_dim) = (fspace.ndim(), slice.as_ref().len());
+        ensure!(
+            obj_ndim == slice_dim,
+            "slice dimension mismatch: dataset has {} dims, slice has {} dims",
+            obj_ndim,
+            slice_dim
+        );
+        if obj_ndim == 0 {
+            return self.read_into(buf);
+        }
+        let out_shape = fspace.select_slice(slice)?;
+        let size: usize = out_shape.iter().product();
+        ensure!(
+            buf.len() == size,
+            "length mismatch when reading slice: memory = {}, selection = {}",
+            buf.len(),
+            size
+        );
+        let mspace = Dataspace::try_new(&out_shape, false)?;
+        self.read_into_slice(buf, Some(&fspace), Some(&mspace))
+    }
+
+    fn read_into_slice<T: H5Type + Copy>(
+        &self, buf: &mut [T], fspace: Option<&Dataspace>, mspace: Option<&Dataspace>,
+    ) -> Result<()> {
+        if contains_enum(&T::type_descriptor()) {
+            // Invalid enum values are only detected after they're read, so they must not be
+            // read into `buf` directly; the scratch buffer is only copied once it's validated.
+            let mut scratch: Vec<mem::MaybeUninit<T>> = Vec::with_capacity(buf.len());
+            self.read_into_buf(scratch.as_mut_ptr() as *mut T, fspace, mspace)?;
+            unsafe {
+                ptr::copy_nonoverlapping(scratch.as_ptr() as *const T, buf.as_mut_ptr(), buf.len());
+            }
+            Ok(())
+        } else {
+            self.read_into_buf(buf.as_mut_ptr(), fspace, mspace)
+        }
     }
 
     /// Reads a dataset/attribute into a 1-dimensional array.
@@ -215,6 +534,7 @@ impl<'a> Reader<'a> {
 pub struct Writer<'a> {
     obj: &'a Container,
     conv: Conversion,
+    dxpl: Option<DatasetTransfer>,
 }
 
 impl<'a> Writer<'a> {
@@ -222,7 +542,7 @@ impl<'a> Writer<'a> {
     ///
     /// Any conversions (including hard/soft) are allowed by default.
     pub fn new(obj: &'a Container) -> Self {
-        Self { obj, conv: Conversion::Soft }
+        Self { obj, conv: Conversion::Soft, dxpl: None }
     }
 
     /// Set maximum allowed conversion level.
@@ -237,21 +557,44 @@ impl<'a> Writer<'a> {
         self
     }
 
+    /// Set the data transfer property list used for writing datasets.
+    pub fn transfer_plist(mut self, dxpl: &DatasetTransfer) -> Self {
+        self.dxpl = Some(dxpl.clone());
+        self
+    }
+
+    /// A short alias for `transfer_plist()`.
+    pub fn dxpl(self, dxpl: &DatasetTransfer) -> Self {
+        self.transfer_plist(dxpl)
+    }
+
     fn write_from_buf<T: H5Type>(
         &self, buf: *const T, fspace: Option<&Dataspace>, mspace: Option<&Dataspace>,
     ) -> Result<()> {
         let file_dtype = self.obj.dtype()?;
-        let mem_dtype = Datatype::from_type::<T>()?;
-        mem_dtype.ensure_convertible(&file_dtype, self.conv)?;
+        let mem_dtype = mem_dtype::<T>(&file_dtype)?;
+        self.write_from_buf_as(&file_dtype, &mem_dtype, buf, fspace, mspace)
+    }
+
+    fn write_from_buf_as<T>(
+        &self, file_dtype: &Datatype, mem_dtype: &Datatype, buf: *const T,
+        fspace: Option<&Dataspace>, mspace: Option<&Dataspace>,
+    ) -> Result<()> {
+        mem_dtype.ensure_convertible(file_dtype, self.conv)?;
         let (obj_id, tp_id) = (self.obj.id(), mem_dtype.id());
 
         let fspace_id = fspace.map_or(H5S_ALL, |f| f.id());
         let mspace_id = mspace.map_or(H5S_ALL, |m| m.id());
+        let dxpl_id = self.dxpl.as_ref().map_or(H5P_DEFAULT, |p| p.id());
 
         if self.obj.is_attr() {
             h5try!(H5Awrite(obj_id, tp_id, buf as *const _));
         } else {
-            h5try!(H5Dwrite(obj_id, tp_id, mspace_id, fspace_id, H5P_DEFAULT, buf as *const _));
+            #[cfg(feature = "io-stats")]
+            let start = Instant::now();
+            h5try!(H5Dwrite(obj_id, tp_id, mspace_id, fspace_id, dxpl_id, buf as *const _));
+            #[cfg(feature = "io-stats")]
+            record_io(self.obj, mem_dtype, fspace, mspace, start.elapsed(), true);
         }
         Ok(())
     }
@@ -261,6 +604,9 @@ impl<'a> Writer<'a> {
     /// If the array has a fixed number of dimensions, it must match the dimensionality of
     /// dataset. Use the multi-dimensional slice macro `s![]` from `ndarray` to conveniently create
     /// a multidimensional slice.
+    ///
+    /// Point selections (`Selection::Points`) are not accepted here; use `write_selection()`
+    /// to scatter the elements of an array to the listed points instead.
     pub fn write_slice<'b, A, T, S, D>(&self, arr: A, slice: &SliceInfo<S, D>) -> Result<()>
     where
         A: Into<ArrayView<'b, T, D>>,
@@ -328,6 +674,34 @@ impl<'a> Writer<'a> {
         }
     }
 
+    /// Writes all elements of the array `arr` (in memory order) into the selected elements
+    /// of the target dataset; the number of elements must match the size of the selection.
+    pub fn write_selection<'b, A, T, D, S>(&self, arr: A, selection: S) -> Result<()>
+    where
+        A: Into<ArrayView<'b, T, D>>,
+        T: H5Type,
+        D: ndarray::Dimension,
+        S: Into<Selection>,
+    {
+        ensure!(!self.obj.is_attr(), "selections cannot be used on attribute datasets");
+        let view = arr.into();
+        ensure!(
+            view.is_standard_layout(),
+            "input array is not in standard layout or is not contiguous"
+        );
+        let fspace = self.obj.space()?;
+        fspace.select(&selection.into())?;
+        let size = fspace.selection_size();
+        ensure!(
+            view.len() == size,
+            "size mismatch when writing selection: memory = {}, selection = {}",
+            view.len(),
+            size
+        );
+        let mspace = Dataspace::try_new(size, false)?;
+        self.write_from_buf(view.as_ptr(), Some(&fspace), Some(&mspace))
+    }
+
     /// Writes an n-dimensional array view into a dataset/attribute.
     ///
     /// The shape of the view must match the shape of the dataset/attribute exactly.
@@ -353,6 +727,67 @@ impl<'a> Writer<'a> {
         self.write_from_buf(view.as_ptr(), None, None)
     }
 
+    /// Writes an n-dimensional array view of floats like `write()`, storing a given finite
+    /// `sentinel` in place of NaN and infinite values; returns the number of replaced values.
+    ///
+    /// The input array is not modified, it's only copied if it contains non-finite values.
+    ///
+    /// Note that this is not available as a `DatasetTransferBuilder` option: a transfer
+    /// property list is owned by the HDF5 library, which has no notion of substituting
+    /// values, so it can neither carry the sentinel nor report back the number of
+    /// replacements.
+    pub fn write_finite<'b, A, T, D>(&self, arr: A, sentinel: T) -> Result<usize>
+    where
+        A: Into<ArrayView<'b, T, D>>,
+        T: H5Type + Float,
+        D: ndarray::Dimension,
+    {
+        ensure!(sentinel.is_finite(), "Sentinel value must be finite");
+        let view = arr.into();
+        let replaced = view.iter().filter(|x| !x.is_finite()).count();
+        if replaced == 0 {
+            self.write(view)?;
+        } else {
+            let arr = view.mapv(|x| if x.is_finite() { x } else { sentinel });
+            self.write(&arr)?;
+        }
+        Ok(replaced)
+    }
+
+    /// Writes an n-dimensional array view into a single field of a compound dataset,
+    /// leaving all other fields of the stored records unchanged.
+    ///
+    /// The shape of the view must match the shape of the dataset exactly.
+    pub fn write_field<'b, A, F, D>(&self, name: &str, arr: A) -> Result<()>
+    where
+        A: Into<ArrayView<'b, F, D>>,
+        F: H5Type,
+        D: ndarray::Dimension,
+    {
+        // Attributes are written without reading back the stored data, so the other
+        // fields would be overwritten with zeros.
+        ensure!(!self.obj.is_attr(), "single fields cannot be written to attributes");
+        let view = arr.into();
+        ensure!(
+            view.is_standard_layout(),
+            "input array is not in standard layout or is not contiguous"
+        );
+
+        let src = view.shape();
+        let dst = &*self.obj.get_shape()?;
+        if src != dst {
+            fail!("shape mismatch when writing: memory = {:?}, destination = {:?}", src, dst);
+        }
+
+        let file_dtype = self.obj.dtype()?;
+        let (field_dtype, field_mem_dtype, mem_dtype) = field_dtypes::<F>(&file_dtype, name)?;
+        field_mem_dtype
+            .ensure_convertible(&field_dtype, self.conv)
+            .map_err(|err| format!("Cannot write field {:?}: {}", name, err))?;
+        // The library fills in the remaining fields from the stored records.
+        self.write_from_buf_as(&file_dtype, &mem_dtype, view.as_ptr(), None, None)
+    }
+
     /// Writes a 1-dimensional array view into a dataset/attribute in memory order.
     ///
     /// The number of elements in the view must match the number of elements in the
@@ -490,6 +925,9 @@ impl Container {
     ///
     /// If the array has a fixed number of dimensions, it must match the dimensionality
     /// of the dataset/attribute.
+    ///
+    /// The element type may differ from the stored one (e.g. `f32` for a stored `f64`), in
+    /// which case the values are converted by the library, subject to the conversion level.
     pub fn read<T: H5Type, D: ndarray::Dimension>(&self) -> Result<Array<T, D>> {
         self.as_reader().read()
     }
@@ -499,6 +937,31 @@ impl Container {
         self.as_reader().read_raw()
     }
 
+    /// Reads a dataset/attribute into a caller-provided buffer in memory order.
+    ///
+    /// The length of `buf` must be equal to the number of elements in the dataset/attribute.
+    pub fn read_into<T: H5Type + Copy>(&self, buf: &mut [T]) -> Result<()> {
+        self.as_reader().read_into(buf)
+    }
+
+    /// Reads a single field of a compound dataset/attribute into an n-dimensional array,
+    /// without transferring any other fields.
+    pub fn read_field<F: H5Type, D: ndarray::Dimension>(&self, name: &str) -> Result<Array<F, D>> {
+        self.as_reader().read_field(name)
+    }
+
+    /// Reads the given `slice` of the dataset into a caller-provided buffer in memory order.
+    ///
+    /// The length of `buf` must be equal to the number of elements selected by `slice`.
+    pub fn read_slice_into<T, S, D>(&self, buf: &mut [T], slice: &SliceInfo<S, D>) -> Result<()>
+    where
+        T: H5Type + Copy,
+        S: AsRef<[SliceOrIndex]>,
+        D: ndarray::Dimension,
+    {
+        self.as_reader().read_slice_into(buf, slice)
+    }
+
     /// Reads a dataset/attribute into a 1-dimensional array.
     ///
     /// The dataset/attribute must be 1-dimensional.
@@ -506,6 +969,15 @@ impl Container {
         self.as_reader().read_1d()
     }
 
+    /// Reads the elements included in the selection into a 1-dimensional array.
+    pub fn read_selection<T, S>(&self, selection: S) -> Result<Array1<T>>
+    where
+        T: H5Type,
+        S: Into<Selection>,
+    {
+        self.as_reader().read_selection(selection)
+    }
+
     /// Reads the given `slice` of the dataset into a 1-dimensional array.
     /// The slice must yield a 1-dimensional result.
     pub fn read_slice_1d<T, S>(&self, slice: &SliceInfo<S, ndarray::Ix1>) -> Result<Array1<T>>
@@ -543,6 +1015,10 @@ impl Container {
     /// the slice, after singleton dimensions are dropped.
     /// Use the multi-dimensional slice macro `s![]` from `ndarray` to conveniently create
     /// a multidimensional slice.
+    ///
+    /// Point selections (`Selection::Points`) are not accepted here since the dimensionality
+    /// of the result is determined by the slice; use `read_selection()` to read them into
+    /// a 1-dimensional array in the order the points are listed.
     pub fn read_slice<T, S, D>(&self, slice: &SliceInfo<S, D>) -> Result<Array<T, D>>
     where
         T: H5Type,
@@ -570,6 +1046,28 @@ impl Container {
         self.as_writer().write(arr)
     }
 
+    /// Writes an n-dimensional array view of floats, replacing non-finite values with a
+    /// sentinel, see `Writer::write_finite()`.
+    pub fn write_finite<'b, A, T, D>(&self, arr: A, sentinel: T) -> Result<usize>
+    where
+        A: Into<ArrayView<'b, T, D>>,
+        T: H5Type + Float,
+        D: ndarray::Dimension,
+    {
+        self.as_writer().write_finite(arr, sentinel)
+    }
+
+    /// Writes an n-dimensional array view into a single field of a compound dataset,
+    /// leaving all other fields of the stored records unchanged.
+    pub fn write_field<'b, A, F, D>(&self, name: &str, arr: A) -> Result<()>
+    where
+        A: Into<ArrayView<'b, F, D>>,
+        F: H5Type,
+        D: ndarray::Dimension,
+    {
+        self.as_writer().write_field(name, arr)
+    }
+
     /// Writes a 1-dimensional array view into a dataset/attribute in memory order.
     ///
     /// The number of elements in the view must match the number of elements in the
@@ -583,11 +1081,26 @@ impl Container {
         self.as_writer().write_raw(arr)
     }
 
+    /// Writes all elements of the array `arr` (in memory order) into the selected elements
+    /// of the target dataset.
+    pub fn write_selection<'b, A, T, D, S>(&self, arr: A, selection: S) -> Result<()>
+    where
+        A: Into<ArrayView<'b, T, D>>,
+        T: H5Type,
+        D: ndarray::Dimension,
+        S: Into<Selection>,
+    {
+        self.as_writer().write_selection(arr, selection)
+    }
+
     /// Writes all data from the array `arr` into the given `slice` of the target dataset.
     /// The shape of `arr` must match the shape the set of elements included in the slice.
     /// If the array has a fixed number of dimensions, it must match the dimensionality of
     /// dataset. Use the multi-dimensional slice macro `s![]` from `ndarray` to conveniently create
     /// a multidimensional slice.
+    ///
+    /// Point selections (`Selection::Points`) are not accepted here; use `write_selection()`
+    /// to scatter the elements of an array to the listed points instead.
     pub fn write_slice<'b, A, T, S, D>(&self, arr: A, slice: &SliceInfo<S, D>) -> Result<()>
     where
         A: Into<ArrayView<'b, T, D>>,
@@ -1,21 +1,61 @@
 use std::fmt::{self, Debug};
+use std::marker::PhantomData;
 use std::mem;
-use std::ops::Deref;
+use std::ops::{Deref, Range};
+use std::str;
+#[cfg(hdf5_1_10_5)]
+use std::vec;
 
+use ndarray::{Array1, ArrayView, SliceInfo, SliceOrIndex};
 use num_integer::div_floor;
 
 use hdf5_sys::{
     h5::HADDR_UNDEF,
     h5d::{
-        H5D_fill_value_t, H5D_layout_t, H5Dcreate2, H5Dcreate_anon, H5Dget_create_plist,
-        H5Dget_offset, H5Dset_extent, H5D_FILL_TIME_ALLOC,
+        H5D_alloc_time_t, H5D_fill_time_t, H5D_fill_value_t, H5D_layout_t, H5D_space_status_t,
+        H5Dcreate2, H5Dcreate_anon, H5Dget_access_plist, H5Dget_create_plist, H5Dget_offset,
+        H5Dget_space_status, H5Dread, H5Dset_extent, H5Dvlen_reclaim, H5Dwrite,
+        H5D_FILL_TIME_ALLOC,
     },
     h5p::{
-        H5Pcreate, H5Pfill_value_defined, H5Pget_chunk, H5Pget_fill_value, H5Pget_layout,
-        H5Pget_obj_track_times, H5Pset_chunk, H5Pset_create_intermediate_group, H5Pset_fill_time,
-        H5Pset_fill_value, H5Pset_obj_track_times,
+        H5Pcreate, H5Pfill_value_defined, H5Pget_alloc_time, H5Pget_chunk, H5Pget_fill_time,
+        H5Pget_fill_value, H5Pget_layout, H5Pget_obj_track_times, H5Pset_alloc_time, H5Pset_chunk,
+        H5Pset_create_intermediate_group, H5Pset_fill_time, H5Pset_fill_value, H5Pset_layout,
+        H5Pset_obj_track_times,
     },
+    h5r::{H5Rcreate, H5R_DATASET_REGION},
 };
+use hdf5_types::{RegionReference, TypeDescriptor, VarLenAscii, VarLenUnicode};
+
+use crate::hl::attribute::{copy_attr, set_attr_creation_order};
+use crate::hl::plist::dataset_access::{DatasetAccess, DatasetAccessBuilder};
+use crate::hl::validation::read_element;
+
+#[cfg(hdf5_1_10_0)]
+use hdf5_sys::h5d::H5D_CHUNK_DONT_FILTER_PARTIAL_CHUNKS;
+#[cfg(hdf5_1_10_0)]
+use hdf5_sys::h5d::{H5Dflush, H5Drefresh};
+#[cfg(hdf5_1_10_5)]
+use hdf5_sys::h5d::{H5Dget_chunk_info, H5Dget_num_chunks};
+#[cfg(hdf5_1_10_2)]
+use hdf5_sys::h5d::{H5Dget_chunk_storage_size, H5Dread_chunk, H5Dwrite_chunk};
+#[cfg(hdf5_1_10_0)]
+use hdf5_sys::h5p::{
+    H5Pget_chunk_opts, H5Pget_virtual_count, H5Pget_virtual_dsetname, H5Pget_virtual_filename,
+    H5Pget_virtual_srcspace, H5Pget_virtual_vspace, H5Pset_chunk_opts, H5Pset_virtual,
+};
+#[cfg(hdf5_1_10_5)]
+use ndarray::ArrayD;
+
+#[cfg(feature = "io-stats")]
+use std::collections::HashMap;
+#[cfg(feature = "io-stats")]
+use std::time::Duration;
+
+#[cfg(feature = "io-stats")]
+use lazy_static::lazy_static;
+#[cfg(feature = "io-stats")]
+use parking_lot::Mutex;
 
 use crate::globals::H5P_LINK_CREATE;
 use crate::internal_prelude::*;
@@ -62,12 +102,239 @@ pub enum Chunk {
     Manual(Vec<Ix>),
 }
 
+/// Maximum size in bytes of the raw data of a dataset with compact layout.
+pub const COMPACT_MAX_SIZE: usize = 64 * 1024;
+
+/// Storage layout of the dataset raw data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// Raw data is stored in a single contiguous block in the file.
+    Contiguous,
+    /// Raw data is stored in chunks of a given shape.
+    Chunked(Vec<Ix>),
+    /// Raw data is stored within the object header (only suitable for small datasets).
+    Compact,
+    /// Raw data is mapped from other datasets (requires HDF5 1.10.0).
+    Virtual,
+}
+
+/// Storage options of chunked datasets (requires HDF5 1.10.0).
+#[cfg(hdf5_1_10_0)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChunkOpts {
+    /// Store partial edge chunks (those not fully within the dataset extent) unfiltered,
+    /// which avoids filtering them again each time the dataset is extended.
+    pub dont_filter_partial_edge: bool,
+}
+
+#[cfg(hdf5_1_10_0)]
+impl ChunkOpts {
+    fn to_raw(self) -> c_uint {
+        if self.dont_filter_partial_edge {
+            H5D_CHUNK_DONT_FILTER_PARTIAL_CHUNKS
+        } else {
+            0
+        }
+    }
+
+    fn from_raw(opts: c_uint) -> Self {
+        Self { dont_filter_partial_edge: opts & H5D_CHUNK_DONT_FILTER_PARTIAL_CHUNKS != 0 }
+    }
+}
+
+/// Mapping of a selection within a virtual dataset onto a selection within a source
+/// dataset (requires HDF5 1.10.0).
+///
+/// The source file name may be `"."` to refer to the file containing the virtual dataset.
+/// Both selections must contain the same number of elements; by default, the entire
+/// virtual dataset and the entire source dataset are selected.
+#[cfg(hdf5_1_10_0)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VirtualMapping {
+    pub vds_selection: Selection,
+    pub src_file: String,
+    pub src_dataset: String,
+    pub src_shape: Vec<Ix>,
+    pub src_selection: Selection,
+}
+
+#[cfg(hdf5_1_10_0)]
+impl VirtualMapping {
+    /// Creates a mapping onto a source dataset of a given shape.
+    pub fn new<D: Dimension>(src_file: &str, src_dataset: &str, src_shape: D) -> Self {
+        Self {
+            vds_selection: Selection::All,
+            src_file: src_file.into(),
+            src_dataset: src_dataset.into(),
+            src_shape: src_shape.dims(),
+            src_selection: Selection::All,
+        }
+    }
+
+    /// Sets the selection within the virtual dataset.
+    pub fn vds_selection<S: Into<Selection>>(mut self, selection: S) -> Self {
+        self.vds_selection = selection.into();
+        self
+    }
+
+    /// Sets the selection within the source dataset.
+    pub fn src_selection<S: Into<Selection>>(mut self, selection: S) -> Self {
+        self.src_selection = selection.into();
+        self
+    }
+
+    fn apply<D: Dimension>(&self, dcpl_id: hid_t, vds_shape: D) -> Result<()> {
+        let vspace = Dataspace::try_new(vds_shape, false)?;
+        vspace.select(&self.vds_selection)?;
+        let src_space = Dataspace::try_new(&self.src_shape, false)?;
+        src_space.select(&self.src_selection)?;
+        ensure!(
+            vspace.selection_size() == src_space.selection_size(),
+            "Virtual mapping selection size mismatch: {} elements in the virtual dataset, \
+             {} elements in {:?}",
+            vspace.selection_size(),
+            src_space.selection_size(),
+            self.src_dataset
+        );
+        let src_file = to_cstring(self.src_file.as_str())?;
+        let src_dataset = to_cstring(self.src_dataset.as_str())?;
+        h5call!(H5Pset_virtual(
+            dcpl_id,
+            vspace.id(),
+            src_file.as_ptr(),
+            src_dataset.as_ptr(),
+            src_space.id()
+        ))
+        .and(Ok(()))
+    }
+}
+
+/// Allocation status of the dataset storage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpaceStatus {
+    /// No storage has been allocated yet.
+    NotAllocated,
+    /// Storage has been allocated for some but not all chunks.
+    PartlyAllocated,
+    /// All storage needed by the dataset has been allocated.
+    Allocated,
+}
+
+/// Time when fill values are written to the allocated dataset storage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FillTime {
+    /// Fill values are written at allocation time only if a fill value has been set.
+    IfSet,
+    /// Fill values are always written at allocation time.
+    Alloc,
+    /// Fill values are never written.
+    Never,
+}
+
+impl From<H5D_fill_time_t> for FillTime {
+    fn from(fill_time: H5D_fill_time_t) -> Self {
+        match fill_time {
+            H5D_fill_time_t::H5D_FILL_TIME_ALLOC => FillTime::Alloc,
+            H5D_fill_time_t::H5D_FILL_TIME_NEVER => FillTime::Never,
+            _ => FillTime::IfSet,
+        }
+    }
+}
+
+impl From<FillTime> for H5D_fill_time_t {
+    fn from(fill_time: FillTime) -> Self {
+        match fill_time {
+            FillTime::IfSet => H5D_fill_time_t::H5D_FILL_TIME_IFSET,
+            FillTime::Alloc => H5D_fill_time_t::H5D_FILL_TIME_ALLOC,
+            FillTime::Never => H5D_fill_time_t::H5D_FILL_TIME_NEVER,
+        }
+    }
+}
+
+/// Time when storage space is allocated for the dataset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AllocTime {
+    /// Depends on the layout: early for compact, late for contiguous and incremental
+    /// for chunked datasets.
+    Default,
+    /// All space is allocated when the dataset is created.
+    Early,
+    /// Space for each chunk is allocated when data is first written to it.
+    Incremental,
+    /// All space is allocated when data is first written to the dataset.
+    Late,
+}
+
+impl From<H5D_alloc_time_t> for AllocTime {
+    fn from(alloc_time: H5D_alloc_time_t) -> Self {
+        match alloc_time {
+            H5D_alloc_time_t::H5D_ALLOC_TIME_EARLY => AllocTime::Early,
+            H5D_alloc_time_t::H5D_ALLOC_TIME_INCR => AllocTime::Incremental,
+            H5D_alloc_time_t::H5D_ALLOC_TIME_LATE => AllocTime::Late,
+            _ => AllocTime::Default,
+        }
+    }
+}
+
+impl From<AllocTime> for H5D_alloc_time_t {
+    fn from(alloc_time: AllocTime) -> Self {
+        match alloc_time {
+            AllocTime::Default => H5D_alloc_time_t::H5D_ALLOC_TIME_DEFAULT,
+            AllocTime::Early => H5D_alloc_time_t::H5D_ALLOC_TIME_EARLY,
+            AllocTime::Incremental => H5D_alloc_time_t::H5D_ALLOC_TIME_INCR,
+            AllocTime::Late => H5D_alloc_time_t::H5D_ALLOC_TIME_LATE,
+        }
+    }
+}
+
+/// Options controlling how a dataset is rewritten via `Dataset::repack()`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RepackOptions {
+    chunk: Option<Vec<Ix>>,
+    filters: Filters,
+}
+
+impl RepackOptions {
+    /// Creates a new set of repack options (chunk shape kept as is, no filters).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the chunk shape of the new dataset.
+    pub fn chunk<D: Dimension>(&mut self, chunk: D) -> &mut Self {
+        self.chunk = Some(chunk.dims());
+        self
+    }
+
+    /// Sets the filters of the new dataset.
+    pub fn filters(&mut self, filters: &Filters) -> &mut Self {
+        self.filters = filters.clone();
+        self
+    }
+
+    pub fn get_chunk(&self) -> Option<&[Ix]> {
+        self.chunk.as_ref().map(Vec::as_slice)
+    }
+
+    pub fn get_filters(&self) -> &Filters {
+        &self.filters
+    }
+}
+
 impl Dataset {
     /// Returns whether this dataset is resizable along some axis.
     pub fn is_resizable(&self) -> bool {
         h5lock!(self.space().ok().map_or(false, |s| s.resizable()))
     }
 
+    /// Returns the maximum shape of the dataset, with `None` for unlimited axes.
+    ///
+    /// The current shape is returned by `shape()`.
+    pub fn max_extent(&self) -> Vec<Option<Ix>> {
+        let maxdims = h5lock!(self.space().map(|s| s.maxdims()).unwrap_or_default());
+        maxdims.into_iter().map(|x| if x == H5S_UNLIMITED as Ix { None } else { Some(x) }).collect()
+    }
+
     /// Returns whether this dataset has a chunked layout.
     pub fn is_chunked(&self) -> bool {
         h5lock!({
@@ -77,6 +344,55 @@ impl Dataset {
         })
     }
 
+    /// Returns the storage layout of the dataset (or contiguous if it can't be determined).
+    pub fn layout(&self) -> Layout {
+        h5lock!({
+            let layout = self
+                .dcpl_id()
+                .ok()
+                .map_or(H5D_layout_t::H5D_LAYOUT_ERROR, |dcpl_id| H5Pget_layout(dcpl_id));
+            match layout {
+                H5D_layout_t::H5D_COMPACT => Layout::Compact,
+                H5D_layout_t::H5D_CHUNKED => Layout::Chunked(self.chunks().unwrap_or_default()),
+                #[cfg(hdf5_1_10_0)]
+                H5D_layout_t::H5D_VIRTUAL => Layout::Virtual,
+                _ => Layout::Contiguous,
+            }
+        })
+    }
+
+    /// Returns the mappings of a virtual dataset onto its source datasets, in the order
+    /// they were added (requires HDF5 1.10.0).
+    ///
+    /// The result is empty if the dataset doesn't use virtual layout.
+    #[cfg(hdf5_1_10_0)]
+    pub fn virtual_maps(&self) -> Result<Vec<VirtualMapping>> {
+        if self.layout() != Layout::Virtual {
+            return Ok(Vec::new());
+        }
+        h5lock!({
+            let dcpl = PropertyList::from_id(h5try!(H5Dget_create_plist(self.id())))?;
+            let mut count: size_t = 0;
+            h5try!(H5Pget_virtual_count(dcpl.id(), &mut count));
+            (0..count)
+                .map(|i| {
+                    let vspace = Dataspace::from_id(h5try!(H5Pget_virtual_vspace(dcpl.id(), i)))?;
+                    let src_space =
+                        Dataspace::from_id(h5try!(H5Pget_virtual_srcspace(dcpl.id(), i)))?;
+                    Ok(VirtualMapping {
+                        vds_selection: vspace.get_selection()?,
+                        src_file: get_h5_str(|m, s| H5Pget_virtual_filename(dcpl.id(), i, m, s))?,
+                        src_dataset: get_h5_str(|m, s| {
+                            H5Pget_virtual_dsetname(dcpl.id(), i, m, s)
+                        })?,
+                        src_shape: src_space.dims(),
+                        src_selection: src_space.get_selection()?,
+                    })
+                })
+                .collect()
+        })
+    }
+
     /// Returns the chunk shape if the dataset is chunked.
     pub fn chunks(&self) -> Option<Vec<Ix>> {
         h5lock!({
@@ -96,6 +412,28 @@ impl Dataset {
         })
     }
 
+    /// Reads the chunks containing the given element offsets into the chunk cache, so that
+    /// subsequent reads of them don't have to fetch and decode them from storage.
+    ///
+    /// This is only a hint and doesn't affect the data read: chunks are only kept if they
+    /// fit into the chunk cache (see `DatasetAccessBuilder::chunk_cache()`) and may be
+    /// evicted by other reads. There's no benefit in prefetching from other threads since
+    /// all the calls into the library are serialized.
+    pub fn prefetch_chunks(&self, offsets: &[Vec<Ix>]) -> Result<()> {
+        ensure!(self.is_chunked(), "Chunk prefetching requires a chunked dataset");
+        let (dtype, shape) = (self.dtype()?, self.shape());
+        for offset in offsets {
+            ensure!(
+                offset.len() == shape.len() && offset.iter().zip(&shape).all(|(&i, &n)| i < n),
+                "Chunk offset {:?} is out of bounds for shape {:?}",
+                offset,
+                shape
+            );
+            read_element(self, &dtype, offset)?;
+        }
+        Ok(())
+    }
+
     /// Returns the filters used to create the dataset.
     pub fn filters(&self) -> Filters {
         h5lock!({
@@ -105,6 +443,33 @@ impl Dataset {
         .unwrap_or_else(|_: crate::error::Error| Filters::default())
     }
 
+    /// Returns the filter pipeline of the dataset, in the order the filters are applied
+    /// when writing.
+    ///
+    /// Unlike `filters()`, this also includes filters unknown to this crate (as
+    /// `Filter::User`), along with their parameters.
+    pub fn filter_pipeline(&self) -> Result<Vec<Filter>> {
+        h5lock!({
+            let dcpl = PropertyList::from_id(H5Dget_create_plist(self.id()))?;
+            Filter::from_dcpl(&dcpl)
+        })
+    }
+
+    /// Returns the chunk storage options, or `None` if the dataset is not chunked (requires
+    /// HDF5 1.10.0).
+    #[cfg(hdf5_1_10_0)]
+    pub fn chunk_opts(&self) -> Option<ChunkOpts> {
+        if !self.is_chunked() {
+            return None;
+        }
+        h5lock!({
+            let dcpl = PropertyList::from_id(H5Dget_create_plist(self.id())).ok()?;
+            let mut opts: c_uint = 0;
+            h5call!(H5Pget_chunk_opts(dcpl.id(), &mut opts)).ok()?;
+            Some(ChunkOpts::from_raw(opts))
+        })
+    }
+
     /// Returns `true` if object modification time is tracked by the dataset.
     pub fn tracks_times(&self) -> bool {
         h5lock!({
@@ -117,7 +482,12 @@ impl Dataset {
     }
 
     /// Returns the absolute byte offset of the dataset in the file if such offset is defined
-    /// (which is not the case for datasets that are chunked, compact or not allocated yet).
+    /// (which is not the case for datasets that are chunked, compact, virtual or not allocated
+    /// yet).
+    ///
+    /// For contiguous datasets, the raw elements are stored at this offset in the stored
+    /// datatype and byte order, so they can be read or memory-mapped directly from the file
+    /// (e.g. via `File::filename()`) once the file has been flushed.
     pub fn offset(&self) -> Option<u64> {
         let offset: haddr_t = h5lock!(H5Dget_offset(self.id()));
         if offset == HADDR_UNDEF {
@@ -150,10 +520,56 @@ impl Dataset {
         })
     }
 
+    /// Returns the time when fill values are written to the dataset storage.
+    pub fn fill_time(&self) -> Result<FillTime> {
+        h5lock!({
+            let mut fill_time = H5D_fill_time_t::H5D_FILL_TIME_ERROR;
+            h5try!(H5Pget_fill_time(self.dcpl_id()?, &mut fill_time));
+            Ok(fill_time.into())
+        })
+    }
+
+    /// Returns the time when storage space is allocated for the dataset.
+    pub fn alloc_time(&self) -> Result<AllocTime> {
+        h5lock!({
+            let mut alloc_time = H5D_alloc_time_t::H5D_ALLOC_TIME_ERROR;
+            h5try!(H5Pget_alloc_time(self.dcpl_id()?, &mut alloc_time));
+            Ok(alloc_time.into())
+        })
+    }
+
+    /// Returns the allocation status of the dataset storage.
+    pub fn space_status(&self) -> Result<SpaceStatus> {
+        let mut status = H5D_space_status_t::H5D_SPACE_STATUS_ERROR;
+        h5call!(H5Dget_space_status(self.id(), &mut status))?;
+        match status {
+            H5D_space_status_t::H5D_SPACE_STATUS_NOT_ALLOCATED => Ok(SpaceStatus::NotAllocated),
+            H5D_space_status_t::H5D_SPACE_STATUS_PART_ALLOCATED => Ok(SpaceStatus::PartlyAllocated),
+            H5D_space_status_t::H5D_SPACE_STATUS_ALLOCATED => Ok(SpaceStatus::Allocated),
+            _ => fail!("Invalid dataset space status"),
+        }
+    }
+
+    /// Returns a copy of the dataset access property list.
+    pub fn access_plist(&self) -> Result<DatasetAccess> {
+        h5lock!(DatasetAccess::from_id(h5try!(H5Dget_access_plist(self.id()))))
+    }
+
+    /// A short alias for `access_plist()`.
+    pub fn dapl(&self) -> Result<DatasetAccess> {
+        self.access_plist()
+    }
+
     fn dcpl_id(&self) -> Result<hid_t> {
         h5call!(H5Dget_create_plist(self.id()))
     }
 
+    /// Changes the extent of the dataset, keeping the elements at their coordinates.
+    ///
+    /// HDF5 can change neither the number of dimensions of a dataset nor the extent of a
+    /// contiguous one, so a dataset can't be reshaped in place; to reinterpret its shape
+    /// (e.g. to flatten it), read the elements in row-major order via `read_raw()` and
+    /// use `Array::from_shape_vec()` or write them into a new dataset.
     pub fn resize<D: Dimension>(&self, d: D) -> Result<()> {
         let mut dims: Vec<hsize_t> = vec![];
         for dim in &d.dims() {
@@ -162,6 +578,514 @@ impl Dataset {
         h5try!(H5Dset_extent(self.id(), dims.as_ptr()));
         Ok(())
     }
+
+    /// Appends an array to the dataset along a given axis, extending the dataset to fit it.
+    ///
+    /// The dataset must be extendible along `axis` (i.e., chunked with a large enough or
+    /// unlimited max dimension), and all other dimensions of the array must match those
+    /// of the dataset.
+    pub fn append<'b, A, T, D>(&self, axis: usize, arr: A) -> Result<()>
+    where
+        A: Into<ArrayView<'b, T, D>>,
+        T: H5Type,
+        D: ndarray::Dimension,
+    {
+        let view = arr.into();
+        let space = self.space()?;
+        let (shape, maxdims) = (space.dims(), space.maxdims());
+        let ndim = shape.len();
+        ensure!(
+            view.ndim() == ndim,
+            "ndim mismatch when appending: dataset has {} dims, array has {} dims",
+            ndim,
+            view.ndim()
+        );
+        ensure!(
+            axis < ndim,
+            "Invalid axis {} for appending to a {}-dimensional dataset",
+            axis,
+            ndim
+        );
+        for i in (0..ndim).filter(|&i| i != axis) {
+            ensure!(
+                view.shape()[i] == shape[i],
+                "shape mismatch when appending along axis {}: memory = {:?}, dataset = {:?}",
+                axis,
+                view.shape(),
+                shape
+            );
+        }
+        let (start, end) = (shape[axis], shape[axis] + view.shape()[axis]);
+        ensure!(
+            maxdims[axis] == H5S_UNLIMITED as Ix || end <= maxdims[axis],
+            "Cannot append along axis {}: dataset is not extendible beyond {} (requested {})",
+            axis,
+            maxdims[axis],
+            end
+        );
+        if start == end {
+            return Ok(());
+        }
+        let mut new_shape = shape;
+        new_shape[axis] = end;
+        self.resize(new_shape)?;
+        let slice: Vec<SliceOrIndex> = (0..ndim)
+            .map(|i| {
+                if i == axis {
+                    SliceOrIndex::Slice { start: start as _, end: Some(end as _), step: 1 }
+                } else {
+                    SliceOrIndex::Slice { start: 0, end: None, step: 1 }
+                }
+            })
+            .collect();
+        self.write_slice(view, &SliceInfo::<_, D>::new(slice)?)
+    }
+
+    /// Creates a region reference pointing to the selected elements of the dataset.
+    pub fn region_ref<S: Into<Selection>>(&self, selection: S) -> Result<RegionReference> {
+        let space = self.space()?;
+        space.select(&selection.into())?;
+        let name = to_cstring(".")?;
+        let mut reference = RegionReference::default();
+        h5try!(H5Rcreate(
+            reference.as_mut_ptr() as *mut _,
+            self.id(),
+            name.as_ptr(),
+            H5R_DATASET_REGION,
+            space.id()
+        ));
+        Ok(reference)
+    }
+
+    /// Reads a dataset of strings into a vector in memory order.
+    ///
+    /// Both fixed-length and variable-length strings are supported and the character
+    /// encoding of the stored strings is detected automatically, so there's no need to
+    /// know the exact string type beforehand. Reading fails if any of the stored strings
+    /// is not valid for its encoding.
+    pub fn read_strings(&self) -> Result<Vec<String>> {
+        let dtype = self.dtype()?;
+        let encoding = match dtype.string_encoding() {
+            Some(encoding) => encoding,
+            None => fail!("Expected a string dataset, got {:?}", dtype.to_descriptor()?),
+        };
+        match dtype.to_descriptor()? {
+            TypeDescriptor::VarLenAscii => {
+                Ok(self.read_raw::<VarLenAscii>()?.iter().map(|s| s.as_str().to_owned()).collect())
+            }
+            TypeDescriptor::VarLenUnicode => Ok(self
+                .read_raw::<VarLenUnicode>()?
+                .iter()
+                .map(|s| s.as_str().to_owned())
+                .collect()),
+            _ => {
+                // Fixed-length strings are read as is and unpadded here without conversion.
+                let size = dtype.size();
+                let mut buf = vec![0_u8; self.size() * size];
+                h5try!(H5Dread(
+                    self.id(),
+                    dtype.id(),
+                    H5S_ALL,
+                    H5S_ALL,
+                    H5P_DEFAULT,
+                    buf.as_mut_ptr() as *mut _
+                ));
+                let space_padded = dtype.string_padding() == Some(StringPadding::SpacePad);
+                buf.chunks(size)
+                    .enumerate()
+                    .map(|(i, bytes)| {
+                        let len = if space_padded {
+                            bytes.iter().rposition(|&c| c != b' ').map_or(0, |n| n + 1)
+                        } else {
+                            bytes.iter().position(|&c| c == 0).unwrap_or(size)
+                        };
+                        match str::from_utf8(&bytes[..len]) {
+                            Ok(s) if encoding == CharEncoding::Utf8 || s.is_ascii() => {
+                                Ok(s.to_owned())
+                            }
+                            _ => fail!("Invalid {} string at index {}", encoding, i),
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Recreates the dataset in the destination group with a new chunk shape and filters,
+    /// copying its data and attributes (similar to what `h5repack` does).
+    ///
+    /// The data is copied as stored, without type conversion, one chunk of the new dataset
+    /// at a time, so the memory usage is bounded regardless of the dataset size. If no chunk
+    /// shape is given, the one of the source dataset is kept; a dataset that isn't chunked
+    /// stays contiguous unless filters are set, in which case the chunk shape is inferred.
+    /// The maximum dimensions and the fill value of the source dataset are preserved.
+    pub fn repack(&self, dest: &Group, name: &str, options: &RepackOptions) -> Result<Dataset> {
+        let dtype = self.dtype()?;
+        let space = self.space()?;
+        let shape = space.dims();
+        let resizable = space.resizable();
+        let chunk = match options.chunk {
+            Some(ref chunk) => Some(chunk.clone()),
+            None => self.chunks().or_else(|| {
+                if options.filters.has_filters() {
+                    Some(infer_chunk_size(&shape, dtype.size()))
+                } else {
+                    None
+                }
+            }),
+        };
+        let dataset = h5lock!({
+            let dcpl = options.filters.to_dcpl(&dtype)?;
+            if let Some(ref chunk) = chunk {
+                ensure!(!shape.is_empty(), "Chunking cannot be enabled for scalar datasets");
+                ensure!(
+                    chunk.len() == shape.len(),
+                    "Invalid chunk ndim: expected {}, got {}",
+                    shape.len(),
+                    chunk.len()
+                );
+                ensure!(
+                    chunk.iter().all(|&c| c > 0),
+                    "Invalid chunk: {:?} (all dimensions must be positive)",
+                    chunk
+                );
+                ensure!(
+                    resizable || chunk.iter().zip(&shape).all(|(&c, &s)| c <= s),
+                    "Invalid chunk: {:?} (must not exceed data shape in any dimension)",
+                    chunk
+                );
+                let c_dims: Vec<hsize_t> = chunk.iter().map(|&x| x as _).collect();
+                h5try!(H5Pset_chunk(dcpl.id(), c_dims.len() as _, c_dims.as_ptr()));
+            }
+            h5try!(H5Pset_obj_track_times(dcpl.id(), self.tracks_times() as _));
+
+            let src_dcpl = PropertyList::from_id(h5try!(H5Dget_create_plist(self.id())))?;
+            let mut defined = H5D_fill_value_t::H5D_FILL_VALUE_UNDEFINED;
+            h5try!(H5Pfill_value_defined(src_dcpl.id(), &mut defined));
+            if defined == H5D_fill_value_t::H5D_FILL_VALUE_USER_DEFINED {
+                let mut value = vec![0_u8; dtype.size()];
+                h5try!(H5Pget_fill_value(src_dcpl.id(), dtype.id(), value.as_mut_ptr() as *mut _));
+                h5try!(H5Pset_fill_value(dcpl.id(), dtype.id(), value.as_ptr() as *const _));
+            }
+
+            let name = to_cstring(name)?;
+            Dataset::from_id(h5try!(H5Dcreate2(
+                dest.id(),
+                name.as_ptr(),
+                dtype.id(),
+                space.id(),
+                H5P_DEFAULT,
+                dcpl.id(),
+                H5P_DEFAULT
+            )))
+        })?;
+
+        let block = chunk.unwrap_or_else(|| infer_chunk_size(&shape, dtype.size()));
+        copy_raw_blocks(self, &dataset, &dtype, &shape, &block)?;
+        for attr_name in self.attr_names()? {
+            copy_attr(&self.attr(&attr_name)?, &dataset, &attr_name)?;
+        }
+        Ok(dataset)
+    }
+
+    /// Returns a lazy iterator over the rows of a two-dimensional dataset.
+    ///
+    /// Each row is read only when the iterator is advanced; for chunked datasets, the chunk
+    /// cache is used across rows, so it should fit at least one row of chunks.
+    pub fn iter_rows<T: H5Type>(&self) -> Result<RowIter<T>> {
+        let shape = self.shape();
+        ensure!(shape.len() == 2, "Row iteration requires a 2-D dataset, got {} dims", shape.len());
+        Ok(RowIter {
+            dataset: self.clone(),
+            rows: 0..shape[0],
+            ncols: shape[1],
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// Lazy iterator over the rows of a two-dimensional dataset, see `Dataset::iter_rows()`.
+pub struct RowIter<T> {
+    dataset: Dataset,
+    rows: Range<Ix>,
+    ncols: Ix,
+    _marker: PhantomData<T>,
+}
+
+impl<T: H5Type> RowIter<T> {
+    fn read_row(&self, row: Ix) -> Result<Array1<T>> {
+        if self.ncols == 0 {
+            return Ok(Array1::from_vec(vec![]));
+        }
+        self.dataset.read_selection(Hyperslab::new((row, 0), (1, self.ncols)))
+    }
+}
+
+impl<T: H5Type> Iterator for RowIter<T> {
+    type Item = Result<Array1<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rows.next().map(|row| self.read_row(row))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rows.size_hint()
+    }
+}
+
+impl<T: H5Type> ExactSizeIterator for RowIter<T> {}
+
+#[cfg(hdf5_1_10_0)]
+impl Dataset {
+    /// Flushes all buffered metadata and raw data of the dataset to the file.
+    ///
+    /// As with `File::flush()`, this doesn't guarantee that the operating system has synced
+    /// the data to the physical media.
+    pub fn flush(&self) -> Result<()> {
+        h5call!(H5Dflush(self.id())).and(Ok(()))
+    }
+
+    /// Refreshes the dataset's metadata (e.g. its extent) from the file, picking up changes
+    /// made by a concurrent SWMR writer.
+    pub fn refresh(&self) -> Result<()> {
+        h5call!(H5Drefresh(self.id())).and(Ok(()))
+    }
+}
+
+#[cfg(hdf5_1_10_2)]
+impl Dataset {
+    fn raw_chunk_offset(&self, offset: &[Ix]) -> Result<Vec<hsize_t>> {
+        let chunk = self.chunks().ok_or("Direct chunk I/O requires a chunked dataset")?;
+        let shape = self.get_shape()?;
+        ensure!(
+            offset.len() == chunk.len(),
+            "Invalid chunk offset ndim: expected {}, got {}",
+            chunk.len(),
+            offset.len()
+        );
+        for i in 0..chunk.len() {
+            ensure!(
+                offset[i] % chunk[i] == 0,
+                "Invalid chunk offset: {:?} (must be a multiple of chunk shape {:?})",
+                offset,
+                chunk
+            );
+            ensure!(
+                offset[i] < shape[i],
+                "Invalid chunk offset: {:?} (out of bounds for dataset shape {:?})",
+                offset,
+                shape
+            );
+        }
+        Ok(offset.iter().map(|&x| x as _).collect())
+    }
+
+    /// Writes raw chunk data at a given chunk offset, bypassing the filter pipeline.
+    ///
+    /// The data must already be in the on-disk format, i.e. encoded by all filters of the
+    /// dataset except those whose bits are set in `filter_mask`.
+    pub fn write_chunk(&self, offset: &[Ix], filter_mask: u32, data: &[u8]) -> Result<()> {
+        let offset = self.raw_chunk_offset(offset)?;
+        h5try!(H5Dwrite_chunk(
+            self.id(),
+            H5P_DEFAULT,
+            filter_mask,
+            offset.as_ptr(),
+            data.len() as _,
+            data.as_ptr() as *const _
+        ));
+        Ok(())
+    }
+
+    /// Reads raw chunk data at a given chunk offset, bypassing the filter pipeline.
+    ///
+    /// Returns the filter mask of the chunk along with its data as stored on disk.
+    pub fn read_chunk(&self, offset: &[Ix]) -> Result<(u32, Vec<u8>)> {
+        let c_offset = self.raw_chunk_offset(offset)?;
+        h5lock!({
+            let mut size: hsize_t = 0;
+            h5try!(H5Dget_chunk_storage_size(self.id(), c_offset.as_ptr(), &mut size));
+            ensure!(size > 0, "Chunk at offset {:?} has not been allocated", offset);
+            let mut filter_mask: u32 = 0;
+            let mut data = vec![0_u8; size as _];
+            h5try!(H5Dread_chunk(
+                self.id(),
+                H5P_DEFAULT,
+                c_offset.as_ptr(),
+                &mut filter_mask,
+                data.as_mut_ptr() as *mut _
+            ));
+            Ok((filter_mask, data))
+        })
+    }
+}
+
+/// Location and size of an allocated chunk of a dataset, see `Dataset::chunk_info()`.
+#[cfg(hdf5_1_10_5)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChunkInfo {
+    /// Logical position of the chunk's first element in the dataset.
+    pub offset: Vec<Ix>,
+    /// Bit mask of the filters in the pipeline that were skipped when writing the chunk.
+    pub filter_mask: u32,
+    /// Address of the chunk in the file.
+    pub addr: u64,
+    /// Size of the chunk in the file in bytes (after filtering).
+    pub size: u64,
+}
+
+#[cfg(hdf5_1_10_5)]
+impl Dataset {
+    /// Returns the location and storage size of each allocated chunk of a chunked dataset.
+    ///
+    /// Chunks that have never been written (and not allocated otherwise) are not included.
+    pub fn chunk_info(&self) -> Result<Vec<ChunkInfo>> {
+        ensure!(self.chunks().is_some(), "Chunk info requires a chunked dataset");
+        let space = self.space()?;
+        let ndim = space.ndim();
+        h5lock!({
+            let mut nchunks: hsize_t = 0;
+            h5try!(H5Dget_num_chunks(self.id(), space.id(), &mut nchunks));
+            let mut chunks = Vec::with_capacity(nchunks as _);
+            for index in 0..nchunks {
+                let mut offset: Vec<hsize_t> = vec![0; ndim];
+                let (mut filter_mask, mut addr, mut size): (c_uint, haddr_t, hsize_t) = (0, 0, 0);
+                h5try!(H5Dget_chunk_info(
+                    self.id(),
+                    space.id(),
+                    index,
+                    offset.as_mut_ptr(),
+                    &mut filter_mask,
+                    &mut addr,
+                    &mut size
+                ));
+                chunks.push(ChunkInfo {
+                    offset: offset.iter().map(|&x| x as _).collect(),
+                    filter_mask: filter_mask as _,
+                    addr: addr as _,
+                    size: size as _,
+                });
+            }
+            Ok(chunks)
+        })
+    }
+
+    /// Returns a lazy iterator over the allocated chunks of a chunked dataset.
+    ///
+    /// Chunks are visited in the order they are laid out in the file, each one being read
+    /// only when the iterator is advanced. Items are pairs of chunk offset and chunk data;
+    /// chunks at the edge of the dataset are clipped to its current extent, and chunks that
+    /// have never been allocated are skipped. (Note that `chunks()` returns the chunk
+    /// shape rather than the chunks themselves.)
+    pub fn iter_chunks<T: H5Type>(&self) -> Result<ChunkIter<T>> {
+        let chunk = self.chunks().ok_or("Chunk iteration requires a chunked dataset")?;
+        let shape = self.shape();
+        let mut chunks = self.chunk_info()?;
+        chunks.sort_by_key(|info| info.addr);
+        let offsets = chunks.into_iter().map(|info| info.offset).collect::<Vec<_>>();
+        Ok(ChunkIter {
+            dataset: self.clone(),
+            chunk,
+            shape,
+            offsets: offsets.into_iter(),
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// Lazy iterator over the chunks of a dataset, see `Dataset::iter_chunks()`.
+#[cfg(hdf5_1_10_5)]
+pub struct ChunkIter<T> {
+    dataset: Dataset,
+    chunk: Vec<Ix>,
+    shape: Vec<Ix>,
+    offsets: vec::IntoIter<Vec<Ix>>,
+    _marker: PhantomData<T>,
+}
+
+#[cfg(hdf5_1_10_5)]
+impl<T: H5Type> ChunkIter<T> {
+    fn read_chunk(&self, offset: Vec<Ix>) -> Result<(Vec<Ix>, ArrayD<T>)> {
+        let count: Vec<Ix> = (0..offset.len())
+            .map(|i| self.chunk[i].min(self.shape[i].saturating_sub(offset[i])))
+            .collect();
+        let data =
+            self.dataset.read_selection::<T, _>(Hyperslab::new(offset.clone(), count.clone()))?;
+        Ok((offset, data.into_shape(count)?))
+    }
+}
+
+#[cfg(hdf5_1_10_5)]
+impl<T: H5Type> Iterator for ChunkIter<T> {
+    type Item = Result<(Vec<Ix>, ArrayD<T>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.offsets.next().map(|offset| self.read_chunk(offset))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.offsets.size_hint()
+    }
+}
+
+#[cfg(hdf5_1_10_5)]
+impl<T: H5Type> ExactSizeIterator for ChunkIter<T> {}
+
+/// Cumulative I/O statistics of a dataset, see `Dataset::io_stats()`.
+///
+/// Byte counts refer to the data in memory, i.e. before compression or type conversion.
+#[cfg(feature = "io-stats")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IoStats {
+    pub bytes_read: u64,
+    pub bytes_written: u64,
+    pub read_calls: u64,
+    pub write_calls: u64,
+    /// Total wall-clock time spent in the library read and write calls (including
+    /// filtering and type conversion).
+    pub elapsed: Duration,
+}
+
+#[cfg(feature = "io-stats")]
+lazy_static! {
+    static ref IO_STATS: Mutex<HashMap<hid_t, IoStats>> = Mutex::new(HashMap::new());
+}
+
+/// Statistics are tracked per dataset identifier (shared by all clones of a handle) and
+/// are dropped once the identifier is closed, since the library may reuse it.
+#[cfg(feature = "io-stats")]
+pub(crate) fn record_io(id: hid_t, bytes: usize, elapsed: Duration, write: bool) {
+    let mut map = IO_STATS.lock();
+    let stats = map.entry(id).or_insert_with(IoStats::default);
+    stats.elapsed += elapsed;
+    if write {
+        stats.write_calls += 1;
+        stats.bytes_written += bytes as u64;
+    } else {
+        stats.read_calls += 1;
+        stats.bytes_read += bytes as u64;
+    }
+}
+
+#[cfg(feature = "io-stats")]
+pub(crate) fn forget_io_stats(id: hid_t) {
+    IO_STATS.lock().remove(&id);
+}
+
+#[cfg(feature = "io-stats")]
+impl Dataset {
+    /// Returns the number of read and write calls made through this dataset handle (or its
+    /// clones), the number of bytes transferred and the time spent.
+    ///
+    /// Handles obtained by opening the same dataset again start out with empty statistics.
+    pub fn io_stats(&self) -> IoStats {
+        IO_STATS.lock().get(&self.id()).cloned().unwrap_or_default()
+    }
+
+    /// Resets the I/O statistics of the dataset handle.
+    pub fn reset_io_stats(&self) {
+        forget_io_stats(self.id());
+    }
 }
 
 #[derive(Clone)]
@@ -171,8 +1095,21 @@ pub struct DatasetBuilder<T> {
     chunk: Chunk,
     parent: Result<Handle>,
     track_times: bool,
+    attr_creation_order: bool,
     resizable: bool,
+    compact: bool,
+    #[cfg(hdf5_1_10_0)]
+    virtual_maps: Vec<VirtualMapping>,
+    #[cfg(hdf5_1_10_0)]
+    chunk_opts: Option<ChunkOpts>,
     fill_value: Option<T>,
+    fill_time: Option<FillTime>,
+    alloc_time: Option<AllocTime>,
+    precision: Option<(usize, usize)>,
+    byte_order: Option<ByteOrder>,
+    string_padding: Option<StringPadding>,
+    committed_type: Option<Datatype>,
+    dapl: DatasetAccessBuilder,
 }
 
 impl<T: H5Type> DatasetBuilder<T> {
@@ -191,43 +1128,177 @@ impl<T: H5Type> DatasetBuilder<T> {
                 chunk: Chunk::Auto,
                 parent: handle,
                 track_times: false,
+                attr_creation_order: false,
                 resizable: false,
+                compact: false,
+                #[cfg(hdf5_1_10_0)]
+                virtual_maps: Vec::new(),
+                #[cfg(hdf5_1_10_0)]
+                chunk_opts: None,
                 fill_value: None,
+                fill_time: None,
+                alloc_time: None,
+                precision: None,
+                byte_order: None,
+                string_padding: None,
+                committed_type: None,
+                dapl: DatasetAccessBuilder::default(),
             }
         })
     }
 
-    pub fn packed(&mut self, packed: bool) -> &mut Self {
-        self.packed = packed;
-        self
+    /// Sets current dataset access property list to a given one.
+    pub fn set_access_plist(&mut self, dapl: &DatasetAccess) -> Result<&mut Self> {
+        DatasetAccessBuilder::from_plist(dapl).map(|dapl| {
+            self.dapl = dapl;
+            self
+        })
     }
 
-    pub fn fill_value(&mut self, fill_value: T) -> &mut Self {
-        self.fill_value = Some(fill_value);
-        self
+    /// A short alias for `set_access_plist()`.
+    pub fn set_dapl(&mut self, dapl: &DatasetAccess) -> Result<&mut Self> {
+        self.set_access_plist(dapl)
     }
 
-    /// Disable chunking.
-    pub fn no_chunk(&mut self) -> &mut Self {
-        self.chunk = Chunk::None;
+    /// Returns the builder object for the dataset access property list.
+    pub fn access_plist(&mut self) -> &mut DatasetAccessBuilder {
+        &mut self.dapl
+    }
+
+    /// A short alias for `access_plist()`.
+    pub fn dapl(&mut self) -> &mut DatasetAccessBuilder {
+        self.access_plist()
+    }
+
+    /// Allows accessing the builder object for the dataset access property list.
+    pub fn with_access_plist<F>(&mut self, func: F) -> &mut Self
+    where
+        F: Fn(&mut DatasetAccessBuilder) -> &mut DatasetAccessBuilder,
+    {
+        func(&mut self.dapl);
+        self
+    }
+
+    /// A short alias for `with_access_plist()`.
+    pub fn with_dapl<F>(&mut self, func: F) -> &mut Self
+    where
+        F: Fn(&mut DatasetAccessBuilder) -> &mut DatasetAccessBuilder,
+    {
+        self.with_access_plist(func)
+    }
+
+    pub fn packed(&mut self, packed: bool) -> &mut Self {
+        self.packed = packed;
+        self
+    }
+
+    /// Sets the number of significant bits and the bit offset of the stored integer datatype
+    /// (e.g. to be used with the n-bit filter); values are converted on reading and writing.
+    pub fn precision(&mut self, precision: usize, offset: usize) -> &mut Self {
+        self.precision = Some((precision, offset));
+        self
+    }
+
+    /// Sets the byte order of the stored datatype regardless of the host (values are
+    /// converted from/to the native byte order on writing and reading).
+    pub fn byte_order(&mut self, order: ByteOrder) -> &mut Self {
+        self.byte_order = Some(order);
+        self
+    }
+
+    /// Sets the padding of the stored fixed-length string datatype (strings are padded with
+    /// nulls by default); padding is added on writing and stripped on reading.
+    pub fn string_padding(&mut self, padding: StringPadding) -> &mut Self {
+        self.string_padding = Some(padding);
+        self
+    }
+
+    /// Uses a committed datatype (see `Group::commit_type()`) as the stored datatype instead
+    /// of creating a new anonymous one; `T` must be convertible to it.
+    pub fn committed_type(&mut self, datatype: &Datatype) -> &mut Self {
+        self.committed_type = Some(datatype.clone());
+        self
+    }
+
+    pub fn fill_value(&mut self, fill_value: T) -> &mut Self {
+        self.fill_value = Some(fill_value);
+        self
+    }
+
+    /// Set the time when fill values are written to the allocated storage (by default, this is
+    /// `FillTime::Alloc` for chunked datasets and `FillTime::IfSet` otherwise).
+    pub fn fill_time(&mut self, fill_time: FillTime) -> &mut Self {
+        self.fill_time = Some(fill_time);
+        self
+    }
+
+    /// Set the time when storage space is allocated; compact datasets always require
+    /// early allocation.
+    pub fn alloc_time(&mut self, alloc_time: AllocTime) -> &mut Self {
+        self.alloc_time = Some(alloc_time);
+        self
+    }
+
+    /// Disable chunking.
+    pub fn no_chunk(&mut self) -> &mut Self {
+        self.chunk = Chunk::None;
+        self.compact = false;
+        self
+    }
+
+    /// Use contiguous layout (same as `no_chunk()`).
+    pub fn contiguous(&mut self) -> &mut Self {
+        self.no_chunk()
+    }
+
+    /// Use compact layout, storing the raw data in the object header.
+    ///
+    /// The size of the raw data must not exceed `COMPACT_MAX_SIZE` (64 KiB); compact
+    /// datasets cannot be filtered or resized.
+    pub fn compact(&mut self) -> &mut Self {
+        self.chunk = Chunk::None;
+        self.compact = true;
+        self
+    }
+
+    /// Use virtual layout, mapping the dataset onto regions of other datasets (requires
+    /// HDF5 1.10.0).
+    ///
+    /// Elements not covered by any mapping, as well as elements mapped onto missing source
+    /// files or datasets, are read as the fill value of the virtual dataset.
+    #[cfg(hdf5_1_10_0)]
+    pub fn virtual_maps(&mut self, mappings: Vec<VirtualMapping>) -> &mut Self {
+        self.virtual_maps = mappings;
         self
     }
 
     /// Enable automatic chunking only if chunking is required (default option).
     pub fn chunk_auto(&mut self) -> &mut Self {
         self.chunk = Chunk::Auto;
+        self.compact = false;
         self
     }
 
     /// Enable chunking with automatic chunk shape.
     pub fn chunk_infer(&mut self) -> &mut Self {
         self.chunk = Chunk::Infer;
+        self.compact = false;
         self
     }
 
     /// Set chunk shape manually.
     pub fn chunk<D: Dimension>(&mut self, chunk: D) -> &mut Self {
         self.chunk = Chunk::Manual(chunk.dims());
+        self.compact = false;
+        self
+    }
+
+    /// Set the chunk storage options (requires chunking and HDF5 1.10.0).
+    ///
+    /// These only affect how the chunks are stored, not the data read back.
+    #[cfg(hdf5_1_10_0)]
+    pub fn chunk_opts(&mut self, opts: ChunkOpts) -> &mut Self {
+        self.chunk_opts = Some(opts);
         self
     }
 
@@ -243,6 +1314,12 @@ impl<T: H5Type> DatasetBuilder<T> {
         self
     }
 
+    /// Enable or disable tracking creation order of attributes (disabled by default).
+    pub fn track_attr_creation_order(&mut self, track: bool) -> &mut Self {
+        self.attr_creation_order = track;
+        self
+    }
+
     /// Make the dataset resizable along all axes (requires chunking).
     pub fn resizable(&mut self, resizable: bool) -> &mut Self {
         self.resizable = resizable;
@@ -255,12 +1332,24 @@ impl<T: H5Type> DatasetBuilder<T> {
         self
     }
 
-    /// Enable szip compression with a specified method (EC, NN) and level (0-32).
+    /// Enable shuffle and gzip compression with a specified level (0-9).
     ///
-    /// If `nn` if set to `true` (default), the nearest neighbor method is used, otherwise
-    /// the method is set to entropy coding.
-    pub fn szip(&mut self, nn: bool, level: u8) -> &mut Self {
-        self.filters.szip(nn, level);
+    /// Shuffle is applied before compression, which is what h5py produces for
+    /// `compression="gzip", shuffle=True`.
+    pub fn compress(&mut self, level: u8) -> &mut Self {
+        self.shuffle(true).gzip(level)
+    }
+
+    /// Enable szip compression with a specified coding method and number of pixels per
+    /// block (an even number up to 32).
+    pub fn szip(&mut self, coding: SzipCoding, pixels_per_block: u32) -> &mut Self {
+        self.filters.szip(coding, pixels_per_block);
+        self
+    }
+
+    /// Enable zstd compression with a specified level (requires the zstd filter plugin).
+    pub fn zstd(&mut self, level: i32) -> &mut Self {
+        self.filters.zstd(level);
         self
     }
 
@@ -276,6 +1365,12 @@ impl<T: H5Type> DatasetBuilder<T> {
         self
     }
 
+    /// Enable or disable n-bit filter (see `precision()`).
+    pub fn nbit(&mut self, nbit: bool) -> &mut Self {
+        self.filters.nbit(nbit);
+        self
+    }
+
     /// Enable scale-offset filter with a specified factor (0 means automatic).
     pub fn scale_offset(&mut self, scale_offset: u32) -> &mut Self {
         self.filters.scale_offset(scale_offset);
@@ -288,17 +1383,49 @@ impl<T: H5Type> DatasetBuilder<T> {
             let id = dcpl.id();
 
             h5try!(H5Pset_obj_track_times(id, self.track_times as _));
+            if self.attr_creation_order {
+                set_attr_creation_order(id, true)?;
+            }
 
             if let Some(ref fill_value) = self.fill_value {
                 h5try!(H5Pset_fill_value(id, datatype.id(), fill_value as *const _ as *const _));
             }
 
+            #[cfg(hdf5_1_10_0)]
+            {
+                if !self.virtual_maps.is_empty() {
+                    let chunked = match self.chunk {
+                        Chunk::Infer | Chunk::Manual(_) => true,
+                        _ => self.compact,
+                    };
+                    ensure!(!chunked, "Virtual datasets cannot be chunked or compact");
+                    ensure!(
+                        !self.filters.has_filters(),
+                        "Filters cannot be used with virtual datasets"
+                    );
+                    ensure!(!self.resizable, "Virtual datasets cannot be resizable");
+                    for mapping in &self.virtual_maps {
+                        mapping.apply(id, &shape)?;
+                    }
+                }
+            }
+
             if let Chunk::None = self.chunk {
                 ensure!(
                     !self.filters.has_filters(),
                     "Chunking must be enabled when filters are present"
                 );
                 ensure!(!self.resizable, "Chunking must be enabled for resizable datasets");
+                if self.compact {
+                    let size = shape.size() * datatype.size();
+                    ensure!(
+                        size <= COMPACT_MAX_SIZE,
+                        "Compact dataset size must not exceed {} bytes (got {} bytes)",
+                        COMPACT_MAX_SIZE,
+                        size
+                    );
+                    h5try!(H5Pset_layout(id, H5D_layout_t::H5D_COMPACT));
+                }
             } else {
                 let no_chunk = if let Chunk::Auto = self.chunk {
                     !self.filters.has_filters() && !self.resizable
@@ -341,6 +1468,32 @@ impl<T: H5Type> DatasetBuilder<T> {
                 }
             }
 
+            #[cfg(hdf5_1_10_0)]
+            {
+                if let Some(opts) = self.chunk_opts {
+                    ensure!(
+                        H5Pget_layout(id) == H5D_layout_t::H5D_CHUNKED,
+                        "Chunking must be enabled when chunk options are set"
+                    );
+                    h5try!(H5Pset_chunk_opts(id, opts.to_raw()));
+                }
+            }
+
+            if let Some(fill_time) = self.fill_time {
+                h5try!(H5Pset_fill_time(id, fill_time.into()));
+            }
+
+            if let Some(alloc_time) = self.alloc_time {
+                ensure!(
+                    !self.compact
+                        || alloc_time == AllocTime::Early
+                        || alloc_time == AllocTime::Default,
+                    "Compact datasets require early space allocation (got {:?})",
+                    alloc_time
+                );
+                h5try!(H5Pset_alloc_time(id, alloc_time.into()));
+            }
+
             Ok(dcpl)
         })
     }
@@ -360,10 +1513,33 @@ impl<T: H5Type> DatasetBuilder<T> {
         };
         h5lock!({
             let datatype = Datatype::from_descriptor(&type_descriptor)?;
+            let datatype = if let Some(ref committed) = self.committed_type {
+                ensure!(committed.is_committed(), "Datatype is not committed");
+                ensure!(
+                    self.precision.is_none()
+                        && self.byte_order.is_none()
+                        && self.string_padding.is_none(),
+                    "Precision, byte order and string padding cannot be set for committed datatypes"
+                );
+                datatype.ensure_convertible(committed, Conversion::Soft)?;
+                committed.clone()
+            } else {
+                if let Some((precision, offset)) = self.precision {
+                    datatype.set_precision(precision, offset)?;
+                }
+                if let Some(order) = self.byte_order {
+                    datatype.set_byte_order(order)?;
+                }
+                if let Some(padding) = self.string_padding {
+                    datatype.set_string_padding(padding)?;
+                }
+                datatype
+            };
             let parent = try_ref_clone!(self.parent);
 
             let dataspace = Dataspace::try_new(&shape, self.resizable)?;
             let dcpl = self.make_dcpl(&datatype, &shape)?;
+            let dapl = self.dapl.finish()?;
 
             match name {
                 Some(name) => {
@@ -376,7 +1552,7 @@ impl<T: H5Type> DatasetBuilder<T> {
                         dataspace.id(),
                         lcpl.id(),
                         dcpl.id(),
-                        H5P_DEFAULT
+                        dapl.id()
                     )))
                 }
                 _ => Dataset::from_id(h5try!(H5Dcreate_anon(
@@ -384,7 +1560,7 @@ impl<T: H5Type> DatasetBuilder<T> {
                     datatype.id(),
                     dataspace.id(),
                     dcpl.id(),
-                    H5P_DEFAULT
+                    dapl.id()
                 ))),
             }
         })
@@ -396,6 +1572,9 @@ impl<T: H5Type> DatasetBuilder<T> {
     }
 
     /// Create an anonymous dataset without linking it.
+    ///
+    /// The dataset can be linked into the file later via `Group::link_hard_object()`;
+    /// otherwise, its storage is released once the last handle to it is closed.
     pub fn create_anon<D: Dimension>(&self, shape: D) -> Result<Dataset> {
         self.finalize(None, shape)
     }
@@ -440,17 +1619,95 @@ fn infer_chunk_size<D: Dimension>(shape: &D, typesize: usize) -> Vec<Ix> {
     chunks
 }
 
+/// Copies the raw data between two datasets of the same shape and datatype, one block
+/// of a given shape at a time.
+fn copy_raw_blocks(
+    src: &Dataset, dst: &Dataset, dtype: &Datatype, shape: &[Ix], block: &[Ix],
+) -> Result<()> {
+    if shape.iter().any(|&s| s == 0) {
+        return Ok(());
+    }
+    let mut buf = vec![0_u8; block.iter().product::<usize>() * dtype.size()];
+    let mut offset = vec![0; shape.len()];
+    loop {
+        let count: Vec<Ix> = (0..shape.len()).map(|i| block[i].min(shape[i] - offset[i])).collect();
+        let fspace = src.space()?;
+        if !shape.is_empty() {
+            fspace.select(&Hyperslab::new(offset.clone(), count.clone()).into())?;
+        }
+        let mspace = Dataspace::try_new(count, false)?;
+        h5lock!({
+            h5try!(H5Dread(
+                src.id(),
+                dtype.id(),
+                mspace.id(),
+                fspace.id(),
+                H5P_DEFAULT,
+                buf.as_mut_ptr() as *mut _
+            ));
+            let written = h5call!(H5Dwrite(
+                dst.id(),
+                dtype.id(),
+                mspace.id(),
+                fspace.id(),
+                H5P_DEFAULT,
+                buf.as_ptr() as *const _
+            ));
+            // Variable-length data allocated by the library while reading must be released.
+            h5try!(H5Dvlen_reclaim(
+                dtype.id(),
+                mspace.id(),
+                H5P_DEFAULT,
+                buf.as_mut_ptr() as *mut _
+            ));
+            written.and(Ok(()))
+        })?;
+
+        // Advance to the next block in row-major order.
+        let mut axis = shape.len();
+        loop {
+            if axis == 0 {
+                return Ok(());
+            }
+            axis -= 1;
+            offset[axis] += block[axis];
+            if offset[axis] < shape[axis] {
+                break;
+            }
+            offset[axis] = 0;
+        }
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use std::fs;
     use std::io::Read;
-
-    use hdf5_sys::{h5d::H5Dwrite, h5s::H5S_ALL};
+    use std::mem;
+    #[cfg(feature = "io-stats")]
+    use std::time::Duration;
+
+    use hdf5_sys::{
+        h5d::{H5Dcreate_anon, H5Dwrite},
+        h5p::{H5Pcreate, H5Pset_chunk, H5Pset_filter, H5Pset_shuffle},
+        h5s::{H5Screate_simple, H5S_ALL},
+        h5z::{H5Z_FILTER_DEFLATE, H5Z_FLAG_OPTIONAL},
+    };
+    use hdf5_types::{RegionReference, VarLenUnicode};
+    use ndarray::{arr2, s, Array2};
 
     use crate::filters::{gzip_available, szip_available};
+    use crate::hl::plist::dataset_access::{ChunkCache, DatasetAccess};
+    use crate::hl::plist::dataset_transfer::DatasetTransfer;
     use crate::internal_prelude::*;
 
-    use super::infer_chunk_size;
+    use super::{
+        infer_chunk_size, AllocTime, FillTime, Layout, RepackOptions, SpaceStatus, COMPACT_MAX_SIZE,
+    };
+    #[cfg(hdf5_1_10_0)]
+    use super::{ChunkOpts, VirtualMapping};
+    #[cfg(feature = "io-stats")]
+    use super::{IoStats, IO_STATS};
 
     #[test]
     pub fn test_infer_chunk_size() {
@@ -635,14 +1892,70 @@ pub mod tests {
             if szip_available() {
                 assert_eq!(
                     file.new_dataset::<u32>()
-                        .szip(false, 4)
+                        .szip(SzipCoding::Entropy, 4)
                         .create_anon(100)
                         .unwrap()
                         .filters()
                         .get_szip(),
-                    Some((false, 4))
+                    Some((SzipCoding::Entropy, 4))
                 );
+
+                // Entropy coding with 16 pixels per block, as used in NASA EOS products.
+                let ds = file
+                    .new_dataset::<u32>()
+                    .chunk(64)
+                    .szip(SzipCoding::Entropy, 16)
+                    .create_anon(256)
+                    .unwrap();
+                assert_eq!(
+                    ds.filter_pipeline().unwrap(),
+                    vec![Filter::Szip { options_mask: SzipCoding::Entropy, pixels_per_block: 16 }]
+                );
+                let data: Vec<u32> = (0..256).map(|x| x * x).collect();
+                ds.write(&data).unwrap();
+                assert_eq!(ds.read_raw::<u32>().unwrap(), data);
+            }
+        });
+
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u32>().create_anon(100).unwrap();
+            assert!(ds.filter_pipeline().unwrap().is_empty());
+            let ds = file.new_dataset::<u32>().shuffle(true).fletcher32(true);
+            let ds = ds.create_anon(100).unwrap();
+            assert_eq!(ds.filter_pipeline().unwrap(), vec![Filter::Fletcher32, Filter::Shuffle]);
+            if gzip_available() {
+                let ds = file.new_dataset::<u32>().shuffle(true).gzip(6).create_anon(100).unwrap();
+                assert_eq!(ds.filter_pipeline().unwrap(), vec![Filter::Shuffle, Filter::Gzip(6)]);
+                assert_eq!(ds.filter_pipeline().unwrap()[1].id(), H5Z_FILTER_DEFLATE);
+
+                let ds = file.new_dataset::<u32>().chunk(50).compress(4).create_anon(100).unwrap();
+                assert_eq!(ds.filter_pipeline().unwrap(), vec![Filter::Shuffle, Filter::Gzip(4)]);
+                let data: Vec<u32> = (0..100).map(|x| x * 1000).collect();
+                ds.write(&data).unwrap();
+                assert_eq!(ds.read_raw::<u32>().unwrap(), data);
             }
+
+            // Filters unknown to the crate are reported along with their parameters.
+            let dcpl_id = h5call!(H5Pcreate(*crate::globals::H5P_DATASET_CREATE)).unwrap();
+            let dcpl = PropertyList::from_id(dcpl_id).unwrap();
+            let values: [c_uint; 2] = [3, 14];
+            h5call!(H5Pset_shuffle(dcpl.id())).unwrap();
+            h5call!(H5Pset_filter(dcpl.id(), 32004, H5Z_FLAG_OPTIONAL, 2, values.as_ptr()))
+                .unwrap();
+            let many: Vec<c_uint> = (0..20).collect();
+            h5call!(H5Pset_filter(dcpl.id(), 32005, H5Z_FLAG_OPTIONAL, 20, many.as_ptr())).unwrap();
+            let blosc = [2, 2, 4, 400, 5, 1, 0];
+            h5call!(H5Pset_filter(dcpl.id(), 32001, H5Z_FLAG_OPTIONAL, 7, blosc.as_ptr())).unwrap();
+            assert_eq!(
+                Filter::from_dcpl(&dcpl).unwrap(),
+                vec![
+                    Filter::Shuffle,
+                    Filter::User { id: 32004, cd_values: vec![3, 14] },
+                    Filter::User { id: 32005, cd_values: many },
+                    Filter::Blosc { level: 5, shuffle: 1, compressor: 0 },
+                ]
+            );
+            assert_err!(Filters::from_dcpl(&dcpl), "Unsupported filter: 32004");
         });
 
         with_tmp_file(|file| {
@@ -654,6 +1967,114 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_fletcher32_verification() {
+        let _e = silence_errors();
+        with_tmp_path(|path| {
+            let data: Vec<u32> = (0..256).map(|i| 0xabcd_0000 | i).collect();
+            let file = File::create(&path).unwrap();
+            let ds = file.new_dataset::<u32>().fletcher32(true).create("data", 256).unwrap();
+            ds.write(&data).unwrap();
+            assert_eq!(ds.read_raw::<u32>().unwrap(), data);
+            drop(ds);
+            drop(file);
+
+            // Flip a bit in the stored chunk (it's stored as is, followed by the checksum).
+            let raw: Vec<u8> = data.iter().flat_map(|x| x.to_ne_bytes().to_vec()).collect();
+            let mut bytes = fs::read(&path).unwrap();
+            let pos = bytes.windows(raw.len()).position(|w| w == raw.as_slice()).unwrap();
+            bytes[pos + 10] ^= 1;
+            fs::write(&path, &bytes).unwrap();
+
+            let ds = File::open(&path).unwrap().dataset("data").unwrap();
+            assert_err!(ds.read_raw::<u32>(), "fletcher32 checksum failed");
+            let dxpl = DatasetTransfer::build().edc_check(false).finish().unwrap();
+            assert!(!dxpl.edc_check());
+            assert!(DatasetTransfer::try_new().unwrap().edc_check());
+            let corrupted = ds.as_reader().dxpl(&dxpl).read_raw::<u32>().unwrap();
+            assert_ne!(corrupted, data);
+            assert_eq!(&corrupted[3..], &data[3..]);
+        })
+    }
+
+    #[test]
+    pub fn test_byte_order() {
+        use hdf5_sys::h5t::{H5T_order_t, H5Tget_order};
+
+        with_tmp_file(|file| {
+            let values = [1.5, -2.25e100, std::f64::consts::PI];
+            let ds = file.new_dataset::<f64>().byte_order(ByteOrder::BigEndian);
+            let ds = ds.create("be", 3).unwrap();
+            ds.write(&values).unwrap();
+            let dtype = ds.dtype().unwrap();
+            assert_eq!(h5lock!(H5Tget_order(dtype.id())), H5T_order_t::H5T_ORDER_BE);
+            assert_eq!(dtype.byte_order(), Some(ByteOrder::BigEndian));
+            assert_eq!(ds.read_raw::<f64>().unwrap(), values);
+            assert_eq!(file.dataset("be").unwrap().read_1d::<f64>().unwrap().to_vec(), values);
+
+            let ds = file.new_dataset::<u32>().byte_order(ByteOrder::LittleEndian);
+            let ds = ds.create("le", 2).unwrap();
+            ds.write(&[1, 0xdead_beef]).unwrap();
+            assert_eq!(ds.dtype().unwrap().byte_order(), Some(ByteOrder::LittleEndian));
+            assert_eq!(ds.read_raw::<u64>().unwrap(), vec![1, 0xdead_beef]);
+
+            let ds = file.new_dataset::<i16>().byte_order(ByteOrder::Native).create("ne", 1);
+            let dtype = ds.unwrap().dtype().unwrap();
+            assert_eq!(dtype.byte_order(), Some(ByteOrder::Native.resolve()));
+            assert_eq!(dtype, Datatype::from_type::<i16>().unwrap());
+            assert_eq!(
+                Datatype::from_type::<hdf5_types::VarLenAscii>().unwrap().byte_order(),
+                None
+            );
+        })
+    }
+
+    #[test]
+    pub fn test_string_padding() {
+        use hdf5_types::FixedAscii;
+
+        type S = FixedAscii<[u8; 8]>;
+
+        with_tmp_file(|file| {
+            let strings = ["", "a", "ab  ", "full8chr"];
+            let values: Vec<S> = strings.iter().map(|s| S::from_ascii(s).unwrap()).collect();
+
+            let ds = file.new_dataset::<S>().create("default", 4).unwrap();
+            assert_eq!(ds.dtype().unwrap().string_padding(), Some(StringPadding::NullPad));
+
+            let ds = file.new_dataset::<S>().string_padding(StringPadding::SpacePad);
+            let ds = ds.create("space", 4).unwrap();
+            assert_eq!(ds.dtype().unwrap().string_padding(), Some(StringPadding::SpacePad));
+            ds.write(&values).unwrap();
+            let stored = ds.read_raw::<S>().unwrap();
+            let stored: Vec<_> = stored.iter().map(|s| s.as_str().to_owned()).collect();
+            assert_eq!(stored, vec!["", "a", "ab", "full8chr"]);
+
+            let ds = file.new_dataset::<S>().string_padding(StringPadding::NullTerm);
+            let ds = ds.create("nullterm", 4).unwrap();
+            assert_eq!(ds.dtype().unwrap().string_padding(), Some(StringPadding::NullTerm));
+            ds.write(&values).unwrap();
+            let stored = ds.read_raw::<S>().unwrap();
+            assert_eq!(stored[2].as_str(), "ab  ");
+            assert_eq!(stored[3].as_str(), "full8ch");
+
+            let ds = file.new_dataset::<S>().string_padding(StringPadding::NullPad);
+            let ds = ds.create("nullpad", 4).unwrap();
+            ds.write(&values).unwrap();
+            assert_eq!(ds.read_raw::<S>().unwrap(), values);
+
+            assert_eq!(
+                Datatype::from_type::<hdf5_types::VarLenAscii>().unwrap().string_padding(),
+                None
+            );
+            assert_eq!(Datatype::from_type::<u8>().unwrap().string_padding(), None);
+            assert_err!(
+                file.new_dataset::<u8>().string_padding(StringPadding::SpacePad).create("u8", 1),
+                "String padding can only be set for fixed-length string datatypes"
+            );
+        })
+    }
+
     #[test]
     pub fn test_resizable() {
         with_tmp_file(|file| {
@@ -669,6 +2090,69 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_prefetch_chunks() {
+        with_tmp_file(|file| {
+            let data = Array2::from_shape_fn((20, 30), |(i, j)| (i * 30 + j) as f64);
+            let ds = file.new_dataset::<f64>().chunk((8, 8)).create("data", (20, 30)).unwrap();
+            ds.write(&data).unwrap();
+            let offsets: Vec<_> = (0..20)
+                .step_by(8)
+                .flat_map(|i| (0..30).step_by(8).map(move |j| vec![i, j]))
+                .collect();
+            assert_eq!(offsets.len(), 12);
+            ds.prefetch_chunks(&offsets).unwrap();
+            ds.prefetch_chunks(&[]).unwrap();
+            assert_eq!(ds.read_2d::<f64>().unwrap(), data);
+
+            assert_err!(
+                ds.prefetch_chunks(&[vec![0, 30]]),
+                "Chunk offset [0, 30] is out of bounds for shape [20, 30]"
+            );
+            assert_err!(ds.prefetch_chunks(&[vec![0]]), "is out of bounds");
+            let ds = file.new_dataset::<f64>().create("contiguous", 10).unwrap();
+            assert_err!(ds.prefetch_chunks(&[vec![0]]), "requires a chunked dataset");
+        })
+    }
+
+    #[test]
+    pub fn test_max_extent() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u32>().create_anon((2, 3)).unwrap();
+            assert_eq!(ds.max_extent(), vec![Some(2), Some(3)]);
+            let ds = file.new_dataset::<u32>().resizable(true).create_anon((2, 3)).unwrap();
+            assert_eq!(ds.max_extent(), vec![None, None]);
+
+            // A table with a fixed number of columns that can be appended to.
+            let ds = (|| -> Result<Dataset> {
+                let dims: [hsize_t; 2] = [2, 3];
+                let maxdims = [H5S_UNLIMITED, 3];
+                let space = Dataspace::from_id(h5try!(H5Screate_simple(
+                    2,
+                    dims.as_ptr(),
+                    maxdims.as_ptr()
+                )))?;
+                let dcpl =
+                    PropertyList::from_id(h5try!(H5Pcreate(*crate::globals::H5P_DATASET_CREATE)))?;
+                h5try!(H5Pset_chunk(dcpl.id(), 2, dims.as_ptr()));
+                let dtype = Datatype::from_type::<u32>()?;
+                Dataset::from_id(h5try!(H5Dcreate_anon(
+                    file.id(),
+                    dtype.id(),
+                    space.id(),
+                    dcpl.id(),
+                    H5P_DEFAULT
+                )))
+            })()
+            .unwrap();
+            assert_eq!(ds.max_extent(), vec![None, Some(3)]);
+            ds.resize((5, 3)).unwrap();
+            assert_eq!(ds.shape(), vec![5, 3]);
+            assert_eq!(ds.max_extent(), vec![None, Some(3)]);
+            assert!(ds.resize((5, 4)).is_err());
+        })
+    }
+
     #[test]
     pub fn test_track_times() {
         with_tmp_file(|file| {
@@ -733,6 +2217,113 @@ pub mod tests {
         })
     }
 
+    #[test]
+    #[cfg(hdf5_1_10_0)]
+    pub fn test_chunk_opts() {
+        with_tmp_file(|file| {
+            let opts = ChunkOpts { dont_filter_partial_edge: true };
+            let data: Vec<i32> = (0..10).map(|x| x * x - 7).collect();
+            let mut builder = file.new_dataset::<i32>();
+            builder.chunk(4).shuffle(true).chunk_opts(opts);
+            if gzip_available() {
+                builder.gzip(4);
+            }
+            let ds = builder.create("edge", 10).unwrap();
+            ds.write(&data).unwrap();
+            assert_eq!(ds.chunk_opts(), Some(opts));
+            assert_eq!(file.dataset("edge").unwrap().read_raw::<i32>().unwrap(), data);
+
+            let ds = file.new_dataset::<i32>().chunk(4).create("default", 10).unwrap();
+            assert_eq!(ds.chunk_opts(), Some(ChunkOpts::default()));
+            let ds = file.new_dataset::<i32>().contiguous().create("contiguous", 10).unwrap();
+            assert_eq!(ds.chunk_opts(), None);
+            assert_err!(
+                file.new_dataset::<i32>().contiguous().chunk_opts(opts).create_anon(10),
+                "Chunking must be enabled when chunk options are set"
+            );
+        })
+    }
+
+    #[test]
+    pub fn test_offset_raw_bytes() {
+        with_tmp_path(|path| {
+            let file = File::create(&path).unwrap();
+            let data: Vec<f64> = (0..32).map(|x| f64::from(x) * 0.25 - 3.).collect();
+            let ds = file.new_dataset::<f64>().contiguous().create("data", 32).unwrap();
+            ds.write(&data).unwrap();
+            let chunked = file.new_dataset::<f64>().chunk(8).create("chunked", 32).unwrap();
+            chunked.write(&data).unwrap();
+            let compact = file.new_dataset::<f64>().compact().create("compact", 32).unwrap();
+            compact.write(&data).unwrap();
+            assert!(chunked.offset().is_none());
+            assert!(compact.offset().is_none());
+            file.flush(crate::hl::file::FlushScope::Local).unwrap();
+
+            let offset = ds.offset().unwrap() as usize;
+            let bytes = fs::read(&path).unwrap();
+            let raw: Vec<f64> = bytes[offset..offset + 32 * 8]
+                .chunks(8)
+                .map(|b| {
+                    let mut buf = [0; 8];
+                    buf.copy_from_slice(b);
+                    f64::from_bits(u64::from_ne_bytes(buf))
+                })
+                .collect();
+            assert_eq!(raw, data);
+        })
+    }
+
+    #[test]
+    pub fn test_space_status() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u32>().create("contiguous", 4).unwrap();
+            assert_eq!(ds.space_status().unwrap(), SpaceStatus::NotAllocated);
+            ds.write(&[1, 2, 3, 4]).unwrap();
+            assert_eq!(ds.space_status().unwrap(), SpaceStatus::Allocated);
+            assert_eq!(ds.storage_size(), 16);
+
+            let ds = file.new_dataset::<u32>().chunk(5).create("chunked", 10).unwrap();
+            assert_eq!(ds.space_status().unwrap(), SpaceStatus::NotAllocated);
+            assert_eq!(ds.storage_size(), 0);
+            ds.write_slice(&[1, 2], s![1..3]).unwrap();
+            assert_eq!(ds.space_status().unwrap(), SpaceStatus::PartlyAllocated);
+            assert_eq!(ds.storage_size(), 20);
+            ds.write_slice(&[3, 4], s![7..9]).unwrap();
+            assert_eq!(ds.space_status().unwrap(), SpaceStatus::Allocated);
+            assert_eq!(ds.storage_size(), 40);
+
+            if gzip_available() {
+                let arr = Array2::from_shape_fn((100, 20), |(i, _)| (i / 10) as u64);
+                let ds = file.new_dataset::<u64>().gzip(6).create("gzip", (100, 20)).unwrap();
+                ds.write(&arr).unwrap();
+                assert_eq!(ds.space_status().unwrap(), SpaceStatus::Allocated);
+                assert!(ds.storage_size() < (arr.len() * mem::size_of::<u64>()) as u64);
+            }
+        })
+    }
+
+    #[test]
+    pub fn test_chunk_cache() {
+        with_tmp_file(|file| {
+            let cache = ChunkCache { nslots: 1009, nbytes: 16 * 1024 * 1024, w0: 0.5 };
+            let ds = file
+                .new_dataset::<u32>()
+                .chunk((10, 10))
+                .with_dapl(|p| p.chunk_cache(1009, 16 * 1024 * 1024, 0.5))
+                .create("foo", (100, 100))
+                .unwrap();
+            assert_eq!(ds.dapl().unwrap().chunk_cache(), cache);
+
+            let dapl = DatasetAccess::build().chunk_cache(2003, 64 * 1024 * 1024, 1.0).finish();
+            let ds = file.dataset_with_access("foo", &dapl.unwrap()).unwrap();
+            assert_eq!(
+                ds.access_plist().unwrap().chunk_cache(),
+                ChunkCache { nslots: 2003, nbytes: 64 * 1024 * 1024, w0: 1.0 }
+            );
+            assert_ne!(file.dataset("foo").unwrap().dapl().unwrap().chunk_cache(), cache);
+        })
+    }
+
     #[test]
     pub fn test_datatype() {
         with_tmp_file(|file| {
@@ -803,4 +2394,558 @@ pub mod tests {
             check_all_fill_values!(ds, 1.234);
         })
     }
+
+    #[test]
+    pub fn test_layout() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u32>().contiguous().create("contiguous", 10).unwrap();
+            assert_eq!(ds.layout(), Layout::Contiguous);
+            let ds = file.new_dataset::<u32>().chunk((2, 3)).create("chunked", (4, 6)).unwrap();
+            assert_eq!(ds.layout(), Layout::Chunked(vec![2, 3]));
+            let ds = file.new_dataset::<u32>().compact().create("compact", (2, 3)).unwrap();
+            assert_eq!(ds.layout(), Layout::Compact);
+            ds.write(&arr2(&[[1, 2, 3], [4, 5, 6]])).unwrap();
+            assert_eq!(file.dataset("compact").unwrap().layout(), Layout::Compact);
+            assert_eq!(ds.read_raw::<u32>().unwrap(), vec![1, 2, 3, 4, 5, 6]);
+            let ds = file.new_dataset::<u8>().compact().chunk(5).create_anon(10).unwrap();
+            assert_eq!(ds.layout(), Layout::Chunked(vec![5]));
+
+            file.new_dataset::<u8>().compact().create_anon(COMPACT_MAX_SIZE / 2).unwrap();
+            assert_err!(
+                file.new_dataset::<u32>().compact().create_anon(COMPACT_MAX_SIZE),
+                "Compact dataset size must not exceed 65536 bytes (got 262144 bytes)"
+            );
+            assert_err!(
+                file.new_dataset::<u32>().compact().gzip(4).create_anon(10),
+                "Chunking must be enabled when filters are present"
+            );
+            assert_err!(
+                file.new_dataset::<u32>().compact().resizable(true).create_anon(10),
+                "Chunking must be enabled for resizable datasets"
+            );
+        })
+    }
+
+    #[test]
+    pub fn test_alloc_time() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u64>().no_chunk().alloc_time(AllocTime::Late);
+            let ds = ds.create("late", 1000).unwrap();
+            assert_eq!(ds.alloc_time().unwrap(), AllocTime::Late);
+            assert_eq!(ds.space_status().unwrap(), SpaceStatus::NotAllocated);
+            assert_eq!(ds.storage_size(), 0);
+            ds.write_slice(&[1, 2], s![10..12]).unwrap();
+            assert_eq!(ds.storage_size(), 8000);
+
+            let ds = file.new_dataset::<u64>().no_chunk().alloc_time(AllocTime::Early);
+            let ds = ds.create("early", 1000).unwrap();
+            assert_eq!(ds.alloc_time().unwrap(), AllocTime::Early);
+            assert_eq!(ds.space_status().unwrap(), SpaceStatus::Allocated);
+            assert_eq!(ds.storage_size(), 8000);
+
+            let ds = file.new_dataset::<u64>().no_chunk().create("default", 10).unwrap();
+            assert_eq!(ds.alloc_time().unwrap(), AllocTime::Late);
+            let ds = file.new_dataset::<u64>().chunk(10).create("chunked", 100).unwrap();
+            assert_eq!(ds.alloc_time().unwrap(), AllocTime::Incremental);
+            let ds = file.new_dataset::<u64>().chunk(10).alloc_time(AllocTime::Early);
+            let ds = ds.create("chunked_early", 100).unwrap();
+            assert_eq!(ds.space_status().unwrap(), SpaceStatus::Allocated);
+            assert_eq!(ds.storage_size(), 800);
+
+            let ds = file.new_dataset::<u8>().compact().alloc_time(AllocTime::Early);
+            assert_eq!(ds.create("compact", 10).unwrap().alloc_time().unwrap(), AllocTime::Early);
+            assert_err!(
+                file.new_dataset::<u8>().compact().alloc_time(AllocTime::Late).create("c", 10),
+                "Compact datasets require early space allocation (got Late)"
+            );
+        })
+    }
+
+    #[test]
+    pub fn test_fill_time() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u8>().no_chunk().create_anon(10).unwrap();
+            assert_eq!(ds.fill_time().unwrap(), FillTime::IfSet);
+            let ds = file.new_dataset::<u8>().chunk(5).create_anon(10).unwrap();
+            assert_eq!(ds.fill_time().unwrap(), FillTime::Alloc);
+            let ds = file.new_dataset::<u8>().chunk(5).fill_time(FillTime::Never);
+            assert_eq!(ds.create_anon(10).unwrap().fill_time().unwrap(), FillTime::Never);
+
+            let ds = file
+                .new_dataset::<f64>()
+                .chunk(10)
+                .fill_value(std::f64::NAN)
+                .fill_time(FillTime::IfSet)
+                .create("nan", 100)
+                .unwrap();
+            assert_eq!(ds.fill_time().unwrap(), FillTime::IfSet);
+            assert!(ds.fill_value::<f64>().unwrap().unwrap().is_nan());
+            ds.write_slice(&[0.0, 1.0, 2.0], s![3..6]).unwrap();
+            let data = ds.read_raw::<f64>().unwrap();
+            assert_eq!(&data[3..6], &[0.0, 1.0, 2.0]);
+            // untouched elements of the allocated chunk as well as of unallocated chunks
+            assert!(data[..3].iter().chain(&data[6..]).all(|x| x.is_nan()));
+        })
+    }
+
+    #[test]
+    pub fn test_repack() {
+        with_tmp_file(|file| {
+            let arr = Array2::from_shape_fn((200, 300), |(i, j)| ((i * 300 + j) % 17) as f64 / 3.);
+            let ds = file.new_dataset::<f64>().no_chunk().fill_value(-1.).create("src", (200, 300));
+            let ds = ds.unwrap();
+            ds.write(&arr).unwrap();
+            ds.set_attr("version", &3_u32).unwrap();
+            ds.set_attr("units", &"m/s".parse::<VarLenUnicode>().unwrap()).unwrap();
+
+            let copy = ds.repack(&file, "copy", &RepackOptions::new()).unwrap();
+            assert_eq!(copy.layout(), Layout::Contiguous);
+            assert_eq!(copy.read_2d::<f64>().unwrap(), arr);
+
+            assert_err!(
+                ds.repack(&file, "bad", RepackOptions::new().chunk(10)),
+                "Invalid chunk ndim: expected 2, got 1"
+            );
+            assert_err!(
+                ds.repack(&file, "bad", RepackOptions::new().chunk((300, 10))),
+                "must not exceed data shape"
+            );
+
+            if gzip_available() {
+                let group = file.create_group("packed").unwrap();
+                let options =
+                    RepackOptions::new().chunk((50, 100)).filters(Filters::new().gzip(6)).clone();
+                assert_eq!(options.get_chunk(), Some(&[50, 100][..]));
+                let packed = ds.repack(&group, "dst", &options).unwrap();
+                assert_eq!(packed.name(), "/packed/dst");
+                assert_eq!(packed.layout(), Layout::Chunked(vec![50, 100]));
+                assert_eq!(packed.filters().get_gzip(), Some(6));
+                assert_eq!(packed.fill_value::<f64>().unwrap(), Some(-1.));
+                let bits = |ds: &Dataset| -> Vec<u64> {
+                    ds.read_raw::<f64>().unwrap().iter().map(|x| x.to_bits()).collect()
+                };
+                assert_eq!(bits(&packed), bits(&ds));
+                assert!(packed.storage_size() < ds.storage_size());
+                assert_eq!(packed.attr_names().unwrap(), vec!["units", "version"]);
+                assert_eq!(packed.attr("version").unwrap().read_scalar::<u32>().unwrap(), 3);
+                let units = packed.attr("units").unwrap().read_scalar::<VarLenUnicode>().unwrap();
+                assert_eq!(units.as_str(), "m/s");
+
+                // Without an explicit chunk shape, the chunk shape of the source is kept.
+                let packed = packed.repack(&file, "dst2", &RepackOptions::new()).unwrap();
+                assert_eq!(packed.layout(), Layout::Chunked(vec![50, 100]));
+                assert_eq!(packed.filters(), Filters::default());
+                assert_eq!(packed.read_2d::<f64>().unwrap(), arr);
+            }
+        })
+    }
+
+    #[test]
+    pub fn test_region_references() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<i32>().create("data", (6, 8)).unwrap();
+            ds.write_raw(&(0..48).collect::<Vec<i32>>()).unwrap();
+
+            let selections: Vec<Selection> = vec![
+                Selection::All,
+                Hyperslab::new((1, 2), (2, 3)).into(),
+                Hyperslab::new((0, 1), (3, 2)).stride((2, 3)).into(),
+                Hyperslab::new((0, 0), (2, 2)).stride((3, 4)).block((2, 2)).into(),
+                vec![Hyperslab::new((0, 0), (1, 8)), Hyperslab::new((4, 2), (2, 2))].into(),
+            ];
+            let refs = selections
+                .iter()
+                .map(|sel| ds.region_ref(sel.clone()))
+                .collect::<Result<Vec<_>>>()
+                .unwrap();
+            assert!(refs.iter().all(|r| !r.is_null()));
+
+            let refs_ds = file.new_dataset::<RegionReference>().create("refs", refs.len()).unwrap();
+            refs_ds.write(&refs).unwrap();
+            let refs_read = refs_ds.read_raw::<RegionReference>().unwrap();
+            assert_eq!(refs_read, refs);
+
+            for (r, sel) in refs_read.iter().zip(&selections) {
+                let (target, target_sel) = file.dereference_region(r).unwrap();
+                assert_eq!(target.name(), "/data");
+                assert_eq!(
+                    target.read_selection::<i32, _>(target_sel).unwrap(),
+                    ds.read_selection::<i32, _>(sel.clone()).unwrap()
+                );
+            }
+
+            let (_, sel) = file.dereference_region(&refs_read[2]).unwrap();
+            assert_eq!(
+                ds.read_selection::<i32, _>(sel).unwrap().to_vec(),
+                vec![1, 4, 17, 20, 33, 36]
+            );
+
+            assert_err!(ds.region_ref(Hyperslab::new((5, 0), (2, 1))), "beyond dataspace bounds");
+            assert_err!(
+                file.dereference_region(&RegionReference::default()),
+                "null region reference"
+            );
+        })
+    }
+
+    #[test]
+    pub fn test_iter_rows() {
+        with_tmp_file(|file| {
+            let arr = Array2::from_shape_fn((1000, 4), |(i, j)| (i * 4 + j) as u32);
+            let ds = file.new_dataset::<u32>().chunk((64, 4)).create("table", (1000, 4)).unwrap();
+            ds.write(&arr).unwrap();
+
+            let rows = ds.iter_rows::<u32>().unwrap();
+            assert_eq!(rows.len(), 1000);
+            let mut sum = 0_u64;
+            for (i, row) in rows.enumerate() {
+                let row = row.unwrap();
+                assert_eq!(row, arr.row(i));
+                sum += u64::from(row[2]);
+            }
+            let full = ds.read_2d::<u32>().unwrap();
+            assert_eq!(sum, full.column(2).iter().map(|&x| u64::from(x)).sum::<u64>());
+
+            let ds = file.new_dataset::<f32>().create("empty", (3, 0)).unwrap();
+            let rows = ds.iter_rows::<f32>().unwrap().collect::<Result<Vec<_>>>().unwrap();
+            assert_eq!(rows.len(), 3);
+            assert!(rows.iter().all(|row| row.is_empty()));
+
+            let ds = file.new_dataset::<u32>().create("flat", 10).unwrap();
+            assert_err!(ds.iter_rows::<u32>(), "Row iteration requires a 2-D dataset, got 1 dims");
+        })
+    }
+
+    #[test]
+    #[cfg(hdf5_1_10_5)]
+    pub fn test_iter_chunks() {
+        with_tmp_file(|file| {
+            let arr = Array2::from_shape_fn((7, 5), |(i, j)| (i * 10 + j) as i32);
+            let ds = file.new_dataset::<i32>().chunk((3, 2)).create("chunked", (7, 5)).unwrap();
+            ds.write(&arr).unwrap();
+
+            let chunks = ds.iter_chunks::<i32>().unwrap();
+            assert_eq!(chunks.len(), 9);
+            let mut offsets = vec![];
+            for chunk in chunks {
+                let (offset, data) = chunk.unwrap();
+                let (i, j) = (offset[0], offset[1]);
+                let expected = arr.slice(s![i..(i + 3).min(7), j..(j + 2).min(5)]);
+                assert_eq!(data, expected.into_dyn());
+                offsets.push(offset);
+            }
+            offsets.sort();
+            let mut expected = vec![];
+            for i in &[0, 3, 6] {
+                for j in &[0, 2, 4] {
+                    expected.push(vec![*i, *j]);
+                }
+            }
+            assert_eq!(offsets, expected);
+
+            let ds = file.new_dataset::<i32>().chunk(2).create("partial", 10).unwrap();
+            ds.write_slice(&[1, 2, 3], s![5..8]).unwrap();
+            let chunks = ds.iter_chunks::<i32>().unwrap().collect::<Result<Vec<_>>>().unwrap();
+            let mut offsets = chunks.iter().map(|c| c.0.clone()).collect::<Vec<_>>();
+            offsets.sort();
+            assert_eq!(offsets, vec![vec![4], vec![6]]);
+
+            let ds = file.new_dataset::<i32>().no_chunk().create("contiguous", 10).unwrap();
+            assert_err!(ds.iter_chunks::<i32>(), "requires a chunked dataset");
+        })
+    }
+
+    #[test]
+    #[cfg(hdf5_1_10_5)]
+    pub fn test_chunk_info() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u32>().chunk(4).create("partial", 20).unwrap();
+            assert!(ds.chunk_info().unwrap().is_empty());
+            ds.write_slice(&[1, 2], s![1..3]).unwrap();
+            ds.write_slice(&[3, 4, 5], s![13..16]).unwrap();
+            let mut chunks = ds.chunk_info().unwrap();
+            chunks.sort_by_key(|info| info.offset.clone());
+            assert_eq!(chunks.len(), 2);
+            assert_eq!(chunks[0].offset, vec![0]);
+            assert_eq!(chunks[1].offset, vec![12]);
+            for info in &chunks {
+                assert_eq!(info.size, 16);
+                assert_eq!(info.filter_mask, 0);
+            }
+            assert_ne!(chunks[0].addr, chunks[1].addr);
+
+            if gzip_available() {
+                let ds = file.new_dataset::<u32>().chunk(100).gzip(9).create("gz", 100).unwrap();
+                ds.write(&vec![0; 100]).unwrap();
+                let chunks = ds.chunk_info().unwrap();
+                assert_eq!(chunks.len(), 1);
+                assert!(chunks[0].size < 400);
+            }
+
+            let ds = file.new_dataset::<u32>().no_chunk().create("contiguous", 10).unwrap();
+            assert_err!(ds.chunk_info(), "Chunk info requires a chunked dataset");
+        })
+    }
+
+    #[test]
+    #[cfg(hdf5_1_10_2)]
+    pub fn test_direct_chunk_io() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u16>().chunk((2, 3)).create("raw", (4, 6)).unwrap();
+            let chunk: Vec<u8> = (1..=6_u16).flat_map(|x| x.to_ne_bytes().to_vec()).collect();
+            ds.write_chunk(&[2, 3], 0, &chunk).unwrap();
+            assert_eq!(ds.read_chunk(&[2, 3]).unwrap(), (0, chunk));
+            assert_eq!(
+                ds.read_raw::<u16>().unwrap(),
+                vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 0, 0, 0, 4, 5, 6]
+            );
+
+            assert_err!(ds.read_chunk(&[0, 0]), "has not been allocated");
+            assert_err!(ds.write_chunk(&[1, 0], 0, &[0; 12]), "must be a multiple of chunk shape");
+            assert_err!(ds.write_chunk(&[4, 0], 0, &[0; 12]), "out of bounds");
+            assert_err!(ds.read_chunk(&[0]), "Invalid chunk offset ndim: expected 2, got 1");
+
+            let ds = file.new_dataset::<u16>().no_chunk().create("contiguous", 10).unwrap();
+            assert_err!(ds.read_chunk(&[0]), "requires a chunked dataset");
+
+            if gzip_available() {
+                let data = (0..100).map(|x| x / 10).collect::<Vec<u32>>();
+                let src = file.new_dataset::<u32>().chunk(50).gzip(9).create("src", 100).unwrap();
+                src.write(&data).unwrap();
+                let dst = file.new_dataset::<u32>().chunk(50).gzip(9).create("dst", 100).unwrap();
+                for &offset in &[0, 50] {
+                    let (filter_mask, chunk) = src.read_chunk(&[offset]).unwrap();
+                    assert!(chunk.len() < 50 * 4);
+                    dst.write_chunk(&[offset], filter_mask, &chunk).unwrap();
+                }
+                assert_eq!(dst.read_raw::<u32>().unwrap(), data);
+            }
+        })
+    }
+
+    #[test]
+    #[cfg(hdf5_1_10_0)]
+    pub fn test_virtual_maps() {
+        with_tmp_dir(|dir| {
+            let paths = [dir.join("a.h5"), dir.join("b.h5")];
+            for (i, path) in paths.iter().enumerate() {
+                let file = File::create(path).unwrap();
+                let data: Vec<i32> = (0..6).map(|x| x + 10 * i as i32).collect();
+                file.new_dataset::<i32>().create("data", (2, 3)).unwrap().write_raw(&data).unwrap();
+            }
+
+            let file = File::create(dir.join("vds.h5")).unwrap();
+            let mappings = paths
+                .iter()
+                .enumerate()
+                .map(|(i, path)| {
+                    VirtualMapping::new(path.to_str().unwrap(), "data", (2, 3))
+                        .vds_selection(Hyperslab::new((0, 3 * i), (2, 3)))
+                })
+                .collect();
+            let ds = file
+                .new_dataset::<i32>()
+                .fill_value(-1)
+                .virtual_maps(mappings)
+                .create("vds", (2, 8))
+                .unwrap();
+            assert_eq!(ds.layout(), Layout::Virtual);
+            let maps = ds.virtual_maps().unwrap();
+            assert_eq!(maps.len(), 2);
+            for (i, (map, path)) in maps.iter().zip(&paths).enumerate() {
+                assert_eq!(map.src_file, path.to_str().unwrap());
+                assert_eq!(map.src_dataset, "data");
+                assert_eq!(map.src_shape, vec![2, 3]);
+                assert_eq!(map.vds_selection, Hyperslab::new((0, 3 * i), (2, 3)).into());
+                assert_eq!(
+                    map.src_selection.npoints(&Dataspace::try_new((2, 3), false).unwrap()).unwrap(),
+                    6
+                );
+            }
+            assert_eq!(
+                ds.read_2d::<i32>().unwrap(),
+                arr2(&[[0, 1, 2, 10, 11, 12, -1, -1], [3, 4, 5, 13, 14, 15, -1, -1]])
+            );
+            // reading across the seam between the two source files
+            assert_eq!(ds.read_slice_1d::<i32, _>(&s![1, 1..5]).unwrap().to_vec(), [4, 5, 13, 14]);
+
+            // missing source files are read as the fill value
+            let mapping = VirtualMapping::new("missing.h5", "data", 4);
+            let ds = file
+                .new_dataset::<i32>()
+                .fill_value(-1)
+                .virtual_maps(vec![mapping])
+                .create("missing", 4)
+                .unwrap();
+            assert_eq!(ds.read_raw::<i32>().unwrap(), vec![-1; 4]);
+            assert_eq!(ds.virtual_maps().unwrap()[0].src_file, "missing.h5");
+            let ds = file.new_dataset::<i32>().create("plain", 4).unwrap();
+            assert!(ds.virtual_maps().unwrap().is_empty());
+
+            let mapping = VirtualMapping::new(".", "vds", (2, 8))
+                .src_selection(Hyperslab::new((0, 0), (1, 3)));
+            assert_err!(
+                file.new_dataset::<i32>().virtual_maps(vec![mapping.clone()]).create_anon(4),
+                "selection size mismatch: 4 elements in the virtual dataset, 3 elements in \"vds\""
+            );
+            assert_err!(
+                file.new_dataset::<i32>().chunk(3).virtual_maps(vec![mapping]).create_anon(3),
+                "Virtual datasets cannot be chunked or compact"
+            );
+        })
+    }
+
+    #[test]
+    #[cfg(hdf5_1_10_0)]
+    pub fn test_flush() {
+        with_tmp_path(|path| {
+            let file = File::create(&path).unwrap();
+            let ds = file.new_dataset::<i32>().chunk(4).create("foo", 10).unwrap();
+            ds.write(&(0..10).collect::<Vec<i32>>()).unwrap();
+            ds.flush().unwrap();
+            let other = File::open(&path).unwrap();
+            assert_eq!(
+                other.dataset("foo").unwrap().read_raw::<i32>().unwrap(),
+                (0..10).collect::<Vec<i32>>()
+            );
+        })
+    }
+
+    #[test]
+    pub fn test_append() {
+        with_tmp_file(|file| {
+            let ds = file
+                .new_dataset::<i32>()
+                .chunk((4, 3))
+                .resizable(true)
+                .create("foo", (0, 3))
+                .unwrap();
+            let mut expected = vec![];
+            for batch in 0..10 {
+                let rows = batch % 4 + 1;
+                let arr =
+                    Array2::from_shape_fn((rows, 3), |(i, j)| (batch * 100 + i * 3 + j) as i32);
+                ds.append(0, &arr).unwrap();
+                expected.extend(arr.iter().cloned());
+            }
+            assert_eq!(ds.shape(), vec![23, 3]);
+            assert_eq!(ds.read_raw::<i32>().unwrap(), expected);
+
+            ds.append(0, &Array2::<i32>::zeros((0, 3))).unwrap();
+            assert_eq!(ds.shape(), vec![23, 3]);
+            ds.append(1, &Array2::<i32>::ones((23, 2))).unwrap();
+            assert_eq!(ds.shape(), vec![23, 5]);
+
+            assert_err!(
+                ds.append(0, &Array2::<i32>::zeros((2, 3))),
+                "shape mismatch when appending"
+            );
+            assert_err!(ds.append(2, &Array2::<i32>::zeros((23, 5))), "Invalid axis 2");
+            assert_err!(ds.append(0, &[1, 2, 3]), "ndim mismatch when appending");
+
+            let ds = file.new_dataset::<i32>().create("fixed", (2, 3)).unwrap();
+            assert_err!(ds.append(0, &Array2::<i32>::zeros((1, 3))), "not extendible beyond 2");
+        })
+    }
+
+    #[test]
+    pub fn test_point_selection() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<i32>().create("foo", (4, 5)).unwrap();
+            ds.write_raw(&(0..20).collect::<Vec<i32>>()).unwrap();
+
+            let points: Array2<Ix> = arr2(&[[3, 4], [0, 1], [2, 2], [0, 1]]);
+            let data = ds.read_selection::<i32, _>(points.clone()).unwrap();
+            assert_eq!(data.to_vec(), vec![19, 1, 12, 1]);
+
+            let points: Array2<Ix> = arr2(&[[1, 0], [3, 3], [0, 4]]);
+            ds.write_selection(&[-1, -2, -3], points.clone()).unwrap();
+            assert_eq!(ds.read_selection::<i32, _>(points).unwrap().to_vec(), vec![-1, -2, -3]);
+            let data = ds.read_raw::<i32>().unwrap();
+            assert_eq!((data[5], data[18], data[4], data[6]), (-1, -2, -3, 6));
+
+            let points: Array2<Ix> = arr2(&[[1, 0], [3, 3]]);
+            assert_err!(
+                ds.write_selection(&[1, 2, 3], points.clone()),
+                "size mismatch when writing selection: memory = 3, selection = 2"
+            );
+            assert_err!(
+                ds.read_selection::<i32, _>(Selection::Points(arr2(&[[4, 0]]))),
+                "out of dataspace bounds"
+            );
+            assert_err!(
+                ds.read_selection::<i32, _>(Selection::Points(arr2(&[[1, 0, 0]]))),
+                "rank mismatch"
+            );
+
+            let r = ds.region_ref(points.clone()).unwrap();
+            let (_, sel) = file.dereference_region(&r).unwrap();
+            assert_eq!(sel, Selection::Points(points));
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    pub fn test_timestamps() {
+        use chrono::NaiveDate;
+        use hdf5_types::{IntSize, Timestamp, TypeDescriptor};
+
+        with_tmp_file(|file| {
+            let datetimes = vec![
+                NaiveDate::from_ymd_opt(1969, 7, 20).unwrap().and_hms_opt(20, 17, 40).unwrap(),
+                NaiveDate::from_ymd_opt(2019, 1, 1).unwrap().and_hms_nano_opt(0, 0, 0, 1).unwrap(),
+            ];
+            let timestamps = datetimes
+                .iter()
+                .map(Timestamp::from_datetime)
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .unwrap();
+            let ds = file.new_dataset::<Timestamp>().create("times", 2).unwrap();
+            ds.write(&timestamps).unwrap();
+            assert_eq!(
+                ds.dtype().unwrap().to_descriptor().unwrap(),
+                TypeDescriptor::Integer(IntSize::U8)
+            );
+            assert_eq!(
+                ds.read_raw::<i64>().unwrap(),
+                vec![-14_182_940_000_000_000, 1_546_300_800_000_000_001]
+            );
+            let read: Vec<_> =
+                ds.read_raw::<Timestamp>().unwrap().into_iter().map(|t| t.to_datetime()).collect();
+            assert_eq!(read, datetimes);
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "io-stats")]
+    pub fn test_io_stats() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u32>().create("foo", (2, 3)).unwrap();
+            assert_eq!(ds.io_stats(), IoStats::default());
+            ds.write(&arr2(&[[1, 2, 3], [4, 5, 6]])).unwrap();
+            ds.write_slice(&[7, 8], s![1, 1..]).unwrap();
+            assert_eq!(ds.read_2d::<u32>().unwrap(), arr2(&[[1, 2, 3], [4, 7, 8]]));
+            assert_eq!(ds.read_slice_1d::<u32, _>(&s![0, ..2]).unwrap().to_vec(), [1, 2]);
+            ds.new_attr::<u32>().create("attr", ()).unwrap().write_scalar(&1).unwrap();
+            let stats = ds.io_stats();
+            assert_eq!((stats.bytes_read, stats.bytes_written), (32, 32));
+            assert_eq!((stats.read_calls, stats.write_calls), (2, 2));
+            assert!(stats.elapsed > Duration::from_secs(0));
+            assert_eq!(ds.clone().io_stats(), stats);
+            assert_eq!(file.dataset("foo").unwrap().io_stats(), IoStats::default());
+            assert_eq!(
+                file.new_dataset::<u8>().create("bar", 1).unwrap().io_stats(),
+                IoStats::default()
+            );
+            ds.reset_io_stats();
+            assert_eq!(ds.io_stats(), IoStats::default());
+
+            // Statistics of closed handles are dropped.
+            ds.read_raw::<u32>().unwrap();
+            let id = ds.id();
+            assert!(IO_STATS.lock().contains_key(&id));
+            drop(ds);
+            assert!(!IO_STATS.lock().contains_key(&id));
+        })
+    }
 }
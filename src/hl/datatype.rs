@@ -4,30 +4,35 @@ use std::fmt::{self, Debug, Display};
 use std::ops::Deref;
 
 use hdf5_sys::h5t::{
-    H5T_cdata_t, H5T_class_t, H5T_cset_t, H5T_str_t, H5Tarray_create2, H5Tcompiler_conv, H5Tcopy,
-    H5Tcreate, H5Tenum_create, H5Tenum_insert, H5Tequal, H5Tfind, H5Tget_array_dims2,
-    H5Tget_array_ndims, H5Tget_class, H5Tget_cset, H5Tget_member_name, H5Tget_member_offset,
-    H5Tget_member_type, H5Tget_member_value, H5Tget_nmembers, H5Tget_sign, H5Tget_size,
-    H5Tget_super, H5Tinsert, H5Tis_variable_str, H5Tset_cset, H5Tset_size, H5Tset_strpad,
+    H5T_cdata_t, H5T_class_t, H5T_cset_t, H5T_order_t, H5T_str_t, H5Tarray_create2, H5Tcommitted,
+    H5Tcompiler_conv, H5Tcopy, H5Tcreate, H5Tenum_create, H5Tenum_insert, H5Tequal, H5Tfind,
+    H5Tget_array_dims2, H5Tget_array_ndims, H5Tget_class, H5Tget_cset, H5Tget_member_name,
+    H5Tget_member_offset, H5Tget_member_type, H5Tget_member_value, H5Tget_nmembers, H5Tget_offset,
+    H5Tget_order, H5Tget_precision, H5Tget_sign, H5Tget_size, H5Tget_strpad, H5Tget_super,
+    H5Tget_tag, H5Tinsert, H5Tis_variable_str, H5Tset_cset, H5Tset_ebias, H5Tset_fields,
+    H5Tset_offset, H5Tset_order, H5Tset_precision, H5Tset_size, H5Tset_strpad, H5Tset_tag,
     H5Tvlen_create, H5T_VARIABLE,
 };
 use hdf5_types::{
-    CompoundField, CompoundType, EnumMember, EnumType, FloatSize, H5Type, IntSize, TypeDescriptor,
+    CompoundField, CompoundType, EnumMember, EnumType, FloatSize, H5Type, IntSize, OpaqueType,
+    TypeDescriptor,
 };
 
-use crate::globals::{H5T_C_S1, H5T_NATIVE_INT, H5T_NATIVE_INT8};
+use crate::globals::{H5T_C_S1, H5T_NATIVE_INT, H5T_NATIVE_INT8, H5T_STD_REF_DSETREG};
 use crate::internal_prelude::*;
 
 #[cfg(target_endian = "big")]
 use crate::globals::{
-    H5T_IEEE_F32BE, H5T_IEEE_F64BE, H5T_STD_I16BE, H5T_STD_I32BE, H5T_STD_I64BE, H5T_STD_I8BE,
-    H5T_STD_U16BE, H5T_STD_U32BE, H5T_STD_U64BE, H5T_STD_U8BE,
+    H5T_IEEE_F32BE, H5T_IEEE_F64BE, H5T_STD_B16BE, H5T_STD_B32BE, H5T_STD_B64BE, H5T_STD_B8BE,
+    H5T_STD_I16BE, H5T_STD_I32BE, H5T_STD_I64BE, H5T_STD_I8BE, H5T_STD_U16BE, H5T_STD_U32BE,
+    H5T_STD_U64BE, H5T_STD_U8BE,
 };
 
 #[cfg(target_endian = "little")]
 use crate::globals::{
-    H5T_IEEE_F32LE, H5T_IEEE_F64LE, H5T_STD_I16LE, H5T_STD_I32LE, H5T_STD_I64LE, H5T_STD_I8LE,
-    H5T_STD_U16LE, H5T_STD_U32LE, H5T_STD_U64LE, H5T_STD_U8LE,
+    H5T_IEEE_F32LE, H5T_IEEE_F64LE, H5T_STD_B16LE, H5T_STD_B32LE, H5T_STD_B64LE, H5T_STD_B8LE,
+    H5T_STD_I16LE, H5T_STD_I32LE, H5T_STD_I64LE, H5T_STD_I8LE, H5T_STD_U16LE, H5T_STD_U32LE,
+    H5T_STD_U64LE, H5T_STD_U8LE,
 };
 
 #[cfg(target_endian = "big")]
@@ -119,12 +124,308 @@ impl Default for Conversion {
     }
 }
 
+/// Policy restricting the conversions between stored and in-memory element types.
+///
+/// Unlike `Conversion`, which refers to how the library implements a conversion, the policy
+/// refers to whether the conversion may lose information.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConversionPolicy {
+    /// The element types must match exactly (byte order aside).
+    Strict,
+    /// Only conversions that preserve all values (e.g. widening integers) are allowed.
+    Lossless,
+    /// Any conversion supported by the library is allowed; integers that don't fit are
+    /// clamped and floats may lose precision (default).
+    Lossy,
+}
+
+impl Default for ConversionPolicy {
+    fn default() -> Self {
+        ConversionPolicy::Lossy
+    }
+}
+
+impl ConversionPolicy {
+    pub(crate) fn ensure_allowed(self, src: &Datatype, dst: &TypeDescriptor) -> Result<()> {
+        if self == ConversionPolicy::Lossy {
+            return Ok(());
+        }
+        let src = src.to_descriptor()?;
+        if self == ConversionPolicy::Strict {
+            ensure!(
+                src == *dst,
+                "Strict conversion policy: stored type {:?} doesn't match {:?}",
+                src,
+                dst
+            );
+        } else {
+            ensure!(
+                is_lossless(&src, dst),
+                "Lossless conversion policy: converting {:?} into {:?} may lose data",
+                src,
+                dst
+            );
+        }
+        Ok(())
+    }
+}
+
+fn is_lossless(src: &TypeDescriptor, dst: &TypeDescriptor) -> bool {
+    use hdf5_types::TypeDescriptor::*;
+    match (src, dst) {
+        _ if src == dst => true,
+        (Integer(a), Integer(b)) | (Unsigned(a), Unsigned(b)) => b >= a,
+        (Unsigned(a), Integer(b)) => b > a,
+        // Integers are exactly representable if they fit into the mantissa.
+        (Integer(_), Float(_)) | (Unsigned(_), Float(_)) => src.size() * 2 <= dst.size(),
+        (Float(_), Float(_)) => dst.size() >= src.size(),
+        (FixedAscii(a), FixedAscii(b)) | (FixedUnicode(a), FixedUnicode(b)) => b >= a,
+        (VarLenAscii, VarLenUnicode) => true,
+        (FixedArray(a, n), FixedArray(b, m)) => n == m && is_lossless(a, b),
+        (VarLenArray(a), VarLenArray(b)) => is_lossless(a, b),
+        (Compound(a), Compound(b)) => b.fields.iter().all(|f| {
+            a.fields.iter().find(|g| g.name == f.name).map_or(false, |g| is_lossless(&g.ty, &f.ty))
+        }),
+        _ => false,
+    }
+}
+
+/// Byte order of an atomic datatype.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ByteOrder {
+    LittleEndian,
+    BigEndian,
+    /// Byte order of the host.
+    Native,
+}
+
+impl ByteOrder {
+    /// Resolves `Native` into the byte order of the host.
+    pub fn resolve(self) -> Self {
+        match self {
+            ByteOrder::Native if cfg!(target_endian = "big") => ByteOrder::BigEndian,
+            ByteOrder::Native => ByteOrder::LittleEndian,
+            order => order,
+        }
+    }
+}
+
+/// Padding of fixed-length strings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StringPadding {
+    /// Null-terminated; the last byte is always reserved for the terminator, so strings
+    /// filling the whole capacity are truncated when stored.
+    NullTerm,
+    /// Padded with nulls (default for fixed-length strings).
+    NullPad,
+    /// Padded with spaces (as in Fortran); trailing spaces are stripped on reading.
+    SpacePad,
+}
+
+impl From<StringPadding> for H5T_str_t {
+    fn from(padding: StringPadding) -> Self {
+        match padding {
+            StringPadding::NullTerm => H5T_str_t::H5T_STR_NULLTERM,
+            StringPadding::NullPad => H5T_str_t::H5T_STR_NULLPAD,
+            StringPadding::SpacePad => H5T_str_t::H5T_STR_SPACEPAD,
+        }
+    }
+}
+
+/// Character encoding of a string datatype.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CharEncoding {
+    Ascii,
+    Utf8,
+}
+
+impl From<CharEncoding> for H5T_cset_t {
+    fn from(encoding: CharEncoding) -> Self {
+        match encoding {
+            CharEncoding::Ascii => H5T_cset_t::H5T_CSET_ASCII,
+            CharEncoding::Utf8 => H5T_cset_t::H5T_CSET_UTF8,
+        }
+    }
+}
+
+impl Display for CharEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            CharEncoding::Ascii => "ASCII",
+            CharEncoding::Utf8 => "UTF-8",
+        })
+    }
+}
+
+/// Class of a datatype.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TypeClass {
+    Integer,
+    Float,
+    Time,
+    /// Fixed-length or variable-length strings.
+    String,
+    Bitfield,
+    Opaque,
+    Compound,
+    Reference,
+    Enum,
+    /// Variable-length sequences (excluding variable-length strings).
+    VarLen,
+    Array,
+}
+
 impl Datatype {
+    /// Returns the class of the datatype.
+    pub fn class(&self) -> Result<TypeClass> {
+        use hdf5_sys::h5t::H5T_class_t::*;
+        Ok(match h5lock!(H5Tget_class(self.id())) {
+            H5T_INTEGER => TypeClass::Integer,
+            H5T_FLOAT => TypeClass::Float,
+            H5T_TIME => TypeClass::Time,
+            H5T_STRING => TypeClass::String,
+            H5T_BITFIELD => TypeClass::Bitfield,
+            H5T_OPAQUE => TypeClass::Opaque,
+            H5T_COMPOUND => TypeClass::Compound,
+            H5T_REFERENCE => TypeClass::Reference,
+            H5T_ENUM => TypeClass::Enum,
+            H5T_VLEN => TypeClass::VarLen,
+            H5T_ARRAY => TypeClass::Array,
+            class => fail!("Invalid datatype class: {:?}", class),
+        })
+    }
+
+    /// Returns whether an integer datatype is signed (`None` for other datatypes).
+    pub fn is_signed(&self) -> Option<bool> {
+        use hdf5_sys::h5t::H5T_sign_t::*;
+        h5lock!({
+            if H5Tget_class(self.id()) != H5T_class_t::H5T_INTEGER {
+                return None;
+            }
+            match H5Tget_sign(self.id()) {
+                H5T_SGN_NONE => Some(false),
+                H5T_SGN_2 => Some(true),
+                _ => None,
+            }
+        })
+    }
+
+    /// Returns the fields of a compound datatype (in the stored order) along with their
+    /// offsets and datatypes.
+    pub fn compound_fields(&self) -> Result<Vec<CompoundField>> {
+        ensure!(self.class()? == TypeClass::Compound, "Not a compound datatype");
+        match self.to_descriptor()? {
+            TypeDescriptor::Compound(tp) => Ok(tp.fields),
+            _ => fail!("Not a compound datatype"),
+        }
+    }
+
     /// Get the total size of the datatype in bytes.
     pub fn size(&self) -> usize {
         h5call!(H5Tget_size(self.id())).unwrap_or(0) as usize
     }
 
+    /// Returns `true` if the datatype is committed to a file (i.e., it's a named datatype).
+    pub fn is_committed(&self) -> bool {
+        h5lock!(H5Tcommitted(self.id()) > 0)
+    }
+
+    /// Returns the number of significant bits of an integer datatype.
+    pub fn precision(&self) -> usize {
+        h5call!(H5Tget_precision(self.id())).unwrap_or(0) as usize
+    }
+
+    /// Returns the offset of the first significant bit of an integer datatype.
+    pub fn offset(&self) -> usize {
+        h5call!(H5Tget_offset(self.id())).unwrap_or(0) as usize
+    }
+
+    /// Sets the number of significant bits and the offset of the first significant bit
+    /// of an integer datatype (the remaining bits are padding).
+    pub fn set_precision(&self, precision: usize, offset: usize) -> Result<()> {
+        h5lock!({
+            match H5Tget_class(self.id()) {
+                H5T_class_t::H5T_INTEGER | H5T_class_t::H5T_BITFIELD => {}
+                _ => fail!("Precision can only be set for integer datatypes"),
+            }
+            let bits = self.size() * 8;
+            ensure!(
+                precision > 0 && precision + offset <= bits,
+                "Invalid precision {} with offset {} for a {}-bit integer datatype",
+                precision,
+                offset,
+                bits
+            );
+            // The precision goes first so that the offset always fits into the datatype.
+            h5try!(H5Tset_precision(self.id(), precision as _));
+            h5try!(H5Tset_offset(self.id(), offset as _));
+            Ok(())
+        })
+    }
+
+    /// Returns the byte order of an atomic datatype, or `None` if it's undefined (e.g. for
+    /// strings) or neither little-endian nor big-endian.
+    pub fn byte_order(&self) -> Option<ByteOrder> {
+        let _e = silence_errors();
+        match h5lock!(H5Tget_order(self.id())) {
+            H5T_order_t::H5T_ORDER_LE => Some(ByteOrder::LittleEndian),
+            H5T_order_t::H5T_ORDER_BE => Some(ByteOrder::BigEndian),
+            _ => None,
+        }
+    }
+
+    /// Sets the byte order of an atomic datatype.
+    pub fn set_byte_order(&self, order: ByteOrder) -> Result<()> {
+        let order = match order.resolve() {
+            ByteOrder::BigEndian => H5T_order_t::H5T_ORDER_BE,
+            _ => H5T_order_t::H5T_ORDER_LE,
+        };
+        h5call!(H5Tset_order(self.id(), order)).and(Ok(()))
+    }
+
+    /// Returns the character encoding of a string datatype (`None` for other datatypes).
+    pub fn string_encoding(&self) -> Option<CharEncoding> {
+        h5lock!({
+            if H5Tget_class(self.id()) != H5T_class_t::H5T_STRING {
+                return None;
+            }
+            match H5Tget_cset(self.id()) {
+                H5T_cset_t::H5T_CSET_ASCII => Some(CharEncoding::Ascii),
+                H5T_cset_t::H5T_CSET_UTF8 => Some(CharEncoding::Utf8),
+                _ => None,
+            }
+        })
+    }
+
+    /// Returns the padding of a fixed-length string datatype (`None` for other datatypes).
+    pub fn string_padding(&self) -> Option<StringPadding> {
+        h5lock!({
+            if H5Tget_class(self.id()) != H5T_class_t::H5T_STRING
+                || H5Tis_variable_str(self.id()) != 0
+            {
+                return None;
+            }
+            match H5Tget_strpad(self.id()) {
+                H5T_str_t::H5T_STR_NULLTERM => Some(StringPadding::NullTerm),
+                H5T_str_t::H5T_STR_NULLPAD => Some(StringPadding::NullPad),
+                H5T_str_t::H5T_STR_SPACEPAD => Some(StringPadding::SpacePad),
+                _ => None,
+            }
+        })
+    }
+
+    /// Sets the padding of a fixed-length string datatype.
+    pub fn set_string_padding(&self, padding: StringPadding) -> Result<()> {
+        h5lock!({
+            ensure!(
+                H5Tget_class(self.id()) == H5T_class_t::H5T_STRING
+                    && H5Tis_variable_str(self.id()) == 0,
+                "String padding can only be set for fixed-length string datatypes"
+            );
+            h5call!(H5Tset_strpad(self.id(), padding.into())).and(Ok(()))
+        })
+    }
+
     pub fn conv_path<D>(&self, dst: D) -> Option<Conversion>
     where
         D: Borrow<Self>,
@@ -163,6 +464,16 @@ impl Datatype {
 
     pub(crate) fn ensure_convertible(&self, dst: &Self, required: Conversion) -> Result<()> {
         // TODO: more detailed error messages after Debug/Display are implemented for Datatype
+        if let (Some(src), Some(dst)) = (self.string_encoding(), dst.string_encoding()) {
+            // Conversions between encodings are allowed by the library but don't validate
+            // anything, so UTF-8 strings could otherwise end up in ASCII strings.
+            ensure!(
+                src == dst || src == CharEncoding::Ascii,
+                "string encoding mismatch: cannot convert {} strings into {} strings",
+                src,
+                dst
+            );
+        }
         if let Some(conv) = self.conv_path(dst) {
             if conv > required {
                 fail!("{} conversion path required; available: {} conversion", required, conv)
@@ -195,6 +506,11 @@ impl Datatype {
                     let size = FloatSize::from_int(size).ok_or("Invalid size of float datatype")?;
                     Ok(TD::Float(size))
                 }
+                H5T_BITFIELD => {
+                    let size =
+                        IntSize::from_int(size).ok_or("Invalid size of bitfield datatype")?;
+                    Ok(TD::Bitfield(size))
+                }
                 H5T_ENUM => {
                     let mut members: Vec<EnumMember> = Vec::new();
                     for idx in 0..h5try!(H5Tget_nmembers(id)) as _ {
@@ -239,13 +555,15 @@ impl Datatype {
                 H5T_ARRAY => {
                     let base_dt = Self::from_id(H5Tget_super(id))?;
                     let ndims = h5try!(H5Tget_array_ndims(id));
-                    if ndims == 1 {
-                        let mut len: hsize_t = 0;
-                        h5try!(H5Tget_array_dims2(id, &mut len as *mut _));
-                        Ok(TD::FixedArray(Box::new(base_dt.to_descriptor()?), len as _))
-                    } else {
-                        Err("Multi-dimensional array datatypes are not supported".into())
+                    let mut dims: Vec<hsize_t> = vec![0; ndims as _];
+                    h5try!(H5Tget_array_dims2(id, dims.as_mut_ptr()));
+                    // Multi-dimensional arrays are represented as nested arrays, which have
+                    // the same memory layout.
+                    let mut desc = base_dt.to_descriptor()?;
+                    for &len in dims.iter().rev() {
+                        desc = TD::FixedArray(Box::new(desc), len as _);
                     }
+                    Ok(desc)
                 }
                 H5T_STRING => {
                     let is_variable = h5try!(H5Tis_variable_str(id)) == 1;
@@ -262,6 +580,24 @@ impl Datatype {
                     let base_dt = Self::from_id(H5Tget_super(id))?;
                     Ok(TD::VarLenArray(Box::new(base_dt.to_descriptor()?)))
                 }
+                H5T_REFERENCE => {
+                    if h5try!(H5Tequal(id, *H5T_STD_REF_DSETREG)) > 0 {
+                        Ok(TD::RegionReference)
+                    } else {
+                        Err("Unsupported reference datatype".into())
+                    }
+                }
+                H5T_OPAQUE => {
+                    let tag = H5Tget_tag(id);
+                    let opaque = if tag.is_null() {
+                        OpaqueType { size, tag: "".into() }
+                    } else {
+                        let opaque = OpaqueType { size, tag: string_from_cstr(tag) };
+                        libc::free(tag as *mut _);
+                        opaque
+                    };
+                    Ok(TD::Opaque(opaque))
+                }
                 _ => Err("Unsupported datatype class".into()),
             }
         })
@@ -276,14 +612,11 @@ impl Datatype {
 
         unsafe fn string_type(size: Option<usize>, encoding: H5T_cset_t) -> Result<hid_t> {
             let string_id = h5try!(H5Tcopy(*H5T_C_S1));
-            let padding = if size.is_none() {
-                H5T_str_t::H5T_STR_NULLTERM
-            } else {
-                H5T_str_t::H5T_STR_NULLPAD
-            };
+            let padding =
+                if size.is_none() { StringPadding::NullTerm } else { StringPadding::NullPad };
             let size = size.unwrap_or(H5T_VARIABLE);
             h5try!(H5Tset_cset(string_id, encoding));
-            h5try!(H5Tset_strpad(string_id, padding));
+            h5try!(H5Tset_strpad(string_id, padding.into()));
             h5try!(H5Tset_size(string_id, size));
             Ok(string_id)
         }
@@ -303,9 +636,24 @@ impl Datatype {
                     IntSize::U8 => be_le!(H5T_STD_U64BE, H5T_STD_U64LE),
                 }),
                 TD::Float(size) => Ok(match size {
+                    FloatSize::U2 => {
+                        // IEEE 754 half-precision, derived from a single-precision type in the
+                        // same way as h5py does it for NumPy's float16.
+                        let float_id = be_le!(H5T_IEEE_F32BE, H5T_IEEE_F32LE);
+                        h5try!(H5Tset_fields(float_id, 15, 10, 5, 0, 10));
+                        h5try!(H5Tset_size(float_id, 2));
+                        h5try!(H5Tset_ebias(float_id, 15));
+                        float_id
+                    }
                     FloatSize::U4 => be_le!(H5T_IEEE_F32BE, H5T_IEEE_F32LE),
                     FloatSize::U8 => be_le!(H5T_IEEE_I16BE, H5T_IEEE_F64LE),
                 }),
+                TD::Bitfield(size) => Ok(match size {
+                    IntSize::U1 => be_le!(H5T_STD_B8BE, H5T_STD_B8LE),
+                    IntSize::U2 => be_le!(H5T_STD_B16BE, H5T_STD_B16LE),
+                    IntSize::U4 => be_le!(H5T_STD_B32BE, H5T_STD_B32LE),
+                    IntSize::U8 => be_le!(H5T_STD_B64BE, H5T_STD_B64LE),
+                }),
                 TD::Boolean => {
                     let bool_id = h5try!(H5Tenum_create(*H5T_NATIVE_INT8));
                     h5try!(H5Tenum_insert(
@@ -357,9 +705,57 @@ impl Datatype {
                 }
                 TD::VarLenAscii => string_type(None, H5T_cset_t::H5T_CSET_ASCII),
                 TD::VarLenUnicode => string_type(None, H5T_cset_t::H5T_CSET_UTF8),
+                TD::RegionReference => Ok(h5try!(H5Tcopy(*H5T_STD_REF_DSETREG))),
+                TD::Opaque(ref opaque) => {
+                    ensure!(
+                        opaque.tag.len() < 256,
+                        "Opaque datatype tag must be shorter than 256 bytes: {:?}",
+                        opaque.tag
+                    );
+                    let opaque_id = h5try!(H5Tcreate(H5T_class_t::H5T_OPAQUE, opaque.size));
+                    if !opaque.tag.is_empty() {
+                        let tag = to_cstring(opaque.tag.as_ref())?;
+                        h5try!(H5Tset_tag(opaque_id, tag.as_ptr()));
+                    }
+                    Ok(opaque_id)
+                }
             }
         });
 
         Self::from_id(datatype_id?)
     }
+
+    /// Creates an in-memory datatype for nested arrays described by `desc` that matches the
+    /// shape of the multi-dimensional array datatype `file_dtype` (e.g. a NumPy subarray
+    /// dtype written by h5py), since HDF5 can only convert between arrays of the same rank.
+    /// Returns `None` if `file_dtype` is not a multi-dimensional array or the shapes differ.
+    pub(crate) fn nested_array_like(
+        desc: &TypeDescriptor, file_dtype: &Self,
+    ) -> Result<Option<Self>> {
+        use hdf5_types::TypeDescriptor as TD;
+
+        let dims: Result<Vec<hsize_t>> = h5lock!({
+            let id = file_dtype.id();
+            if H5Tget_class(id) != H5T_class_t::H5T_ARRAY {
+                return Ok(vec![]);
+            }
+            let mut dims: Vec<hsize_t> = vec![0; h5try!(H5Tget_array_ndims(id)) as _];
+            h5try!(H5Tget_array_dims2(id, dims.as_mut_ptr()));
+            Ok(dims)
+        });
+        let dims = dims?;
+        if dims.len() < 2 {
+            return Ok(None);
+        }
+        let mut base = desc;
+        for &dim in &dims {
+            match *base {
+                TD::FixedArray(ref ty, len) if len as hsize_t == dim => base = ty,
+                _ => return Ok(None),
+            }
+        }
+        let base_dt = Self::from_descriptor(base)?;
+        Self::from_id(h5try!(H5Tarray_create2(base_dt.id(), dims.len() as _, dims.as_ptr())))
+            .map(Some)
+    }
 }
@@ -0,0 +1,375 @@
+//! Dimension scales, compatible with the HDF5 high-level `H5DS` API.
+//!
+//! Dimension scales are stored as attributes following the HDF5 dimension scale
+//! specification, so they are recognized by other tools (e.g. netCDF-4 and h5py).
+
+use std::mem;
+use std::ptr;
+use std::slice;
+
+use hdf5_sys::{
+    h5a::{H5Acreate2, H5Adelete, H5Aexists, H5Aget_type, H5Aread, H5Awrite},
+    h5d::H5Dvlen_reclaim,
+    h5r::{hobj_ref_t, H5Rcreate, H5R_OBJECT},
+    h5t::{
+        hvl_t, H5T_class_t, H5Tcopy, H5Tcreate, H5Tget_class, H5Tget_size, H5Tinsert,
+        H5Tis_variable_str, H5Tset_size, H5Tvlen_create,
+    },
+};
+
+#[cfg(not(hdf5_1_10_0))]
+use hdf5_sys::h5r::H5Rdereference;
+#[cfg(hdf5_1_10_0)]
+use hdf5_sys::h5r::H5Rdereference2;
+
+use crate::globals::{H5T_C_S1, H5T_NATIVE_INT, H5T_STD_REF_OBJ};
+use crate::internal_prelude::*;
+
+const CLASS: &str = "CLASS";
+const NAME: &str = "NAME";
+const DIMENSION_LIST: &str = "DIMENSION_LIST";
+const REFERENCE_LIST: &str = "REFERENCE_LIST";
+const DIMENSION_SCALE_CLASS: &str = "DIMENSION_SCALE";
+
+/// An entry of the `REFERENCE_LIST` attribute of a dimension scale.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct ReferenceEntry {
+    dataset: hobj_ref_t,
+    dimension: c_int,
+}
+
+fn attr_exists(obj: &Location, name: &str) -> Result<bool> {
+    let name = to_cstring(name)?;
+    Ok(h5call!(H5Aexists(obj.id(), name.as_ptr()))? > 0)
+}
+
+fn delete_attr(obj: &Location, name: &str) -> Result<()> {
+    if attr_exists(obj, name)? {
+        let name = to_cstring(name)?;
+        h5try!(H5Adelete(obj.id(), name.as_ptr()));
+    }
+    Ok(())
+}
+
+fn write_attr<D: Dimension>(
+    obj: &Location, name: &str, datatype: &Datatype, shape: D, buf: *const c_void,
+) -> Result<()> {
+    h5lock!({
+        delete_attr(obj, name)?;
+        let dataspace = Dataspace::try_new(shape, false)?;
+        let name = to_cstring(name)?;
+        let attr = Attribute::from_id(h5try!(H5Acreate2(
+            obj.id(),
+            name.as_ptr(),
+            datatype.id(),
+            dataspace.id(),
+            H5P_DEFAULT,
+            H5P_DEFAULT
+        )))?;
+        h5call!(H5Awrite(attr.id(), datatype.id(), buf)).and(Ok(()))
+    })
+}
+
+fn write_string_attr(obj: &Location, name: &str, value: &str) -> Result<()> {
+    let value = to_cstring(value)?;
+    let bytes = value.as_bytes_with_nul();
+    h5lock!({
+        let datatype = Datatype::from_id(h5try!(H5Tcopy(*H5T_C_S1)))?;
+        h5try!(H5Tset_size(datatype.id(), bytes.len()));
+        write_attr(obj, name, &datatype, (), bytes.as_ptr() as *const _)
+    })
+}
+
+fn read_string_attr(obj: &Location, name: &str) -> Result<Option<String>> {
+    h5lock!({
+        if !attr_exists(obj, name)? {
+            return Ok(None);
+        }
+        let attr = obj.attr(name)?;
+        let datatype = Datatype::from_id(h5try!(H5Aget_type(attr.id())))?;
+        if H5Tget_class(datatype.id()) != H5T_class_t::H5T_STRING
+            || h5call!(H5Tis_variable_str(datatype.id()))? > 0
+            || attr.size() != 1
+        {
+            return Ok(None);
+        }
+        let mut buf = vec![0 as c_char; H5Tget_size(datatype.id())];
+        h5try!(H5Aread(attr.id(), datatype.id(), buf.as_mut_ptr() as *mut _));
+        Ok(Some(string_from_fixed_bytes(&buf, buf.len())))
+    })
+}
+
+fn ref_list_datatype() -> Result<Datatype> {
+    h5lock!({
+        let datatype = Datatype::from_id(h5try!(H5Tcreate(
+            H5T_class_t::H5T_COMPOUND,
+            mem::size_of::<ReferenceEntry>()
+        )))?;
+        let (dataset, dimension) = (to_cstring("dataset")?, to_cstring("dimension")?);
+        h5try!(H5Tinsert(datatype.id(), dataset.as_ptr(), 0, *H5T_STD_REF_OBJ));
+        h5try!(H5Tinsert(
+            datatype.id(),
+            dimension.as_ptr(),
+            mem::size_of::<hobj_ref_t>(),
+            *H5T_NATIVE_INT
+        ));
+        Ok(datatype)
+    })
+}
+
+fn dim_list_datatype() -> Result<Datatype> {
+    h5lock!(Datatype::from_id(h5try!(H5Tvlen_create(*H5T_STD_REF_OBJ))))
+}
+
+impl Dataset {
+    fn object_ref(&self) -> Result<hobj_ref_t> {
+        let mut reference: hobj_ref_t = 0;
+        let name = to_cstring(".")?;
+        h5call!(H5Rcreate(
+            &mut reference as *mut _ as *mut _,
+            self.id(),
+            name.as_ptr(),
+            H5R_OBJECT,
+            -1
+        ))?;
+        Ok(reference)
+    }
+
+    fn dereference(&self, reference: hobj_ref_t) -> Result<Dataset> {
+        let ptr = &reference as *const _ as *const _;
+        h5lock!({
+            #[cfg(hdf5_1_10_0)]
+            let obj_id = H5Rdereference2(self.id(), H5P_DEFAULT, H5R_OBJECT, ptr);
+            #[cfg(not(hdf5_1_10_0))]
+            let obj_id = H5Rdereference(self.id(), H5R_OBJECT, ptr);
+            Dataset::from_id(h5check(obj_id)?)
+        })
+    }
+
+    /// Reads the `DIMENSION_LIST` attribute: references to scales attached to each dimension.
+    fn read_dimension_list(&self) -> Result<Vec<Vec<hobj_ref_t>>> {
+        let ndim = self.ndim();
+        h5lock!({
+            if !attr_exists(self, DIMENSION_LIST)? {
+                return Ok(vec![Vec::new(); ndim]);
+            }
+            let attr = self.attr(DIMENSION_LIST)?;
+            ensure!(
+                attr.shape() == vec![ndim],
+                "Invalid {} attribute shape: {:?}",
+                DIMENSION_LIST,
+                attr.shape()
+            );
+            let datatype = dim_list_datatype()?;
+            let mut buf = vec![hvl_t { len: 0, p: ptr::null_mut() }; ndim];
+            h5try!(H5Aread(attr.id(), datatype.id(), buf.as_mut_ptr() as *mut _));
+            let lists: Vec<_> = buf
+                .iter()
+                .map(|vl| {
+                    if vl.len == 0 || vl.p.is_null() {
+                        Vec::new()
+                    } else {
+                        slice::from_raw_parts(vl.p as *const hobj_ref_t, vl.len).to_vec()
+                    }
+                })
+                .collect();
+            let space = attr.space()?;
+            h5try!(H5Dvlen_reclaim(
+                datatype.id(),
+                space.id(),
+                H5P_DEFAULT,
+                buf.as_mut_ptr() as *mut _
+            ));
+            Ok(lists)
+        })
+    }
+
+    fn write_dimension_list(&self, lists: &[Vec<hobj_ref_t>]) -> Result<()> {
+        if lists.iter().all(Vec::is_empty) {
+            return delete_attr(self, DIMENSION_LIST);
+        }
+        let buf: Vec<_> =
+            lists.iter().map(|l| hvl_t { len: l.len(), p: l.as_ptr() as *mut _ }).collect();
+        let datatype = dim_list_datatype()?;
+        write_attr(self, DIMENSION_LIST, &datatype, lists.len(), buf.as_ptr() as *const _)
+    }
+
+    /// Reads the `REFERENCE_LIST` attribute: datasets and dimensions the scale is attached to.
+    fn read_reference_list(&self) -> Result<Vec<ReferenceEntry>> {
+        h5lock!({
+            if !attr_exists(self, REFERENCE_LIST)? {
+                return Ok(Vec::new());
+            }
+            let attr = self.attr(REFERENCE_LIST)?;
+            let datatype = ref_list_datatype()?;
+            let mut buf = vec![ReferenceEntry { dataset: 0, dimension: 0 }; attr.size()];
+            h5try!(H5Aread(attr.id(), datatype.id(), buf.as_mut_ptr() as *mut _));
+            Ok(buf)
+        })
+    }
+
+    fn write_reference_list(&self, entries: &[ReferenceEntry]) -> Result<()> {
+        if entries.is_empty() {
+            return delete_attr(self, REFERENCE_LIST);
+        }
+        let datatype = ref_list_datatype()?;
+        write_attr(self, REFERENCE_LIST, &datatype, entries.len(), entries.as_ptr() as *const _)
+    }
+
+    fn check_scale_dim(&self, scale: &Dataset, dim: usize) -> Result<()> {
+        let ndim = self.ndim();
+        ensure!(dim < ndim, "Invalid dimension {} for a dataset with {} dimensions", dim, ndim);
+        ensure!(
+            scale.filename() == self.filename(),
+            "Dimension scale must be in the same file as the dataset"
+        );
+        Ok(())
+    }
+
+    /// Converts the dataset into a dimension scale with a given name (may be empty).
+    pub fn set_scale(&self, name: &str) -> Result<()> {
+        h5lock!({
+            ensure!(
+                !attr_exists(self, DIMENSION_LIST)?,
+                "A dataset with attached dimension scales cannot be a dimension scale"
+            );
+            write_string_attr(self, CLASS, DIMENSION_SCALE_CLASS)?;
+            if !name.is_empty() {
+                write_string_attr(self, NAME, name)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Returns `true` if the dataset is a dimension scale.
+    pub fn is_scale(&self) -> bool {
+        let _e = silence_errors();
+        match read_string_attr(self, CLASS) {
+            Ok(Some(class)) => class == DIMENSION_SCALE_CLASS,
+            _ => false,
+        }
+    }
+
+    /// Returns the name of the dimension scale if it has been set.
+    pub fn scale_name(&self) -> Result<Option<String>> {
+        read_string_attr(self, NAME)
+    }
+
+    /// Attaches a dimension scale to a given dimension of the dataset.
+    ///
+    /// The scale must be one-dimensional, with its length matching the extent of the dataset
+    /// along the given dimension; if it's not a dimension scale yet, it becomes one.
+    pub fn attach_scale(&self, scale: &Dataset, dim: usize) -> Result<()> {
+        self.check_scale_dim(scale, dim)?;
+        h5lock!({
+            ensure!(!self.is_scale(), "Cannot attach a dimension scale to a dimension scale");
+            ensure!(
+                scale.ndim() == 1 && scale.shape()[0] == self.shape()[dim],
+                "Dimension scale shape {:?} doesn't match the extent of dimension {} ({})",
+                scale.shape(),
+                dim,
+                self.shape()[dim]
+            );
+            let (dataset_ref, scale_ref) = (self.object_ref()?, scale.object_ref()?);
+            ensure!(dataset_ref != scale_ref, "Cannot attach a dataset to itself");
+
+            let mut lists = self.read_dimension_list()?;
+            if lists[dim].contains(&scale_ref) {
+                return Ok(());
+            }
+            lists[dim].push(scale_ref);
+
+            let mut entries = scale.read_reference_list()?;
+            entries.push(ReferenceEntry { dataset: dataset_ref, dimension: dim as _ });
+
+            if !scale.is_scale() {
+                scale.set_scale("")?;
+            }
+            self.write_dimension_list(&lists)?;
+            scale.write_reference_list(&entries)
+        })
+    }
+
+    /// Detaches a dimension scale from a given dimension of the dataset.
+    pub fn detach_scale(&self, scale: &Dataset, dim: usize) -> Result<()> {
+        self.check_scale_dim(scale, dim)?;
+        h5lock!({
+            let (dataset_ref, scale_ref) = (self.object_ref()?, scale.object_ref()?);
+            let mut lists = self.read_dimension_list()?;
+            ensure!(
+                lists[dim].contains(&scale_ref),
+                "Dimension scale is not attached to dimension {}",
+                dim
+            );
+            lists[dim].retain(|&r| r != scale_ref);
+
+            let mut entries = scale.read_reference_list()?;
+            entries.retain(|e| e.dataset != dataset_ref || e.dimension != dim as c_int);
+
+            self.write_dimension_list(&lists)?;
+            scale.write_reference_list(&entries)
+        })
+    }
+
+    /// Returns the dimension scales attached to a given dimension of the dataset.
+    pub fn attached_scales(&self, dim: usize) -> Result<Vec<Dataset>> {
+        let ndim = self.ndim();
+        ensure!(dim < ndim, "Invalid dimension {} for a dataset with {} dimensions", dim, ndim);
+        let lists = self.read_dimension_list()?;
+        lists[dim].iter().map(|&r| self.dereference(r)).collect()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::internal_prelude::*;
+
+    #[test]
+    pub fn test_dimension_scales() {
+        with_tmp_file(|file| {
+            let data = file.new_dataset::<f32>().create("data", (3, 4)).unwrap();
+            let x = file.new_dataset::<f64>().create("x", 4).unwrap();
+            x.write(&[0.5, 1.5, 2.5, 3.5]).unwrap();
+            let y = file.new_dataset::<u32>().create("y", 3).unwrap();
+
+            assert!(!x.is_scale());
+            x.set_scale("x coordinate").unwrap();
+            assert!(x.is_scale());
+            assert_eq!(x.scale_name().unwrap().unwrap(), "x coordinate");
+            assert_eq!(data.scale_name().unwrap(), None);
+            assert!(data.attached_scales(1).unwrap().is_empty());
+
+            data.attach_scale(&x, 1).unwrap();
+            data.attach_scale(&x, 1).unwrap();
+            data.attach_scale(&y, 0).unwrap();
+            assert!(y.is_scale());
+            assert_eq!(y.scale_name().unwrap(), None);
+
+            let data = file.dataset("data").unwrap();
+            let scales = data.attached_scales(1).unwrap();
+            assert_eq!(scales.len(), 1);
+            assert_eq!(scales[0].name(), "/x");
+            assert_eq!(scales[0].read_raw::<f64>().unwrap(), vec![0.5, 1.5, 2.5, 3.5]);
+            assert_eq!(data.attached_scales(0).unwrap()[0].name(), "/y");
+            assert_eq!(x.attr("REFERENCE_LIST").unwrap().size(), 1);
+
+            assert_err!(
+                data.attach_scale(&x, 0),
+                "Dimension scale shape [4] doesn't match the extent of dimension 0 (3)"
+            );
+            assert_err!(data.attach_scale(&x, 2), "Invalid dimension 2");
+            assert_err!(data.attached_scales(2), "Invalid dimension 2");
+            assert_err!(x.attach_scale(&x, 0), "Cannot attach a dimension scale");
+            assert_err!(data.set_scale("data"), "cannot be a dimension scale");
+
+            data.detach_scale(&x, 1).unwrap();
+            assert!(data.attached_scales(1).unwrap().is_empty());
+            assert_eq!(data.attached_scales(0).unwrap().len(), 1);
+            assert_err!(data.detach_scale(&x, 1), "is not attached to dimension 1");
+            assert!(x.attr_names().unwrap().iter().all(|name| name != "REFERENCE_LIST"));
+            data.detach_scale(&y, 0).unwrap();
+            assert!(data.attr_names().unwrap().is_empty());
+        })
+    }
+}
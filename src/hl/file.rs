@@ -1,20 +1,41 @@
 use std::fmt::{self, Debug};
 use std::ops::Deref;
 use std::path::Path;
-
-use hdf5_sys::h5f::{
-    H5Fclose, H5Fcreate, H5Fflush, H5Fget_access_plist, H5Fget_create_plist, H5Fget_filesize,
-    H5Fget_freespace, H5Fget_intent, H5Fget_obj_count, H5Fget_obj_ids, H5Fopen, H5F_ACC_DEFAULT,
-    H5F_ACC_EXCL, H5F_ACC_RDONLY, H5F_ACC_RDWR, H5F_ACC_TRUNC, H5F_OBJ_ALL, H5F_OBJ_FILE,
-    H5F_SCOPE_LOCAL,
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use hdf5_sys::{
+    h5f::{
+        H5Fclose, H5Fcreate, H5Fflush, H5Fget_access_plist, H5Fget_create_plist, H5Fget_file_image,
+        H5Fget_filesize, H5Fget_freespace, H5Fget_intent, H5Fget_mdc_hit_rate, H5Fget_obj_count,
+        H5Fget_obj_ids, H5Fopen, H5Freset_mdc_hit_rate_stats, H5F_ACC_DEFAULT, H5F_ACC_EXCL,
+        H5F_ACC_RDONLY, H5F_ACC_RDWR, H5F_ACC_TRUNC, H5F_OBJ_ALL, H5F_OBJ_FILE, H5F_SCOPE_GLOBAL,
+        H5F_SCOPE_LOCAL,
+    },
+    h5o::H5Oopen_by_addr,
+    h5p::H5Pset_file_image,
+    h5r::{H5Rget_region, H5R_DATASET_REGION},
 };
+use hdf5_types::RegionReference;
+
+#[cfg(hdf5_1_10_0)]
+use hdf5_sys::h5f::{H5Fstart_swmr_write, H5F_ACC_SWMR_READ};
+#[cfg(not(hdf5_1_10_0))]
+use hdf5_sys::h5r::H5Rdereference;
+#[cfg(hdf5_1_10_0)]
+use hdf5_sys::h5r::H5Rdereference2;
 
 use crate::hl::plist::{
-    file_access::{FileAccess, FileAccessBuilder},
+    file_access::{FileAccess, FileAccessBuilder, FileDriver},
     file_create::{FileCreate, FileCreateBuilder},
 };
 use crate::internal_prelude::*;
 
+#[cfg(hdf5_1_10_2)]
+use crate::hl::plist::file_access::LibraryVersion;
+#[cfg(hdf5_1_10_1)]
+use crate::hl::plist::file_create::FileSpaceStrategy;
+
 /// File opening mode.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum OpenMode {
@@ -30,6 +51,21 @@ pub enum OpenMode {
     Append,
 }
 
+/// Scope of a file flush operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlushScope {
+    /// Flush only the file itself.
+    Local,
+    /// Flush the entire virtual file, including all files mounted into it.
+    Global,
+}
+
+impl Default for FlushScope {
+    fn default() -> Self {
+        FlushScope::Local
+    }
+}
+
 /// HDF5 file object.
 #[repr(transparent)]
 #[derive(Clone)]
@@ -73,7 +109,17 @@ impl Deref for File {
 
 impl File {
     /// Opens a file as read-only, file must exist.
+    ///
+    /// If the `ros3` feature is enabled, `s3://` and `https://` URLs are opened with
+    /// the read-only S3 driver using anonymous access.
     pub fn open<P: AsRef<Path>>(filename: P) -> Result<Self> {
+        #[cfg(feature = "ros3")]
+        {
+            let name = filename.as_ref().to_str().unwrap_or("");
+            if name.starts_with("s3://") || name.starts_with("https://") {
+                return Self::with_options().with_fapl(|p| p.ros3()).open(filename);
+            }
+        }
         Self::open_as(filename, OpenMode::Read)
     }
 
@@ -107,7 +153,32 @@ impl File {
         FileBuilder::new()
     }
 
+    /// Opens a read-only in-memory file from a file image, e.g. one obtained via `to_bytes()`.
+    ///
+    /// The image is copied, nothing is read from or written to disk; any attempt to
+    /// modify the file fails with a "no write intent on file" error.
+    pub fn open_from_bytes(bytes: &[u8]) -> Result<Self> {
+        static IMAGE_COUNT: AtomicUsize = AtomicUsize::new(0);
+        // Each image needs a distinct name, otherwise the library may hand out an existing
+        // file that happens to be open under the same name.
+        let name = format!("file-image-{}", IMAGE_COUNT.fetch_add(1, Ordering::SeqCst));
+        let name = to_cstring(name)?;
+        h5lock!({
+            let fapl = FileAccess::build().core_filebacked(false).finish()?;
+            h5try!(H5Pset_file_image(fapl.id(), bytes.as_ptr() as *mut _, bytes.len() as _));
+            File::from_id(h5try!(H5Fopen(name.as_ptr(), H5F_ACC_RDONLY, fapl.id())))
+        })
+    }
+
+    /// A short alias for `open_from_bytes()`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::open_from_bytes(bytes)
+    }
+
     /// Returns the file size in bytes (or 0 if the file handle is invalid).
+    ///
+    /// This is the size as seen by the library, which includes the user block and the free
+    /// space and may exceed the size on disk until the file is flushed.
     pub fn size(&self) -> u64 {
         h5get_d!(H5Fget_filesize(self.id()): hsize_t) as _
     }
@@ -117,6 +188,19 @@ impl File {
         h5lock!(H5Fget_freespace(self.id())).max(0) as _
     }
 
+    /// Returns the metadata cache hit rate since the last reset of the statistics.
+    ///
+    /// The statistics are reset automatically at the start of each epoch if the adaptive
+    /// cache resize code is enabled (see `MetadataCacheConfig`).
+    pub fn mdc_hit_rate(&self) -> Result<f64> {
+        h5get!(H5Fget_mdc_hit_rate(self.id()): f64)
+    }
+
+    /// Resets the metadata cache hit rate statistics.
+    pub fn reset_mdc_hit_rate_stats(&self) -> Result<()> {
+        h5call!(H5Freset_mdc_hit_rate_stats(self.id())).and(Ok(()))
+    }
+
     /// Returns true if the file was opened in a read-only mode.
     pub fn is_read_only(&self) -> bool {
         h5get!(H5Fget_intent(self.id()): c_uint).unwrap_or(H5F_ACC_DEFAULT) != H5F_ACC_RDWR
@@ -127,9 +211,38 @@ impl File {
         h5lock!(self.fcpl().map(|p| p.userblock()).unwrap_or(0))
     }
 
-    /// Flushes the file to the storage medium.
-    pub fn flush(&self) -> Result<()> {
-        h5call!(H5Fflush(self.id(), H5F_SCOPE_LOCAL)).and(Ok(()))
+    /// Flushes all buffered metadata and raw data of the file to the storage medium.
+    ///
+    /// Note that this hands the data over to the operating system but doesn't guarantee
+    /// that it has been synced to the physical media.
+    pub fn flush(&self, scope: FlushScope) -> Result<()> {
+        let scope = match scope {
+            FlushScope::Local => H5F_SCOPE_LOCAL,
+            FlushScope::Global => H5F_SCOPE_GLOBAL,
+        };
+        h5call!(H5Fflush(self.id(), scope)).and(Ok(()))
+    }
+
+    /// Switches the file into SWMR (single-writer/multiple-reader) writing mode.
+    ///
+    /// The file must be open for writing with the latest library version bounds, and all
+    /// objects have to be created before this is called; afterwards, readers that opened the
+    /// file via `FileBuilder::with_swmr_read()` may observe data appended to its datasets
+    /// as soon as it's flushed. Requires HDF5 1.10.0 or later.
+    pub fn start_swmr_write(&self) -> Result<()> {
+        #[cfg(hdf5_1_10_0)]
+        {
+            #[cfg(hdf5_1_10_2)]
+            ensure!(
+                self.fapl()?.libver_bounds().low == LibraryVersion::latest(),
+                "SWMR writing requires the file to be opened with the latest library version bounds"
+            );
+            h5call!(H5Fstart_swmr_write(self.id())).and(Ok(()))
+        }
+        #[cfg(not(hdf5_1_10_0))]
+        {
+            fail!("SWMR is not supported: HDF5 1.10.0 or later is required")
+        }
     }
 
     /// Returns objects IDs of the contained objects. NOTE: these are borrowed references.
@@ -150,6 +263,19 @@ impl File {
         })
     }
 
+    /// Returns a copy of the file image, i.e. the file contents as they would be on disk.
+    ///
+    /// This works with any file driver and is particularly useful for in-memory files
+    /// created with the core driver without a backing store.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        h5lock!({
+            let size = h5check(H5Fget_file_image(self.id(), ptr::null_mut(), 0))?;
+            let mut buf = vec![0_u8; size as _];
+            h5check(H5Fget_file_image(self.id(), buf.as_mut_ptr() as *mut _, size as _))?;
+            Ok(buf)
+        })
+    }
+
     /// Closes the file and invalidates all open handles for contained objects.
     pub fn close(self) {
         h5lock!({
@@ -192,6 +318,30 @@ impl File {
     pub fn fcpl(&self) -> Result<FileCreate> {
         self.create_plist()
     }
+
+    /// Opens an object (group, dataset or named datatype) by its token, regardless of the
+    /// path it's currently linked under.
+    ///
+    /// The token must have been obtained from an object in the same file.
+    pub fn open_by_token(&self, token: &ObjectToken) -> Result<Location> {
+        Location::from_id(h5try!(H5Oopen_by_addr(self.id(), token.addr() as _)))
+    }
+
+    /// Dereferences a region reference, returning the target dataset and the selection.
+    pub fn dereference_region(&self, reference: &RegionReference) -> Result<(Dataset, Selection)> {
+        ensure!(!reference.is_null(), "null region reference");
+        let ptr = reference.as_ptr() as *const _;
+        h5lock!({
+            #[cfg(hdf5_1_10_0)]
+            let obj_id = H5Rdereference2(self.id(), H5P_DEFAULT, H5R_DATASET_REGION, ptr);
+            #[cfg(not(hdf5_1_10_0))]
+            let obj_id = H5Rdereference(self.id(), H5R_DATASET_REGION, ptr);
+            let dataset = Dataset::from_id(h5check(obj_id)?)?;
+            let space =
+                Dataspace::from_id(h5try!(H5Rget_region(self.id(), H5R_DATASET_REGION, ptr)))?;
+            Ok((dataset, space.get_selection()?))
+        })
+    }
 }
 
 /// File builder allowing to customize file access/creation property lists.
@@ -199,6 +349,7 @@ impl File {
 pub struct FileBuilder {
     fapl: FileAccessBuilder,
     fcpl: FileCreateBuilder,
+    swmr_read: bool,
 }
 
 impl FileBuilder {
@@ -240,32 +391,78 @@ impl FileBuilder {
                 return Ok(file);
             }
         }
-        let filename = to_cstring(
-            filename
-                .to_str()
-                .ok_or_else(|| format!("Invalid UTF-8 in file name: {:?}", filename))?,
-        )?;
-        let flags = match mode {
+        let filename = filename
+            .to_str()
+            .ok_or_else(|| format!("Invalid UTF-8 in file name: {:?}", filename))?
+            .to_owned();
+        let fapl = self.fapl.finish()?;
+        let filename = match fapl.driver() {
+            FileDriver::Family(_) => {
+                ensure!(
+                    has_member_template(&filename),
+                    "Family driver requires a file name template with a `%d` placeholder for \
+                     the member index, got {:?}",
+                    filename
+                );
+                filename
+            }
+            #[cfg(feature = "ros3")]
+            FileDriver::Ros3(drv) => {
+                ensure!(mode == OpenMode::Read, "ROS3 driver only supports read-only access");
+                s3_to_https(&filename, &drv.region)
+            }
+            _ => filename,
+        };
+        let filename = to_cstring(filename.as_str())?;
+        let mut flags = match mode {
             OpenMode::Read => H5F_ACC_RDONLY,
             OpenMode::ReadWrite => H5F_ACC_RDWR,
             OpenMode::Create => H5F_ACC_TRUNC,
             OpenMode::CreateExcl | OpenMode::Append => H5F_ACC_EXCL,
         };
+        if self.swmr_read {
+            ensure!(
+                mode == OpenMode::Read,
+                "SWMR reading requires the file to be opened as read-only"
+            );
+            flags |= swmr_read_flag()?;
+        }
         let fname_ptr = filename.as_ptr();
         h5lock!({
-            let fapl = self.fapl.finish()?;
             match mode {
                 OpenMode::Read | OpenMode::ReadWrite => {
-                    File::from_id(h5try!(H5Fopen(fname_ptr, flags, fapl.id())))
+                    match h5call!(H5Fopen(fname_ptr, flags, fapl.id())) {
+                        Ok(file_id) => File::from_id(file_id),
+                        Err(err) => {
+                            // Replace the rather obscure error if page buffering is the cause.
+                            if has_page_buffer(&fapl) {
+                                let file =
+                                    File::from_id(H5Fopen(fname_ptr, H5F_ACC_RDONLY, H5P_DEFAULT));
+                                if let Ok(fcpl) = file.and_then(|file| file.fcpl()) {
+                                    ensure_paged_strategy(&fapl, &fcpl)?;
+                                }
+                            }
+                            Err(err)
+                        }
+                    }
                 }
                 _ => {
                     let fcpl = self.fcpl.finish()?;
+                    ensure_paged_strategy(&fapl, &fcpl)?;
                     File::from_id(h5try!(H5Fcreate(fname_ptr, flags, fcpl.id(), fapl.id())))
                 }
             }
         })
     }
 
+    /// Opens the file in SWMR (single-writer/multiple-reader) reading mode, so that data
+    /// written by a concurrent SWMR writer becomes visible without reopening the file (use
+    /// `Dataset::refresh()` to pick up the changes). Only valid for read-only access.
+    pub fn with_swmr_read(&mut self) -> &mut Self {
+        self.swmr_read = true;
+        self
+    }
+
     // File Access Property List
 
     /// Sets current file access property list to a given one.
@@ -351,11 +548,87 @@ impl FileBuilder {
     }
 }
 
+/// Converts `s3://bucket/key` URLs to the virtual-hosted-style HTTPS URLs expected
+/// by the ROS3 driver; other names are returned unchanged.
+#[cfg(feature = "ros3")]
+fn s3_to_https(filename: &str, region: &str) -> String {
+    if !filename.starts_with("s3://") {
+        return filename.to_owned();
+    }
+    let path = &filename["s3://".len()..];
+    let (bucket, key) = match path.find('/') {
+        Some(pos) => (&path[..pos], &path[pos..]),
+        None => (path, ""),
+    };
+    if region.is_empty() {
+        format!("https://{}.s3.amazonaws.com{}", bucket, key)
+    } else {
+        format!("https://{}.s3.{}.amazonaws.com{}", bucket, region, key)
+    }
+}
+
+/// Checks that a file name contains a printf-style integer conversion (e.g. `%d` or `%03d`),
+/// which the family driver substitutes with the member index.
+fn has_member_template(filename: &str) -> bool {
+    let mut chars = filename.chars();
+    while let Some(c) = chars.next() {
+        if c == '%' {
+            match chars.by_ref().find(|c| !c.is_ascii_digit() && !"-+ #".contains(*c)) {
+                Some('d') => return true,
+                Some('%') => continue,
+                _ => return false,
+            }
+        }
+    }
+    false
+}
+
+#[cfg(hdf5_1_10_1)]
+fn has_page_buffer(fapl: &FileAccess) -> bool {
+    fapl.page_buffer_size().buf_size > 0
+}
+
+#[cfg(not(hdf5_1_10_1))]
+fn has_page_buffer(_fapl: &FileAccess) -> bool {
+    false
+}
+
+/// Page buffering can only be enabled for files using the paged file space strategy.
+#[cfg(hdf5_1_10_1)]
+fn ensure_paged_strategy(fapl: &FileAccess, fcpl: &FileCreate) -> Result<()> {
+    let paged = match fcpl.file_space_strategy() {
+        FileSpaceStrategy::FreeSpaceManager { paged, .. } => paged,
+        _ => false,
+    };
+    ensure!(
+        paged || !has_page_buffer(fapl),
+        "Page buffering requires a file created with a paged file space strategy"
+    );
+    Ok(())
+}
+
+#[cfg(not(hdf5_1_10_1))]
+fn ensure_paged_strategy(_fapl: &FileAccess, _fcpl: &FileCreate) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(hdf5_1_10_0)]
+fn swmr_read_flag() -> Result<c_uint> {
+    Ok(H5F_ACC_SWMR_READ)
+}
+
+#[cfg(not(hdf5_1_10_0))]
+fn swmr_read_flag() -> Result<c_uint> {
+    fail!("SWMR is not supported: HDF5 1.10.0 or later is required")
+}
+
 #[cfg(test)]
 pub mod tests {
+    use super::FlushScope;
     use crate::internal_prelude::*;
+    use ndarray::arr2;
     use std::fs;
-    use std::io::{Read, Write};
+    use std::io::{Read, Seek, SeekFrom, Write};
 
     #[test]
     pub fn test_is_read_only() {
@@ -448,7 +721,7 @@ pub mod tests {
             assert_ne!(orig_size, 0);
             #[cfg(not(hdf5_1_10_0))]
             assert_eq!(orig_size, 0);
-            assert!(file.flush().is_ok());
+            assert!(file.flush(FlushScope::Local).is_ok());
             assert!(file.size() > 0);
             let new_size = fs::metadata(file.filename()).unwrap().len();
             assert!(new_size > orig_size);
@@ -456,6 +729,163 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_swmr_errors() {
+        with_tmp_path(|path| {
+            File::create(&path).unwrap().close();
+            assert_err!(
+                File::with_options().with_swmr_read().open_rw(&path),
+                "SWMR reading requires the file to be opened as read-only"
+            );
+            #[cfg(hdf5_1_10_2)]
+            assert_err!(
+                File::open_rw(&path).unwrap().start_swmr_write(),
+                "requires the file to be opened with the latest library version bounds"
+            );
+            #[cfg(not(hdf5_1_10_0))]
+            {
+                assert_err!(File::open_rw(&path).unwrap().start_swmr_write(), "not supported");
+                assert_err!(File::with_options().with_swmr_read().open(&path), "not supported");
+            }
+        })
+    }
+
+    #[test]
+    pub fn test_flush_second_handle() {
+        with_tmp_path(|path| {
+            let file = File::create(&path).unwrap();
+            let data: Vec<u64> = (0..16).map(|x| 0xabcd_0000_0000_0000 | x).collect();
+            let ds = file.new_dataset::<u64>().no_chunk().create("foo", 16).unwrap();
+            ds.write(&data).unwrap();
+            file.flush(FlushScope::Global).unwrap();
+
+            let offset = ds.offset().unwrap();
+            let mut bytes = vec![0_u8; 16 * 8];
+            let mut raw = fs::File::open(&path).unwrap();
+            raw.seek(SeekFrom::Start(offset)).unwrap();
+            raw.read_exact(&mut bytes).unwrap();
+            let on_disk: Vec<u8> = data.iter().flat_map(|x| x.to_ne_bytes().to_vec()).collect();
+            assert_eq!(bytes, on_disk);
+
+            let other = File::open(&path).unwrap();
+            assert_eq!(other.dataset("foo").unwrap().read_raw::<u64>().unwrap(), data);
+        })
+    }
+
+    #[test]
+    pub fn test_aggregation_block_sizes() {
+        with_tmp_path(|path| {
+            let file = FileBuilder::new()
+                .with_fapl(|p| p.meta_block_size(8192).small_data_block_size(4096))
+                .create(&path)
+                .unwrap();
+            for i in 0..100 {
+                file.new_dataset::<u8>().create(&format!("ds{}", i), 1).unwrap();
+            }
+            let fapl = file.fapl().unwrap();
+            assert_eq!(fapl.meta_block_size(), 8192);
+            assert_eq!(fapl.small_data_block_size(), 4096);
+        })
+    }
+
+    #[test]
+    #[cfg(hdf5_1_10_1)]
+    pub fn test_page_buffer() {
+        use crate::plist::file_create::FileSpaceStrategy;
+        with_tmp_dir(|dir| {
+            let (paged, plain) = (dir.join("paged.h5"), dir.join("plain.h5"));
+            let strategy =
+                FileSpaceStrategy::FreeSpaceManager { paged: true, persist: false, threshold: 1 };
+            let file = FileBuilder::new()
+                .with_fcpl(|p| p.file_space_strategy(strategy))
+                .with_fapl(|p| p.page_buffer_size(4 * 4096, 0, 0))
+                .create(&paged)
+                .unwrap();
+            file.new_dataset::<u32>().create("foo", 3).unwrap().write(&[1, 2, 3]).unwrap();
+            file.close();
+            File::create(&plain).unwrap().close();
+
+            let file = FileBuilder::new()
+                .with_fapl(|p| p.page_buffer_size(16 * 4096, 20, 30))
+                .open(&paged)
+                .unwrap();
+            assert_eq!(file.fapl().unwrap().page_buffer_size().buf_size, 16 * 4096);
+            assert_eq!(file.dataset("foo").unwrap().read_raw::<u32>().unwrap(), vec![1, 2, 3]);
+
+            let mut builder = FileBuilder::new();
+            builder.with_fapl(|p| p.page_buffer_size(16 * 4096, 0, 0));
+            assert_err!(builder.open(&plain), "requires a file created with a paged file space");
+            assert_err!(builder.create(&plain), "requires a file created with a paged file space");
+            assert_err!(builder.open(dir.join("missing.h5")), "unable to open file");
+        })
+    }
+
+    #[test]
+    pub fn test_mdc_config() {
+        use crate::plist::file_access::MetadataCacheConfig;
+        with_tmp_path(|path| {
+            let mut mdc = MetadataCacheConfig::default();
+            mdc.max_size = 1 << 16;
+            mdc.min_size = 1 << 14;
+            mdc.initial_size = 1 << 15;
+            mdc.min_clean_fraction = 0.5;
+            mdc.evictions_enabled = true;
+            let file = FileBuilder::new().with_fapl(|p| p.mdc_config(&mdc)).create(&path).unwrap();
+            assert_eq!(file.fapl().unwrap().mdc_config(), mdc);
+            file.reset_mdc_hit_rate_stats().unwrap();
+            assert_eq!(file.mdc_hit_rate().unwrap(), 0.0);
+            file.new_dataset::<u8>().create("foo", 1).unwrap();
+            file.dataset("foo").unwrap();
+            let hit_rate = file.mdc_hit_rate().unwrap();
+            assert!(hit_rate >= 0.0 && hit_rate <= 1.0);
+        })
+    }
+
+    #[test]
+    #[cfg(hdf5_1_10_2)]
+    pub fn test_libver_bounds() {
+        use crate::plist::file_access::{LibVerBounds, LibraryVersion};
+        with_tmp_path(|path| {
+            let (low, high) = (LibraryVersion::Earliest, LibraryVersion::V18);
+            let file =
+                FileBuilder::new().with_fapl(|p| p.libver_bounds(low, high)).create(&path).unwrap();
+            assert_eq!(file.fapl().unwrap().libver_bounds(), LibVerBounds { low, high });
+            assert_err!(
+                file.start_swmr_write(),
+                "SWMR writing requires the file to be opened with the latest library version bounds"
+            );
+            assert_err!(
+                FileBuilder::new()
+                    .with_fapl(|p| p.libver_bounds(LibraryVersion::V110, LibraryVersion::V18))
+                    .create(&path),
+                "Library version low bound (V110) must not exceed the high bound (V18)"
+            );
+            assert_err!(
+                FileBuilder::new().with_fapl(|p| p.libver_bounds(low, low)).create(&path),
+                "Library version high bound can't be `Earliest`"
+            );
+        })
+    }
+
+    #[test]
+    pub fn test_size() {
+        with_tmp_path(|path| {
+            let file = FileBuilder::new().with_fcpl(|p| p.userblock(4096)).create(&path).unwrap();
+            let empty_size = file.size();
+            assert!(empty_size > 4096);
+            let datasets = vec![
+                file.new_dataset::<u64>().create("a", 1000).unwrap(),
+                file.new_dataset::<u8>().chunk(100).create("b", 500).unwrap(),
+            ];
+            datasets[0].write_raw(&vec![1_u64; 1000]).unwrap();
+            datasets[1].write_raw(&vec![2_u8; 500]).unwrap();
+            let storage: u64 = datasets.iter().map(|ds| ds.storage_size()).sum();
+            assert_eq!(storage, 8000 + 500);
+            assert!(file.size() >= 4096 + storage);
+            assert!(file.size() > empty_size);
+        })
+    }
+
     #[test]
     pub fn test_userblock() {
         with_tmp_file(|file| {
@@ -464,7 +894,11 @@ pub mod tests {
         with_tmp_path(|path| {
             assert_err!(
                 FileBuilder::new().with_fcpl(|p| p.userblock(1)).create(&path),
-                "userblock size is non-zero and less than 512"
+                "invalid userblock size: 1"
+            );
+            assert_err!(
+                FileBuilder::new().with_fcpl(|p| p.userblock(1000)).create(&path),
+                "invalid userblock size: 1000 (expected 0 or a power of 2 not less than 512)"
             );
             FileBuilder::new().with_fcpl(|p| p.userblock(512)).create(&path).unwrap();
             assert_eq!(File::open(&path).unwrap().userblock(), 512);
@@ -496,6 +930,22 @@ pub mod tests {
                 }
             }
             File::open(&path).unwrap().group("foo/bar").unwrap();
+        });
+        with_tmp_path(|path| {
+            FileBuilder::new().with_fcpl(|p| p.userblock(1024)).create(&path).unwrap();
+            {
+                let mut file = fs::OpenOptions::new().write(true).open(&path).unwrap();
+                file.write_all(b"MAGIC").unwrap();
+                file.seek(SeekFrom::Start(1020)).unwrap();
+                file.write_all(b"END!").unwrap();
+            }
+            let file = File::open(&path).unwrap();
+            assert_eq!(file.userblock(), 1024);
+            drop(file);
+            let mut data = vec![0; 1024];
+            fs::File::open(&path).unwrap().read_exact(&mut data).unwrap();
+            assert_eq!(&data[..5], b"MAGIC");
+            assert_eq!(&data[1020..], b"END!");
         })
     }
 
@@ -541,6 +991,59 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_file_image() {
+        with_tmp_path(|path| {
+            let file =
+                FileBuilder::new().with_fapl(|p| p.core_filebacked(false)).create(&path).unwrap();
+            let ds = file.new_dataset::<i32>().create("foo/bar", (2, 3)).unwrap();
+            ds.write(&arr2(&[[1, 2, 3], [4, 5, 6]])).unwrap();
+            let bytes = file.to_bytes().unwrap();
+            file.close();
+            assert!(fs::metadata(&path).is_err());
+            assert_eq!(&bytes[..8], b"\x89HDF\r\n\x1a\n");
+
+            let image = File::from_bytes(&bytes).unwrap();
+            let other = File::from_bytes(&bytes).unwrap();
+            assert!(image.is_read_only());
+            assert_eq!(
+                image.dataset("foo/bar").unwrap().read_2d::<i32>().unwrap(),
+                arr2(&[[1, 2, 3], [4, 5, 6]])
+            );
+            assert_err!(image.create_group("baz"), "no write intent on file");
+            drop(image);
+            assert_eq!(
+                other.dataset("foo/bar").unwrap().read_raw::<i32>().unwrap(),
+                vec![1, 2, 3, 4, 5, 6]
+            );
+            assert_eq!(other.to_bytes().unwrap().len(), bytes.len());
+
+            assert_err!(File::from_bytes(b"not an hdf5 file"), "unable to open file");
+        })
+    }
+
+    #[test]
+    pub fn test_open_from_bytes() {
+        with_tmp_path(|path| {
+            let file = File::create(&path).unwrap();
+            file.create_group("a/b").unwrap();
+            file.new_dataset::<u8>().create("a/c", 4).unwrap().write(&[1, 2, 3, 4]).unwrap();
+            let bytes = file.to_bytes().unwrap();
+            file.close();
+
+            let file = File::open_from_bytes(&bytes).unwrap();
+            assert_eq!(file.member_names().unwrap(), vec!["a"]);
+            let group = file.group("a").unwrap();
+            assert_eq!(group.member_names().unwrap(), vec!["b", "c"]);
+            assert!(group.group("b").unwrap().is_empty());
+            let ds = group.dataset("c").unwrap();
+            assert_eq!(ds.read_raw::<u8>().unwrap(), vec![1, 2, 3, 4]);
+            assert_err!(ds.write(&[5, 6, 7, 8]), "no write intent on file");
+            assert_err!(file.new_dataset::<u8>().create("d", 1), "no write intent on file");
+            assert_eq!(file.to_bytes().unwrap().len(), bytes.len());
+        })
+    }
+
     #[test]
     pub fn test_core_fd_filebacked() {
         with_tmp_path(|path| {
@@ -575,6 +1078,80 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_split_fd() {
+        with_tmp_dir(|dir| {
+            let path = dir.join("data");
+            let file = FileBuilder::new().with_fapl(|p| p.split()).create(&path).unwrap();
+            file.new_dataset::<i32>().create("foo", 3).unwrap().write(&[1, 2, 3]).unwrap();
+            file.close();
+            assert!(dir.join("data.meta").is_file());
+            assert!(dir.join("data.raw").is_file());
+            assert!(!path.exists());
+            let file = FileBuilder::new().with_fapl(|p| p.split()).open(&path).unwrap();
+            assert_eq!(file.dataset("foo").unwrap().read_raw::<i32>().unwrap(), vec![1, 2, 3]);
+
+            let path = dir.join("custom");
+            let builder = || {
+                let mut builder = FileBuilder::new();
+                builder.with_fapl(|p| p.split_options("-m.h5", "-r.h5"));
+                builder
+            };
+            builder().create(&path).unwrap().create_group("bar").unwrap();
+            assert!(dir.join("custom-m.h5").is_file());
+            assert!(dir.join("custom-r.h5").is_file());
+            builder().open(&path).unwrap().group("bar").unwrap();
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "ros3")]
+    pub fn test_s3_to_https() {
+        assert_eq!(
+            super::s3_to_https("s3://bucket/dir/data.h5", "us-east-2"),
+            "https://bucket.s3.us-east-2.amazonaws.com/dir/data.h5"
+        );
+        assert_eq!(
+            super::s3_to_https("s3://bucket/data.h5", ""),
+            "https://bucket.s3.amazonaws.com/data.h5"
+        );
+        assert_eq!(super::s3_to_https("https://host/data.h5", ""), "https://host/data.h5");
+    }
+
+    #[test]
+    pub fn test_family_fd() {
+        assert!(super::has_member_template("data-%d.h5"));
+        assert!(super::has_member_template("data-%05d.h5"));
+        assert!(super::has_member_template("100%%-%d.h5"));
+        assert!(!super::has_member_template("data.h5"));
+        assert!(!super::has_member_template("data-%s.h5"));
+        assert!(!super::has_member_template("100%%.h5"));
+
+        with_tmp_dir(|dir| {
+            let path = dir.join("data-%d.h5");
+            let data: Vec<u64> = (0..10_000).collect();
+            let file = FileBuilder::new()
+                .with_fapl(|p| p.family_options(32 * 1024))
+                .create(&path)
+                .unwrap();
+            file.new_dataset::<u64>().no_chunk().create("foo", data.len()).unwrap();
+            file.dataset("foo").unwrap().write(&data).unwrap();
+            file.close();
+            assert!(dir.join("data-0.h5").is_file());
+            assert!(dir.join("data-1.h5").is_file());
+            assert!(fs::metadata(dir.join("data-0.h5")).unwrap().len() <= 32 * 1024);
+
+            let file =
+                FileBuilder::new().with_fapl(|p| p.family_options(32 * 1024)).open(&path).unwrap();
+            assert_eq!(file.dataset("foo").unwrap().read_raw::<u64>().unwrap(), data);
+
+            assert_err!(
+                FileBuilder::new().with_fapl(|p| p.family()).create(dir.join("data.h5")),
+                "Family driver requires a file name template with a `%d` placeholder"
+            );
+        })
+    }
+
     #[test]
     pub fn test_stdio_fd() {
         with_tmp_path(|path| {
@@ -588,6 +1165,44 @@ pub mod tests {
         })
     }
 
+    #[test]
+    #[cfg(hdf5_1_10_1)]
+    pub fn test_free_space_reuse() {
+        use crate::plist::file_create::FileSpaceStrategy;
+        with_tmp_path(|path| {
+            let data = vec![1u8; 1 << 16];
+            let strategy =
+                FileSpaceStrategy::FreeSpaceManager { paged: true, persist: true, threshold: 1 };
+            let file = FileBuilder::new()
+                .with_fcpl(|p| p.file_space_strategy(strategy))
+                .create(&path)
+                .unwrap();
+            file.new_dataset::<u8>()
+                .no_chunk()
+                .create("a", data.len())
+                .unwrap()
+                .write_raw(&data)
+                .unwrap();
+            file.close();
+
+            let file = File::open_rw(&path).unwrap();
+            file.unlink("a").unwrap();
+            file.close();
+
+            let file = File::open_rw(&path).unwrap();
+            assert!(file.free_space() >= data.len() as u64);
+            let size = file.size();
+            file.new_dataset::<u8>()
+                .no_chunk()
+                .create("b", data.len())
+                .unwrap()
+                .write_raw(&data)
+                .unwrap();
+            assert!(file.free_space() < data.len() as u64);
+            assert!(file.size() < size + data.len() as u64);
+        })
+    }
+
     #[test]
     pub fn test_debug() {
         with_tmp_dir(|dir| {
@@ -1,18 +1,34 @@
+use std::any::Any;
+use std::collections::VecDeque;
 use std::fmt::{self, Debug};
 use std::ops::Deref;
+use std::panic::{self, AssertUnwindSafe};
 
 use hdf5_sys::{
     h5::{hsize_t, H5_index_t, H5_iter_order_t},
     h5d::H5Dopen2,
-    h5g::{H5G_info_t, H5Gcreate2, H5Gget_info, H5Gopen2},
+    h5g::{H5G_info_t, H5Gcreate2, H5Gget_create_plist, H5Gget_info, H5Gopen2},
     h5l::{
-        H5L_info_t, H5L_iterate_t, H5Lcreate_hard, H5Lcreate_soft, H5Ldelete, H5Lexists,
-        H5Literate, H5Lmove, H5L_SAME_LOC,
+        H5L_info_t, H5L_iterate_t, H5L_type_t, H5Lcreate_external, H5Lcreate_hard, H5Lcreate_soft,
+        H5Ldelete, H5Lexists, H5Lget_info, H5Literate, H5Lmove, H5L_SAME_LOC,
     },
-    h5p::{H5Pcreate, H5Pset_create_intermediate_group},
+    h5o::{H5O_info_t, H5O_iterate_t, H5O_type_t},
+    h5p::{
+        H5Pcreate, H5Pget_link_creation_order, H5Pget_obj_track_times,
+        H5Pset_create_intermediate_group, H5Pset_link_creation_order, H5Pset_obj_track_times,
+        H5P_CRT_ORDER_INDEXED, H5P_CRT_ORDER_TRACKED,
+    },
+    h5t::{H5Tcommit2, H5Topen2},
 };
 
-use crate::globals::H5P_LINK_CREATE;
+#[cfg(not(hdf5_1_10_3))]
+use hdf5_sys::h5o::H5Ovisit;
+#[cfg(hdf5_1_10_3)]
+use hdf5_sys::h5o::{H5Ovisit2, H5O_INFO_BASIC};
+
+use crate::globals::{H5P_GROUP_CREATE, H5P_LINK_CREATE};
+use crate::hl::attribute::set_attr_creation_order;
+use crate::hl::plist::dataset_access::DatasetAccess;
 use crate::internal_prelude::*;
 
 /// Represents the HDF5 group object.
@@ -61,6 +77,251 @@ fn group_info(id: hid_t) -> Result<H5G_info_t> {
     h5call!(H5Gget_info(id, info)).and(Ok(unsafe { *info }))
 }
 
+/// Type of a link within a group.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinkType {
+    /// A hard link pointing directly to an object.
+    Hard,
+    /// A soft link storing the path to an object in the same file.
+    Soft,
+    /// An external link storing the path to an object in another file.
+    External,
+}
+
+/// Index used to determine the order of group members during iteration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IndexType {
+    /// Alphanumeric order of link names.
+    Name,
+    /// Order in which links were created (must be tracked by the group).
+    CreationOrder,
+}
+
+impl Default for IndexType {
+    fn default() -> Self {
+        IndexType::Name
+    }
+}
+
+impl From<IndexType> for H5_index_t {
+    fn from(index: IndexType) -> Self {
+        match index {
+            IndexType::Name => H5_index_t::H5_INDEX_NAME,
+            IndexType::CreationOrder => H5_index_t::H5_INDEX_CRT_ORDER,
+        }
+    }
+}
+
+/// Direction of iteration over an index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IterationOrder {
+    /// Increasing order.
+    Increasing,
+    /// Decreasing order.
+    Decreasing,
+    /// Fastest available order, no particular ordering is guaranteed.
+    Native,
+}
+
+impl Default for IterationOrder {
+    fn default() -> Self {
+        IterationOrder::Increasing
+    }
+}
+
+impl From<IterationOrder> for H5_iter_order_t {
+    fn from(order: IterationOrder) -> Self {
+        match order {
+            IterationOrder::Increasing => H5_iter_order_t::H5_ITER_INC,
+            IterationOrder::Decreasing => H5_iter_order_t::H5_ITER_DEC,
+            IterationOrder::Native => H5_iter_order_t::H5_ITER_NATIVE,
+        }
+    }
+}
+
+/// Type of an object visited by `Group::visit()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObjectType {
+    Group,
+    Dataset,
+    /// Committed datatype.
+    Datatype,
+    Unknown,
+}
+
+impl From<H5O_type_t> for ObjectType {
+    fn from(obj_type: H5O_type_t) -> Self {
+        match obj_type {
+            H5O_type_t::H5O_TYPE_GROUP => ObjectType::Group,
+            H5O_type_t::H5O_TYPE_DATASET => ObjectType::Dataset,
+            H5O_type_t::H5O_TYPE_NAMED_DATATYPE => ObjectType::Datatype,
+            _ => ObjectType::Unknown,
+        }
+    }
+}
+
+/// Tells `Group::visit()` whether to continue the traversal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VisitAction {
+    Continue,
+    Stop,
+}
+
+/// Lazy iterator over names of the members of a group.
+///
+/// Names are fetched from the library in small batches, the library lock is not held
+/// in between calls to `next()`.
+#[derive(Debug)]
+pub struct MemberIter {
+    group: Group,
+    index: IndexType,
+    order: IterationOrder,
+    position: hsize_t,
+    batch: VecDeque<String>,
+    done: bool,
+}
+
+impl MemberIter {
+    const BATCH_SIZE: usize = 256;
+
+    fn fetch_batch(&mut self) -> Result<()> {
+        extern "C" fn batch_callback(
+            _id: hid_t, name: *const c_char, _info: *const H5L_info_t, op_data: *mut c_void,
+        ) -> herr_t {
+            let batch: &mut VecDeque<String> = unsafe { &mut *(op_data as *mut VecDeque<String>) };
+            batch.push_back(string_from_cstr(name));
+            if batch.len() < MemberIter::BATCH_SIZE {
+                0 // Continue iteration
+            } else {
+                1 // Stop iteration, the position will be stored for the next batch
+            }
+        }
+
+        let callback_fn: H5L_iterate_t = Some(batch_callback);
+        let other_data: *mut c_void = &mut self.batch as *mut _ as *mut c_void;
+        let ret = h5call!(H5Literate(
+            self.group.id(),
+            self.index.into(),
+            self.order.into(),
+            &mut self.position,
+            callback_fn,
+            other_data
+        ))?;
+        if ret == 0 {
+            self.done = true;
+        }
+        Ok(())
+    }
+}
+
+impl Iterator for MemberIter {
+    type Item = Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.batch.is_empty() && !self.done {
+            if let Err(err) = self.fetch_batch() {
+                self.done = true;
+                return Some(Err(err));
+            }
+        }
+        self.batch.pop_front().map(Ok)
+    }
+}
+
+/// Builder used to create groups.
+#[derive(Clone)]
+pub struct GroupBuilder {
+    parent: Result<Handle>,
+    track_creation_order: bool,
+    index_creation_order: bool,
+    attr_creation_order: bool,
+    track_times: bool,
+}
+
+impl GroupBuilder {
+    /// Create a new group builder and bind it to the parent group.
+    pub fn new(parent: &Group) -> Self {
+        h5lock!({
+            // Store the reference to the parent handle and try to increase its reference count.
+            let handle = Handle::try_new(parent.id());
+            if let Ok(ref handle) = handle {
+                handle.incref();
+            }
+
+            Self {
+                parent: handle,
+                track_creation_order: false,
+                index_creation_order: false,
+                attr_creation_order: false,
+                track_times: true,
+            }
+        })
+    }
+
+    /// Enable or disable tracking creation order of links (disabled by default).
+    pub fn track_creation_order(&mut self, track: bool) -> &mut Self {
+        self.track_creation_order = track;
+        self
+    }
+
+    /// Enable or disable indexing links by creation order (disabled by default); this implies
+    /// tracking the creation order.
+    pub fn index_creation_order(&mut self, index: bool) -> &mut Self {
+        self.index_creation_order = index;
+        self
+    }
+
+    /// Enable or disable tracking creation order of attributes (disabled by default).
+    pub fn track_attr_creation_order(&mut self, track: bool) -> &mut Self {
+        self.attr_creation_order = track;
+        self
+    }
+
+    /// Enable or disable tracking object modification time (enabled by default).
+    ///
+    /// Disabling it makes files with identical contents reproducible byte for byte.
+    pub fn track_times(&mut self, track_times: bool) -> &mut Self {
+        self.track_times = track_times;
+        self
+    }
+
+    fn make_gcpl(&self) -> Result<PropertyList> {
+        h5lock!({
+            let gcpl = PropertyList::from_id(h5try!(H5Pcreate(*H5P_GROUP_CREATE)))?;
+            let mut flags = 0;
+            if self.track_creation_order || self.index_creation_order {
+                flags |= H5P_CRT_ORDER_TRACKED;
+            }
+            if self.index_creation_order {
+                flags |= H5P_CRT_ORDER_INDEXED;
+            }
+            h5try!(H5Pset_link_creation_order(gcpl.id(), flags));
+            if self.attr_creation_order {
+                set_attr_creation_order(gcpl.id(), true)?;
+            }
+            h5try!(H5Pset_obj_track_times(gcpl.id(), self.track_times as _));
+            Ok(gcpl)
+        })
+    }
+
+    /// Create the group and link it into the file structure.
+    pub fn create(&self, name: &str) -> Result<Group> {
+        h5lock!({
+            let parent = try_ref_clone!(self.parent);
+            let lcpl = make_lcpl()?;
+            let gcpl = self.make_gcpl()?;
+            let name = to_cstring(name)?;
+            Group::from_id(h5try!(H5Gcreate2(
+                parent.id(),
+                name.as_ptr(),
+                lcpl.id(),
+                gcpl.id(),
+                H5P_DEFAULT
+            )))
+        })
+    }
+}
+
 fn make_lcpl() -> Result<PropertyList> {
     h5lock!({
         let lcpl = PropertyList::from_id(h5try!(H5Pcreate(*H5P_LINK_CREATE)))?;
@@ -68,6 +329,37 @@ fn make_lcpl() -> Result<PropertyList> {
     })
 }
 
+/// Group wrapper applying a default filter pipeline to the datasets created through it,
+/// see `Group::with_default_filters()`.
+#[derive(Clone, Debug)]
+pub struct FilteredGroup {
+    group: Group,
+    filters: Filters,
+}
+
+impl FilteredGroup {
+    /// Returns the default filters.
+    pub fn filters(&self) -> &Filters {
+        &self.filters
+    }
+
+    /// Instantiates a new dataset builder with the default filters set; they can be
+    /// adjusted or replaced altogether (via `filters()`) on the builder.
+    pub fn new_dataset<T: H5Type>(&self) -> DatasetBuilder<T> {
+        let mut builder = self.group.new_dataset::<T>();
+        builder.filters(&self.filters);
+        builder
+    }
+}
+
+impl Deref for FilteredGroup {
+    type Target = Group;
+
+    fn deref(&self) -> &Group {
+        &self.group
+    }
+}
+
 impl Group {
     /// Returns the number of objects in the container (or 0 if the container is invalid).
     pub fn len(&self) -> u64 {
@@ -95,6 +387,50 @@ impl Group {
         })
     }
 
+    /// Creates a group along with all missing intermediate groups (like `mkdir -p`), or
+    /// opens it if it already exists; the deepest group in the path is returned.
+    pub fn create_group_all(&self, path: &str) -> Result<Self> {
+        let trimmed = path.trim_end_matches('/');
+        let path = if trimmed.is_empty() && path.starts_with('/') { "/" } else { trimmed };
+        h5lock!({
+            if path == "/" || self.link_exists(path) {
+                self.group(path)
+            } else {
+                self.create_group(path)
+            }
+        })
+    }
+
+    /// Instantiates a new group builder.
+    pub fn new_group(&self) -> GroupBuilder {
+        GroupBuilder::new(self)
+    }
+
+    /// Returns true if the creation order of links is tracked in this group.
+    pub fn tracks_creation_order(&self) -> bool {
+        h5lock!({
+            PropertyList::from_id(H5Gget_create_plist(self.id()))
+                .and_then(|gcpl| {
+                    h5get!(H5Pget_link_creation_order(gcpl.id()): c_uint)
+                        .map(|flags| flags & H5P_CRT_ORDER_TRACKED != 0)
+                })
+                .unwrap_or(false)
+        })
+    }
+
+    /// Returns `true` if object modification time is tracked by the group.
+    pub fn tracks_times(&self) -> bool {
+        h5lock!({
+            PropertyList::from_id(H5Gget_create_plist(self.id()))
+                .and_then(|gcpl| {
+                    let mut track_times: hbool_t = 0;
+                    h5call!(H5Pget_obj_track_times(gcpl.id(), &mut track_times))
+                        .map(|_| track_times > 0)
+                })
+                .unwrap_or(false)
+        })
+    }
+
     /// Opens an existing group in a file or group.
     pub fn group(&self, name: &str) -> Result<Self> {
         let name = to_cstring(name)?;
@@ -113,6 +449,31 @@ impl Group {
         })
     }
 
+    /// Creates an external link `link_name` to the object at `target_path` in file
+    /// `target_file`. Note: `link_name` is relative to the current object.
+    ///
+    /// Relative file names are resolved at link traversal time and are looked up relative
+    /// to the directory of the file containing the link (among other locations).
+    pub fn link_external(
+        &self, link_name: &str, target_file: &str, target_path: &str,
+    ) -> Result<()> {
+        h5lock!({
+            let lcpl = make_lcpl()?;
+            let link_name = to_cstring(link_name)?;
+            let target_file = to_cstring(target_file)?;
+            let target_path = to_cstring(target_path)?;
+            h5call!(H5Lcreate_external(
+                target_file.as_ptr(),
+                target_path.as_ptr(),
+                self.id(),
+                link_name.as_ptr(),
+                lcpl.id(),
+                H5P_DEFAULT
+            ))
+            .and(Ok(()))
+        })
+    }
+
     /// Creates a hard link. Note: `src` and `dst` are relative to the current object.
     pub fn link_hard(&self, src: &str, dst: &str) -> Result<()> {
         // TODO: &mut self?
@@ -129,6 +490,22 @@ impl Group {
         .and(Ok(()))
     }
 
+    /// Creates a hard link to an existing object. Note: `dst` is relative to the current
+    /// object; the object must reside in the same file.
+    pub fn link_hard_object(&self, obj: &Location, dst: &str) -> Result<()> {
+        let src = to_cstring(".")?;
+        let dst = to_cstring(dst)?;
+        h5call!(H5Lcreate_hard(
+            obj.id(),
+            src.as_ptr(),
+            self.id(),
+            dst.as_ptr(),
+            H5P_DEFAULT,
+            H5P_DEFAULT
+        ))
+        .and(Ok(()))
+    }
+
     /// Relinks an object. Note: `name` and `path` are relative to the current object.
     pub fn relink(&self, name: &str, path: &str) -> Result<()> {
         // TODO: &mut self?
@@ -153,7 +530,12 @@ impl Group {
     }
 
     /// Check if a link with a given name exists in this file or group.
+    ///
+    /// The name may be a path; `false` is returned if any of the intermediate links are
+    /// missing or can't be traversed.
     pub fn link_exists(&self, name: &str) -> bool {
+        // Missing intermediate links are reported as errors by the library.
+        let _e = silence_errors();
         (|| -> Result<bool> {
             let name = to_cstring(name)?;
             Ok(h5call!(H5Lexists(self.id(), name.as_ptr(), H5P_DEFAULT))? > 0)
@@ -161,17 +543,104 @@ impl Group {
         .unwrap_or(false)
     }
 
+    /// Returns the type of a link with a given name in this file or group.
+    pub fn link_type(&self, name: &str) -> Result<LinkType> {
+        let name = to_cstring(name)?;
+        let mut info = H5L_info_t::default();
+        h5call!(H5Lget_info(self.id(), name.as_ptr(), &mut info, H5P_DEFAULT))?;
+        match info.type_ {
+            H5L_type_t::H5L_TYPE_HARD => Ok(LinkType::Hard),
+            H5L_type_t::H5L_TYPE_SOFT => Ok(LinkType::Soft),
+            H5L_type_t::H5L_TYPE_EXTERNAL => Ok(LinkType::External),
+            _ => fail!("Unsupported link type: {:?}", info.type_),
+        }
+    }
+
     /// Instantiates a new dataset builder.
     pub fn new_dataset<T: H5Type>(&self) -> DatasetBuilder<T> {
         DatasetBuilder::<T>::new(self)
     }
 
+    /// Returns a wrapper of the group applying the given default filter pipeline to all
+    /// datasets created via its `new_dataset()` (subgroups aren't affected).
+    ///
+    /// The pipeline may be one returned by `Dataset::filter_pipeline()`; it must be
+    /// representable by `Filters`, see `Filters::from_pipeline()`. Other methods reached
+    /// through the group (e.g. `write_scalar()`, whose unchunked datasets can't be
+    /// filtered) create datasets without the default filters.
+    pub fn with_default_filters(&self, pipeline: Vec<Filter>) -> Result<FilteredGroup> {
+        let filters = Filters::from_pipeline(&pipeline)?;
+        Ok(FilteredGroup { group: self.clone(), filters })
+    }
+
     /// Opens an existing dataset in the file or group.
     pub fn dataset(&self, name: &str) -> Result<Dataset> {
         let name = to_cstring(name)?;
         Dataset::from_id(h5try!(H5Dopen2(self.id(), name.as_ptr(), H5P_DEFAULT)))
     }
 
+    /// Creates a scalar dataset holding a single value.
+    ///
+    /// The value can be read back via `Container::read_scalar()`.
+    pub fn write_scalar<T: H5Type>(&self, name: &str, value: &T) -> Result<Dataset> {
+        let ds = self.new_dataset::<T>().create(name, ())?;
+        ds.write_scalar(value)?;
+        Ok(ds)
+    }
+
+    /// Opens an existing dataset in the file or group using a given access property list.
+    pub fn dataset_with_access(&self, name: &str, dapl: &DatasetAccess) -> Result<Dataset> {
+        let name = to_cstring(name)?;
+        Dataset::from_id(h5try!(H5Dopen2(self.id(), name.as_ptr(), dapl.id())))
+    }
+
+    /// Commits the datatype of `T` to the file under a given name so that it can be shared
+    /// by multiple datasets (see `DatasetBuilder::committed_type()`).
+    pub fn commit_type<T: H5Type>(&self, name: &str) -> Result<Datatype> {
+        let datatype = Datatype::from_descriptor(&T::type_descriptor().to_c_repr())?;
+        let name = to_cstring(name)?;
+        h5try!(H5Tcommit2(
+            self.id(),
+            name.as_ptr(),
+            datatype.id(),
+            H5P_DEFAULT,
+            H5P_DEFAULT,
+            H5P_DEFAULT
+        ));
+        Ok(datatype)
+    }
+
+    /// Opens an existing committed datatype in the file or group.
+    pub fn datatype(&self, name: &str) -> Result<Datatype> {
+        let name = to_cstring(name)?;
+        Datatype::from_id(h5try!(H5Topen2(self.id(), name.as_ptr(), H5P_DEFAULT)))
+    }
+
+    /// Returns a lazy iterator over names of the members in the group, non-recursively.
+    ///
+    /// Unlike `member_names()`, this doesn't allocate all names upfront, which makes it
+    /// suitable for very large groups and allows stopping the iteration early.
+    ///
+    /// Iterating in creation order requires the group to track creation order of links.
+    pub fn iter_members(&self, index: IndexType, order: IterationOrder) -> Result<MemberIter> {
+        ensure!(self.is_valid(), "Invalid group handle");
+        if index == IndexType::CreationOrder {
+            ensure!(
+                self.tracks_creation_order(),
+                "Creation order of links is not tracked in group {:?}",
+                self.name()
+            );
+        }
+        Ok(MemberIter {
+            group: self.clone(),
+            index,
+            order,
+            position: 0,
+            batch: VecDeque::new(),
+            done: false,
+        })
+    }
+
     /// Returns names of all the members in the group, non-recursively.
     pub fn member_names(&self) -> Result<Vec<String>> {
         extern "C" fn members_callback(
@@ -200,10 +669,71 @@ impl Group {
 
         Ok(result)
     }
+
+    /// Recursively visits all objects reachable from the group via hard links, calling `f`
+    /// with the path of each object relative to the group and its type.
+    ///
+    /// Each object is visited once even if it can be reached via multiple paths (so cycles
+    /// of hard links are not a problem); soft and external links are not followed. Members
+    /// of each group are visited in a given order; visiting in creation order requires all
+    /// the groups to track creation order of links. Returning `VisitAction::Stop` from `f`
+    /// ends the traversal.
+    pub fn visit<F>(&self, index: IndexType, order: IterationOrder, mut f: F) -> Result<()>
+    where
+        F: FnMut(&str, ObjectType) -> VisitAction,
+    {
+        struct VisitData<'a> {
+            func: &'a mut dyn FnMut(&str, ObjectType) -> VisitAction,
+            panic: Option<Box<dyn Any + Send>>,
+        }
+
+        extern "C" fn visit_callback(
+            _id: hid_t, name: *const c_char, info: *const H5O_info_t, op_data: *mut c_void,
+        ) -> herr_t {
+            let data = unsafe { &mut *(op_data as *mut VisitData) };
+            let name = string_from_cstr(name);
+            if name == "." {
+                return 0; // the group itself
+            }
+            let obj_type = unsafe { (*info).type_ }.into();
+            // Panics must not unwind into the library, they are resumed once it returns.
+            match panic::catch_unwind(AssertUnwindSafe(|| (data.func)(&name, obj_type))) {
+                Ok(VisitAction::Continue) => 0,
+                Ok(VisitAction::Stop) => 1,
+                Err(payload) => {
+                    data.panic = Some(payload);
+                    -1
+                }
+            }
+        }
+
+        ensure!(self.is_valid(), "Invalid group handle");
+        let callback_fn: H5O_iterate_t = Some(visit_callback);
+        let mut data = VisitData { func: &mut f, panic: None };
+        let op_data = &mut data as *mut _ as *mut c_void;
+        #[cfg(hdf5_1_10_3)]
+        let result = h5call!(H5Ovisit2(
+            self.id(),
+            index.into(),
+            order.into(),
+            callback_fn,
+            op_data,
+            H5O_INFO_BASIC
+        ));
+        #[cfg(not(hdf5_1_10_3))]
+        let result = h5call!(H5Ovisit(self.id(), index.into(), order.into(), callback_fn, op_data));
+        if let Some(payload) = data.panic {
+            panic::resume_unwind(payload);
+        }
+        result.and(Ok(()))
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
+    use std::panic::{self, AssertUnwindSafe};
+
+    use super::{ObjectType, VisitAction};
     use crate::internal_prelude::*;
 
     #[test]
@@ -241,6 +771,30 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_create_group_all() {
+        with_tmp_file(|file| {
+            let c = file.create_group_all("a/b/c").unwrap();
+            assert_eq!(c.name(), "/a/b/c");
+            let b = file.group("a/b").unwrap();
+            b.new_attr::<u8>().create("foo", ()).unwrap();
+            let d = file.create_group_all("/a/b/d/").unwrap();
+            assert_eq!(d.name(), "/a/b/d");
+            // The existing intermediate group is reused, not recreated.
+            assert_eq!(file.group("a/b").unwrap().attr_names().unwrap(), vec!["foo"]);
+            assert_eq!(b.member_names().unwrap(), vec!["c", "d"]);
+            file.group("a/b/c").unwrap();
+            file.group("a/b/d").unwrap();
+
+            assert_eq!(file.create_group_all("a/b/c").unwrap().name(), "/a/b/c");
+            assert_eq!(b.create_group_all("c/e").unwrap().name(), "/a/b/c/e");
+            assert_eq!(file.create_group_all("/").unwrap().name(), "/");
+            file.new_dataset::<u8>().create("a/ds", 1).unwrap();
+            assert!(file.create_group_all("a/ds").is_err());
+            assert!(file.create_group_all("a/ds/x").is_err());
+        })
+    }
+
     #[test]
     pub fn test_clone() {
         with_tmp_file(|file| {
@@ -305,6 +859,101 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_link_hard_object() {
+        with_tmp_file(|file| {
+            let group = file.create_group("a/b").unwrap();
+            let ds = file.new_dataset::<u8>().create("a/ds", 2).unwrap();
+            ds.write(&[1, 2]).unwrap();
+            file.link_hard_object(&ds, "ds_alias").unwrap();
+            group.link_hard_object(&group, "self").unwrap();
+            file.group("a").unwrap().link_hard_object(&file, "root").unwrap();
+            assert_eq!(file.dataset("ds_alias").unwrap().read_raw::<u8>().unwrap(), vec![1, 2]);
+            assert_eq!(file.link_type("ds_alias").unwrap(), LinkType::Hard);
+            file.group("a/b/self/self").unwrap();
+            file.dataset("a/root/a/ds").unwrap();
+            assert_err!(
+                file.link_hard_object(&ds, "a/ds"),
+                "unable to create link: name already exists"
+            );
+            file.unlink("a/ds").unwrap();
+            assert_eq!(file.dataset("ds_alias").unwrap().read_raw::<u8>().unwrap(), vec![1, 2]);
+
+            with_tmp_file(|other| {
+                assert_err_re!(other.link_hard_object(&ds, "ds"), "same file");
+                assert!(!other.link_exists("ds"));
+            })
+        })
+    }
+
+    #[test]
+    pub fn test_default_filters() {
+        with_tmp_file(|file| {
+            let group = file
+                .create_group("a")
+                .unwrap()
+                .with_default_filters(vec![Filter::Fletcher32, Filter::Shuffle])
+                .unwrap();
+            assert!(group.filters().get_shuffle() && group.filters().get_fletcher32());
+            let ds = group.new_dataset::<u32>().create("x", 100).unwrap();
+            assert_eq!(ds.filter_pipeline().unwrap(), vec![Filter::Fletcher32, Filter::Shuffle]);
+            let filters =
+                group.new_dataset::<u32>().fletcher32(false).create("y", 100).unwrap().filters();
+            assert!(filters.get_shuffle() && !filters.get_fletcher32());
+            let ds =
+                group.new_dataset::<u32>().filters(&Filters::default()).create("z", 100).unwrap();
+            assert!(!ds.filters().has_filters());
+            group.write_scalar("s", &1_u32).unwrap();
+            assert!(!group.dataset("s").unwrap().filters().has_filters());
+            assert_eq!(group.member_names().unwrap(), vec!["s", "x", "y", "z"]);
+            assert!(!file.new_dataset::<u32>().create("w", 100).unwrap().filters().has_filters());
+
+            let ds = file.dataset("a/x").unwrap();
+            let group =
+                file.create_group("b").unwrap().with_default_filters(ds.filter_pipeline().unwrap());
+            assert_eq!(group.unwrap().filters(), &ds.filters());
+            assert_err!(
+                file.group("b")
+                    .unwrap()
+                    .with_default_filters(vec![Filter::Shuffle, Filter::Fletcher32]),
+                "Unsupported filter order"
+            );
+            assert_err!(
+                file.group("b")
+                    .unwrap()
+                    .with_default_filters(vec![Filter::Shuffle, Filter::Shuffle]),
+                "Unsupported filter order"
+            );
+            assert_err!(
+                file.group("b")
+                    .unwrap()
+                    .with_default_filters(vec![Filter::User { id: 32000, cd_values: vec![] }]),
+                "Unsupported filter: 32000"
+            );
+        })
+    }
+
+    #[test]
+    pub fn test_link_anon_dataset() {
+        with_tmp_path(|path| {
+            {
+                let file = File::create(&path).unwrap();
+                let ds = file.new_dataset::<u16>().create_anon(3).unwrap();
+                ds.write(&[1, 2, 3]).unwrap();
+                assert_eq!(ds.link_count().unwrap(), 0);
+                file.create_group("a").unwrap().link_hard_object(&ds, "ds").unwrap();
+                assert_eq!(ds.link_count().unwrap(), 1);
+                let scratch = file.new_dataset::<u16>().create_anon(1000).unwrap();
+                scratch.write(&vec![7; 1000]).unwrap();
+                assert_eq!(file.member_names().unwrap(), vec!["a"]);
+            }
+            let file = File::open(&path).unwrap();
+            assert_eq!(file.member_names().unwrap(), vec!["a"]);
+            assert_eq!(file.group("a").unwrap().member_names().unwrap(), vec!["ds"]);
+            assert_eq!(file.dataset("a/ds").unwrap().read_raw::<u16>().unwrap(), vec![1, 2, 3]);
+        })
+    }
+
     #[test]
     pub fn test_link_soft() {
         with_tmp_file(|file| {
@@ -316,6 +965,7 @@ pub mod tests {
             file.relink("a/b", "/a/d").unwrap();
             assert_err!(file.group("/a/soft2/c"), "unable to open group");
             file.link_soft("/a/bar", "/a/baz").unwrap();
+            assert_eq!(file.link_type("/a/baz").unwrap(), LinkType::Soft);
             assert_err!(file.group("/a/baz"), "unable to open group");
             file.create_group("/a/bar").unwrap();
             file.group("/a/baz").unwrap();
@@ -325,6 +975,44 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_link_external() {
+        with_tmp_dir(|dir| {
+            let data = File::create(dir.join("data.h5")).unwrap();
+            data.new_dataset::<u32>().create("x/y", 3).unwrap().write(&[1, 2, 3]).unwrap();
+            data.close();
+
+            let index = File::create(dir.join("index.h5")).unwrap();
+            index.link_external("day1", "data.h5", "/x/y").unwrap();
+            index.link_external("a/x", "data.h5", "/x").unwrap();
+            index.link_external("broken", "missing.h5", "/x/y").unwrap();
+            index.link_soft("/day1", "soft").unwrap();
+            index.new_dataset::<u8>().create("local", 1).unwrap();
+            assert_err!(
+                index.link_external("day1", "data.h5", "/x/y"),
+                "unable to create link: name already exists"
+            );
+
+            assert_eq!(index.dataset("day1").unwrap().read_raw::<u32>().unwrap(), vec![1, 2, 3]);
+            assert_eq!(index.dataset("soft").unwrap().read_raw::<u32>().unwrap(), vec![1, 2, 3]);
+            let group = index.group("a/x").unwrap();
+            assert_eq!(group.member_names().unwrap(), vec!["y"]);
+            assert_eq!(group.dataset("y").unwrap().read_raw::<u32>().unwrap(), vec![1, 2, 3]);
+            assert_eq!(index.dataset("a/x/y").unwrap().shape(), vec![3]);
+
+            assert_eq!(index.link_type("day1").unwrap(), LinkType::External);
+            assert_eq!(index.link_type("broken").unwrap(), LinkType::External);
+            assert_eq!(index.link_type("soft").unwrap(), LinkType::Soft);
+            assert_eq!(index.link_type("local").unwrap(), LinkType::Hard);
+            assert_eq!(index.link_type("a").unwrap(), LinkType::Hard);
+            assert!(index.link_type("foo").is_err());
+
+            assert!(index.link_exists("broken"));
+            assert_err_re!(index.dataset("broken"), "unable to open");
+            assert_err_re!(index.group("broken"), "unable to open");
+        })
+    }
+
     #[test]
     pub fn test_link_exists() {
         with_tmp_file(|file| {
@@ -342,6 +1030,8 @@ pub mod tests {
             assert!(group.link_exists("c"));
             assert!(!group.link_exists("a"));
             assert!(!group.link_exists("soft"));
+            assert!(!file.link_exists("x/y/z"));
+            assert!(!file.link_exists("a/b/c/d/e"));
             #[cfg(not(hdf5_1_10_0))]
             assert!(!group.link_exists("/"));
             #[cfg(hdf5_1_10_0)]
@@ -385,6 +1075,201 @@ pub mod tests {
         });
     }
 
+    #[test]
+    pub fn test_write_scalar() {
+        with_tmp_file(|file| {
+            let ds = file.write_scalar("config/scale", &0.25_f64).unwrap();
+            assert_eq!(ds.ndim(), 0);
+            assert_eq!(ds.read_scalar::<f64>().unwrap(), 0.25);
+            file.write_scalar("count", &-7_i64).unwrap();
+            assert_eq!(file.dataset("count").unwrap().read_scalar::<i64>().unwrap(), -7);
+            assert_err!(file.write_scalar("count", &1_i64), "name already exists");
+
+            file.new_dataset::<i64>().create("vec", 1).unwrap().write(&[3]).unwrap();
+            assert_err!(
+                file.dataset("vec").unwrap().read_scalar::<i64>(),
+                "ndim mismatch: expected scalar, got 1"
+            );
+        });
+    }
+
+    #[test]
+    pub fn test_iter_members() {
+        with_tmp_file(|file| {
+            let group = file.create_group("foo").unwrap();
+            assert_eq!(
+                group.iter_members(IndexType::Name, IterationOrder::Increasing).unwrap().count(),
+                0
+            );
+            let names: Vec<_> = (0..1000).map(|i| format!("{:04}", i)).collect();
+            for name in &names {
+                group.create_group(name).unwrap();
+            }
+
+            let mut iter = group.iter_members(IndexType::Name, IterationOrder::Increasing).unwrap();
+            let mut first = vec![];
+            for name in &mut iter {
+                let name = name.unwrap();
+                // The library lock must not be held while it's yielding names.
+                assert!(group.group(&name).unwrap().is_empty());
+                first.push(name);
+                if first.len() == 10 {
+                    break;
+                }
+            }
+            assert_eq!(first, &names[..10]);
+            assert_eq!(iter.next().unwrap().unwrap(), "0010");
+
+            let all = group.iter_members(IndexType::default(), IterationOrder::default()).unwrap();
+            assert_eq!(all.collect::<Result<Vec<_>>>().unwrap(), names);
+            let rev = group.iter_members(IndexType::Name, IterationOrder::Decreasing).unwrap();
+            let rev: Vec<_> = rev.map(|name| name.unwrap()).collect();
+            assert_eq!(rev.len(), 1000);
+            assert_eq!(rev[0], "0999");
+            assert_eq!(rev[999], "0000");
+            let mut native = group
+                .iter_members(IndexType::Name, IterationOrder::Native)
+                .unwrap()
+                .collect::<Result<Vec<_>>>()
+                .unwrap();
+            native.sort();
+            assert_eq!(native, names);
+        })
+    }
+
+    #[test]
+    pub fn test_visit() {
+        with_tmp_file(|file| {
+            let c = file.create_group("a/b/c").unwrap();
+            file.new_dataset::<u8>().create("a/x", 1).unwrap();
+            file.new_dataset::<u8>().create("a/b/y", 1).unwrap();
+            c.new_dataset::<u8>().create("z", 1).unwrap();
+            file.commit_type::<u32>("t").unwrap();
+            file.link_hard("a", "a/b/c/loop").unwrap();
+            file.link_hard("a/x", "x2").unwrap();
+            file.link_soft("/a/b", "soft").unwrap();
+
+            let mut visited = Vec::new();
+            file.visit(IndexType::Name, IterationOrder::Increasing, |path, obj_type| {
+                visited.push((path.to_owned(), obj_type));
+                VisitAction::Continue
+            })
+            .unwrap();
+            let mut paths: Vec<_> = visited.iter().map(|(path, _)| path.as_str()).collect();
+            paths.sort();
+            assert_eq!(paths.len(), 7);
+            // Each object is only visited once, via whichever path is found first.
+            assert!(paths.contains(&"a/x") ^ paths.contains(&"x2"));
+            paths.retain(|&p| p != "a/x" && p != "x2");
+            assert_eq!(paths, ["a", "a/b", "a/b/c", "a/b/c/z", "a/b/y", "t"]);
+            let types: Vec<_> = visited.iter().map(|&(_, obj_type)| obj_type).collect();
+            assert_eq!(types.iter().filter(|&&t| t == ObjectType::Group).count(), 3);
+            assert_eq!(types.iter().filter(|&&t| t == ObjectType::Dataset).count(), 3);
+            assert_eq!(types.iter().filter(|&&t| t == ObjectType::Datatype).count(), 1);
+
+            let mut visited = Vec::new();
+            c.visit(IndexType::Name, IterationOrder::Increasing, |path, _| {
+                visited.push(path.to_owned());
+                VisitAction::Continue
+            })
+            .unwrap();
+            // The hard link back to "a" is followed from within "c".
+            assert!(visited.contains(&"z".to_owned()));
+            assert!(visited.contains(&"loop".to_owned()));
+
+            let mut count = 0;
+            file.visit(IndexType::Name, IterationOrder::Increasing, |_, _| {
+                count += 1;
+                if count == 2 {
+                    VisitAction::Stop
+                } else {
+                    VisitAction::Continue
+                }
+            })
+            .unwrap();
+            assert_eq!(count, 2);
+
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                file.visit(IndexType::Name, IterationOrder::Increasing, |_, _| panic!("oops"))
+            }));
+            assert!(result.is_err());
+        })
+    }
+
+    #[test]
+    pub fn test_track_times() {
+        with_tmp_file(|file| {
+            let group = file.create_group("tracked").unwrap();
+            assert!(group.tracks_times());
+            assert!(group.modified_time().unwrap().is_some());
+            let group = file.new_group().track_times(false).create("untracked").unwrap();
+            assert!(!group.tracks_times());
+            assert_eq!(group.modified_time().unwrap(), None);
+            assert_eq!(group.changed_time().unwrap(), None);
+
+            for name in &["a", "b"] {
+                let ds = group.new_dataset::<u8>().track_times(false).create(name, 1).unwrap();
+                ds.write(&[1]).unwrap();
+                assert!(!ds.tracks_times());
+                assert_eq!(ds.modified_time().unwrap(), None);
+            }
+        })
+    }
+
+    #[test]
+    pub fn test_creation_order() {
+        with_tmp_file(|file| {
+            let group = file.new_group().index_creation_order(true).create("foo/bar").unwrap();
+            assert!(group.tracks_creation_order());
+            assert!(!file.group("foo").unwrap().tracks_creation_order());
+            for name in &["b", "a", "c"] {
+                group.create_group(name).unwrap();
+            }
+            let names = |index, order| -> Vec<String> {
+                group.iter_members(index, order).unwrap().map(|name| name.unwrap()).collect()
+            };
+            assert_eq!(
+                names(IndexType::CreationOrder, IterationOrder::Increasing),
+                ["b", "a", "c"]
+            );
+            assert_eq!(
+                names(IndexType::CreationOrder, IterationOrder::Decreasing),
+                ["c", "a", "b"]
+            );
+            assert_eq!(names(IndexType::Name, IterationOrder::Increasing), ["a", "b", "c"]);
+            assert_eq!(group.member_names().unwrap(), ["a", "b", "c"]);
+
+            let group = file.new_group().track_creation_order(true).create("tracked").unwrap();
+            assert!(group.tracks_creation_order());
+            group.create_group("y").unwrap();
+            group.create_group("x").unwrap();
+            let names: Vec<_> = group
+                .iter_members(IndexType::CreationOrder, IterationOrder::Increasing)
+                .unwrap()
+                .map(|name| name.unwrap())
+                .collect();
+            assert_eq!(names, ["y", "x"]);
+
+            let group = file.new_group().create("untracked").unwrap();
+            assert!(!group.tracks_creation_order());
+            assert_err!(
+                group.iter_members(IndexType::CreationOrder, IterationOrder::Increasing),
+                "Creation order of links is not tracked in group \"/untracked\""
+            );
+            group.iter_members(IndexType::Name, IterationOrder::Increasing).unwrap();
+
+            let group = file.new_group().track_attr_creation_order(true).create("attrs").unwrap();
+            group.new_attr::<u8>().create("z", ()).unwrap();
+            group.new_attr::<u8>().create("y", ()).unwrap();
+            let names: Vec<_> = group
+                .iter_attrs(IndexType::CreationOrder, IterationOrder::Increasing)
+                .unwrap()
+                .map(|(name, _)| name)
+                .collect();
+            assert_eq!(names, ["z", "y"]);
+        })
+    }
+
     #[test]
     pub fn test_get_member_names() {
         with_tmp_file(|file| {
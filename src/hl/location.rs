@@ -1,13 +1,24 @@
 use std::fmt::{self, Debug};
 use std::ops::Deref;
 use std::ptr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use hdf5_sys::{
     h5f::H5Fget_name,
     h5i::{H5Iget_file_id, H5Iget_name},
-    h5o::{H5Oget_comment, H5Oset_comment},
+    h5o::{
+        H5O_info_t, H5Ocopy, H5Oget_comment, H5Oset_comment, H5O_COPY_EXPAND_EXT_LINK_FLAG,
+        H5O_COPY_EXPAND_SOFT_LINK_FLAG, H5O_COPY_SHALLOW_HIERARCHY_FLAG,
+    },
+    h5p::{H5Pcreate, H5Pset_copy_object},
 };
 
+#[cfg(not(hdf5_1_10_3))]
+use hdf5_sys::h5o::H5Oget_info;
+#[cfg(hdf5_1_10_3)]
+use hdf5_sys::h5o::{H5Oget_info2, H5O_INFO_BASIC, H5O_INFO_TIME};
+
+use crate::globals::H5P_OBJECT_COPY;
 use crate::internal_prelude::*;
 
 /// Named location (file, group, dataset, named datatype).
@@ -47,6 +58,93 @@ impl Deref for Location {
     }
 }
 
+/// Token identifying an object within its file regardless of the links pointing to it,
+/// see `Location::token()` and `File::open_by_token()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ObjectToken(haddr_t);
+
+impl ObjectToken {
+    /// Returns the address of the object header in the file.
+    pub fn addr(&self) -> u64 {
+        self.0 as _
+    }
+}
+
+/// Converts a timestamp stored in the object header; zero means there's none.
+fn to_system_time(time: libc::time_t) -> Option<SystemTime> {
+    if time > 0 {
+        Some(UNIX_EPOCH + Duration::from_secs(time as _))
+    } else {
+        None
+    }
+}
+
+/// Options controlling how objects are copied via `Location::copy_to_with()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CopyOptions {
+    expand_soft_links: bool,
+    expand_external_links: bool,
+    shallow_hierarchy: bool,
+}
+
+impl CopyOptions {
+    /// Creates a new set of copy options (full recursive copy, links preserved as is).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy the objects pointed to by soft links instead of the links themselves.
+    pub fn expand_soft_links(&mut self, expand: bool) -> &mut Self {
+        self.expand_soft_links = expand;
+        self
+    }
+
+    /// Copy the objects pointed to by external links instead of the links themselves.
+    pub fn expand_external_links(&mut self, expand: bool) -> &mut Self {
+        self.expand_external_links = expand;
+        self
+    }
+
+    /// Only copy the immediate members of a group instead of the whole hierarchy.
+    pub fn shallow_hierarchy(&mut self, shallow: bool) -> &mut Self {
+        self.shallow_hierarchy = shallow;
+        self
+    }
+
+    pub fn get_expand_soft_links(&self) -> bool {
+        self.expand_soft_links
+    }
+
+    pub fn get_expand_external_links(&self) -> bool {
+        self.expand_external_links
+    }
+
+    pub fn get_shallow_hierarchy(&self) -> bool {
+        self.shallow_hierarchy
+    }
+
+    fn flags(&self) -> c_uint {
+        let mut flags = 0;
+        if self.expand_soft_links {
+            flags |= H5O_COPY_EXPAND_SOFT_LINK_FLAG;
+        }
+        if self.expand_external_links {
+            flags |= H5O_COPY_EXPAND_EXT_LINK_FLAG;
+        }
+        if self.shallow_hierarchy {
+            flags |= H5O_COPY_SHALLOW_HIERARCHY_FLAG;
+        }
+        flags
+    }
+
+    fn make_ocpypl(&self) -> Result<PropertyList> {
+        h5lock!({
+            let ocpypl = PropertyList::from_id(h5try!(H5Pcreate(*H5P_OBJECT_COPY)))?;
+            h5call!(H5Pset_copy_object(ocpypl.id(), self.flags())).and(Ok(ocpypl))
+        })
+    }
+}
+
 impl Location {
     /// Returns the name of the object within the file, or empty string if the object doesn't
     /// have a name (e.g., an anonymous dataset).
@@ -67,10 +165,9 @@ impl Location {
     }
 
     /// Returns the commment attached to the named object, if any.
-    pub fn comment(&self) -> Option<String> {
-        // TODO: should this return Result<Option<String>> or fail silently?
-        let comment = h5lock!(get_h5_str(|m, s| H5Oget_comment(self.id(), m, s)).ok());
-        comment.and_then(|c| if c.is_empty() { None } else { Some(c) })
+    pub fn comment(&self) -> Result<Option<String>> {
+        let comment = h5lock!(get_h5_str(|m, s| H5Oget_comment(self.id(), m, s)))?;
+        Ok(if comment.is_empty() { None } else { Some(comment) })
     }
 
     /// Set or the commment attached to the named object.
@@ -85,10 +182,99 @@ impl Location {
         // TODO: &mut self?
         h5call!(H5Oset_comment(self.id(), ptr::null_mut())).and(Ok(()))
     }
+
+    /// Returns the basic object info (address, type and reference count).
+    pub(crate) fn basic_info(&self) -> Result<H5O_info_t> {
+        let mut info = H5O_info_t::default();
+        // Since 1.10.3, the fields to be retrieved have to be requested explicitly.
+        #[cfg(hdf5_1_10_3)]
+        h5call!(H5Oget_info2(self.id(), &mut info, H5O_INFO_BASIC))?;
+        #[cfg(not(hdf5_1_10_3))]
+        h5call!(H5Oget_info(self.id(), &mut info))?;
+        Ok(info)
+    }
+
+    fn time_info(&self) -> Result<H5O_info_t> {
+        let mut info = H5O_info_t::default();
+        #[cfg(hdf5_1_10_3)]
+        h5call!(H5Oget_info2(self.id(), &mut info, H5O_INFO_TIME))?;
+        #[cfg(not(hdf5_1_10_3))]
+        h5call!(H5Oget_info(self.id(), &mut info))?;
+        Ok(info)
+    }
+
+    /// Returns the last access time of the object, or `None` if it hasn't been recorded
+    /// (note that the library currently never updates it after creation).
+    ///
+    /// Times are only recorded for objects created with time tracking enabled (e.g. via
+    /// `DatasetBuilder::track_times()`).
+    pub fn accessed_time(&self) -> Result<Option<SystemTime>> {
+        self.time_info().map(|info| to_system_time(info.atime))
+    }
+
+    /// Returns the last modification time of the object's data, or `None` if it hasn't been
+    /// recorded.
+    pub fn modified_time(&self) -> Result<Option<SystemTime>> {
+        self.time_info().map(|info| to_system_time(info.mtime))
+    }
+
+    /// Returns the last time the object's metadata has been changed, or `None` if it hasn't
+    /// been recorded.
+    pub fn changed_time(&self) -> Result<Option<SystemTime>> {
+        self.time_info().map(|info| to_system_time(info.ctime))
+    }
+
+    /// Returns the creation time of the object, or `None` if it hasn't been recorded.
+    pub fn created_time(&self) -> Result<Option<SystemTime>> {
+        self.time_info().map(|info| to_system_time(info.btime))
+    }
+
+    /// Returns the number of hard links pointing to the object.
+    pub fn link_count(&self) -> Result<u64> {
+        self.basic_info().map(|info| u64::from(info.rc))
+    }
+
+    /// Returns the address of the object header in the file; two locations in the same file
+    /// refer to the same object if and only if their addresses are equal.
+    pub fn object_address(&self) -> Result<u64> {
+        self.basic_info().map(|info| info.addr as _)
+    }
+
+    /// Returns a token identifying the object within its file; unlike the name, the token
+    /// doesn't change when the links pointing to the object are renamed.
+    pub fn token(&self) -> Result<ObjectToken> {
+        self.basic_info().map(|info| ObjectToken(info.addr))
+    }
+
+    /// Recursively copies the object (along with its attributes and members) into the
+    /// destination group under a given name; the destination may belong to another file.
+    pub fn copy_to(&self, dest: &Group, name: &str) -> Result<()> {
+        self.copy_to_with(dest, name, &CopyOptions::default())
+    }
+
+    /// Copies the object into the destination group using the provided copy options.
+    pub fn copy_to_with(&self, dest: &Group, name: &str, options: &CopyOptions) -> Result<()> {
+        let src = to_cstring(".")?;
+        let name = to_cstring(name)?;
+        h5lock!({
+            let ocpypl = options.make_ocpypl()?;
+            h5call!(H5Ocopy(
+                self.id(),
+                src.as_ptr(),
+                dest.id(),
+                name.as_ptr(),
+                ocpypl.id(),
+                H5P_DEFAULT
+            ))
+            .and(Ok(()))
+        })
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
+    use hdf5_sys::h5o::{H5O_COPY_EXPAND_SOFT_LINK_FLAG, H5O_COPY_SHALLOW_HIERARCHY_FLAG};
+
     use crate::internal_prelude::*;
 
     #[test]
@@ -112,14 +298,140 @@ pub mod tests {
         })
     }
 
+    #[test]
+    pub fn test_times() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u8>().create("untracked", 1).unwrap();
+            assert_eq!(ds.modified_time().unwrap(), None);
+            assert_eq!(ds.changed_time().unwrap(), None);
+            assert_eq!(ds.created_time().unwrap(), None);
+
+            let now = SystemTime::now();
+            let ds = file.new_dataset::<u8>().track_times(true).create("tracked", 1).unwrap();
+            ds.write(&[1]).unwrap();
+            for time in &[ds.modified_time().unwrap(), ds.changed_time().unwrap()] {
+                let time = time.unwrap();
+                let diff = time.duration_since(now).unwrap_or_else(|e| e.duration());
+                assert!(diff < Duration::from_secs(60), "{:?} vs {:?}", time, now);
+            }
+        })
+    }
+
+    #[test]
+    pub fn test_link_count() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u8>().create("foo", 1).unwrap();
+            file.new_dataset::<u8>().create("bar", 1).unwrap();
+            assert_eq!(ds.link_count().unwrap(), 1);
+            file.link_hard("foo", "baz").unwrap();
+            assert_eq!(ds.link_count().unwrap(), 2);
+            let alias = file.dataset("baz").unwrap();
+            assert_eq!(alias.object_address().unwrap(), ds.object_address().unwrap());
+            assert_ne!(
+                file.dataset("bar").unwrap().object_address().unwrap(),
+                ds.object_address().unwrap()
+            );
+            file.unlink("foo").unwrap();
+            assert_eq!(alias.link_count().unwrap(), 1);
+            assert_eq!(file.link_count().unwrap(), 1);
+            let anon = file.new_dataset::<u8>().create_anon(1).unwrap();
+            assert_eq!(anon.link_count().unwrap(), 0);
+        })
+    }
+
+    #[test]
+    pub fn test_token() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u8>().create("a/foo", 1).unwrap();
+            let token = ds.token().unwrap();
+            assert_eq!(token.addr(), ds.object_address().unwrap());
+            assert_ne!(file.group("a").unwrap().token().unwrap(), token);
+            file.relink("a/foo", "bar").unwrap();
+            drop(ds);
+
+            let loc = file.open_by_token(&token).unwrap();
+            assert_eq!(loc.id_type(), H5I_DATASET);
+            assert_eq!(loc.name(), "/bar");
+            assert_eq!(loc.token().unwrap(), token);
+            assert_eq!(
+                loc.object_address().unwrap(),
+                file.dataset("bar").unwrap().object_address().unwrap()
+            );
+            assert_eq!(file.open_by_token(&file.token().unwrap()).unwrap().name(), "/");
+        })
+    }
+
     #[test]
     pub fn test_comment() {
         with_tmp_file(|file| {
-            assert!(file.comment().is_none());
+            assert!(file.comment().unwrap().is_none());
             assert!(file.set_comment("foo").is_ok());
-            assert_eq!(file.comment().unwrap(), "foo");
+            assert_eq!(file.comment().unwrap().unwrap(), "foo");
             assert!(file.clear_comment().is_ok());
-            assert!(file.comment().is_none());
+            assert!(file.comment().unwrap().is_none());
+
+            let ds = file.new_dataset::<u8>().create("ds", 1).unwrap();
+            assert!(ds.comment().unwrap().is_none());
+            let comment = "провенанс: ∑ 测试 ✓";
+            ds.set_comment(comment).unwrap();
+            assert_eq!(ds.comment().unwrap().unwrap(), comment);
+            assert_eq!(ds.comment().unwrap().unwrap().as_bytes(), comment.as_bytes());
+            assert!(file.comment().unwrap().is_none());
+            assert_err!(ds.set_comment("foo\0bar"), "null byte in string");
+            ds.set_comment("").unwrap();
+            assert!(ds.comment().unwrap().is_none());
+        })
+    }
+
+    #[test]
+    pub fn test_copy_options() {
+        let mut options = CopyOptions::new();
+        assert_eq!(options.flags(), 0);
+        options.expand_soft_links(true).shallow_hierarchy(true);
+        assert!(options.get_expand_soft_links());
+        assert!(!options.get_expand_external_links());
+        assert!(options.get_shallow_hierarchy());
+        assert_eq!(
+            options.flags(),
+            H5O_COPY_EXPAND_SOFT_LINK_FLAG | H5O_COPY_SHALLOW_HIERARCHY_FLAG
+        );
+    }
+
+    #[test]
+    pub fn test_copy_to() {
+        with_tmp_dir(|dir| {
+            let src = File::create(dir.join("src.h5")).unwrap();
+            let dst = File::create(dir.join("dst.h5")).unwrap();
+
+            let ds = src
+                .new_dataset::<u32>()
+                .chunk((10, 5))
+                .shuffle(true)
+                .fletcher32(true)
+                .create("foo/bar", (20, 10))
+                .unwrap();
+            ds.write_raw(&(0..200).collect::<Vec<u32>>()).unwrap();
+            ds.set_comment("baz").unwrap();
+
+            ds.copy_to(&dst, "copy").unwrap();
+            let copy = dst.dataset("copy").unwrap();
+            assert_eq!(copy.shape(), vec![20, 10]);
+            assert_eq!(copy.chunks(), Some(vec![10, 5]));
+            assert_eq!(copy.filters(), ds.filters());
+            assert!(copy.filters().get_shuffle());
+            assert!(copy.filters().get_fletcher32());
+            assert_eq!(copy.comment().unwrap().unwrap(), "baz");
+            assert_eq!(copy.read_raw::<u32>().unwrap(), (0..200).collect::<Vec<u32>>());
+
+            src.group("foo").unwrap().copy_to(&dst, "group").unwrap();
+            assert_eq!(dst.dataset("group/bar").unwrap().filters(), ds.filters());
+
+            let grp = dst.create_group("shallow").unwrap();
+            src.copy_to_with(&grp, "root", CopyOptions::new().shallow_hierarchy(true)).unwrap();
+            assert!(grp.link_exists("root/foo"));
+            assert!(!grp.link_exists("root/foo/bar"));
+
+            assert_err!(ds.copy_to(&dst, "copy"), "already exists");
         })
     }
 }
@@ -1,21 +1,40 @@
+#[cfg(feature = "tokio")]
+mod async_io;
+pub mod attribute;
 pub mod container;
 pub mod dataset;
 pub mod datatype;
+mod dimension_scale;
 pub mod file;
 pub mod group;
 pub mod location;
 pub mod object;
+pub mod packet;
 pub mod plist;
+pub mod selection;
 pub mod space;
+pub mod stats;
+pub mod stream;
+pub mod tree;
+pub mod typed;
+mod validation;
 
 pub use self::{
+    attribute::{AttrValue, Attribute, AttributeBuilder},
     container::{Container, Reader, Writer},
     dataset::{Dataset, DatasetBuilder},
-    datatype::{Conversion, Datatype},
+    datatype::{
+        ByteOrder, CharEncoding, Conversion, ConversionPolicy, Datatype, StringPadding, TypeClass,
+    },
     file::{File, FileBuilder, OpenMode},
-    group::Group,
-    location::Location,
+    group::{
+        FilteredGroup, Group, GroupBuilder, IndexType, IterationOrder, LinkType, MemberIter,
+        ObjectType, VisitAction,
+    },
+    location::{CopyOptions, Location, ObjectToken},
     object::Object,
     plist::PropertyList,
+    selection::{Hyperslab, Selection},
     space::Dataspace,
+    validation::ValidationReport,
 };
@@ -0,0 +1,118 @@
+//! Append-only tables of fixed-size records.
+
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::slice;
+
+use ndarray::s;
+
+use crate::internal_prelude::*;
+
+/// Default number of records per chunk in newly created packet tables.
+pub const DEFAULT_CHUNK_SIZE: usize = 512;
+
+/// Append-only table of records of type `T` (e.g. a `#[derive(H5Type)]` struct).
+///
+/// A packet table is stored as a chunked one-dimensional dataset with unlimited maximum
+/// size, the same layout as the one used by the high-level `H5PT` C API, so the tables
+/// can be shared with programs using it. It's implemented on top of the core library
+/// and doesn't require the HDF5 high-level library to be available.
+pub struct PacketTable<T> {
+    ds: Dataset,
+    phantom: PhantomData<T>,
+}
+
+impl<T> Debug for PacketTable<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PacketTable").field("dataset", &self.ds).finish()
+    }
+}
+
+impl<T: H5Type> PacketTable<T> {
+    /// Creates a new empty packet table with the default chunk size.
+    pub fn create(group: &Group, name: &str) -> Result<Self> {
+        Self::create_chunked(group, name, DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Creates a new empty packet table storing `chunk_size` records per chunk.
+    pub fn create_chunked(group: &Group, name: &str, chunk_size: usize) -> Result<Self> {
+        ensure!(chunk_size > 0, "Packet table chunk size must be positive");
+        let ds = group.new_dataset::<T>().chunk(chunk_size).resizable(true).create(name, 0)?;
+        Ok(Self { ds, phantom: PhantomData })
+    }
+
+    /// Opens an existing packet table (any one-dimensional extendible dataset).
+    pub fn open(group: &Group, name: &str) -> Result<Self> {
+        let ds = group.dataset(name)?;
+        ensure!(ds.ndim() == 1, "Packet table must be one-dimensional, got {} dims", ds.ndim());
+        ensure!(ds.is_resizable(), "Packet table must be resizable");
+        Ok(Self { ds, phantom: PhantomData })
+    }
+
+    /// Appends a single record to the end of the table.
+    pub fn append(&self, record: &T) -> Result<()> {
+        self.append_many(slice::from_ref(record))
+    }
+
+    /// Appends multiple records to the end of the table.
+    pub fn append_many(&self, records: &[T]) -> Result<()> {
+        self.ds.append(0, records)
+    }
+
+    /// Reads the record at a given index.
+    pub fn get(&self, index: usize) -> Result<T> {
+        let len = self.len();
+        ensure!(index < len, "Packet index {} is out of bounds (table length is {})", index, len);
+        let records = self.ds.read_slice_1d::<T, _>(&s![index..index + 1])?;
+        Ok(records.into_raw_vec().remove(0))
+    }
+
+    /// Returns the number of records in the table.
+    pub fn len(&self) -> usize {
+        self.ds.size()
+    }
+
+    /// Returns `true` if the table contains no records.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the underlying dataset.
+    pub fn dataset(&self) -> &Dataset {
+        &self.ds
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::PacketTable;
+    use crate::internal_prelude::*;
+
+    #[test]
+    pub fn test_packet_table() {
+        with_tmp_file(|file| {
+            let table = PacketTable::<u32>::create(&file, "table").unwrap();
+            assert!(table.is_empty());
+            assert_err!(table.get(0), "Packet index 0 is out of bounds (table length is 0)");
+            table.append(&1).unwrap();
+            table.append_many(&[2, 3]).unwrap();
+            table.append_many(&[]).unwrap();
+            assert_eq!(table.len(), 3);
+            assert_eq!(table.get(1).unwrap(), 2);
+            assert_eq!(table.dataset().chunks(), Some(vec![super::DEFAULT_CHUNK_SIZE]));
+
+            let table = PacketTable::<u32>::open(&file, "table").unwrap();
+            table.append(&4).unwrap();
+            assert_eq!(table.dataset().read_raw::<u32>().unwrap(), vec![1, 2, 3, 4]);
+
+            file.new_dataset::<u32>().create("fixed", 3).unwrap();
+            assert_err!(PacketTable::<u32>::open(&file, "fixed"), "must be resizable");
+            file.new_dataset::<u32>().chunk((1, 1)).resizable(true).create("2d", (1, 1)).unwrap();
+            assert_err!(PacketTable::<u32>::open(&file, "2d"), "must be one-dimensional");
+            assert_err!(
+                PacketTable::<u32>::create_chunked(&file, "empty", 0),
+                "chunk size must be positive"
+            );
+        })
+    }
+}
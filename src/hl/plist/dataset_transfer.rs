@@ -0,0 +1,241 @@
+//! Data transfer properties.
+
+use std::fmt::{self, Debug};
+use std::ops::Deref;
+use std::ptr;
+
+#[cfg(h5_have_parallel)]
+use hdf5_sys::h5p::{H5FD_mpio_xfer_t, H5Pget_dxpl_mpio, H5Pset_dxpl_mpio};
+use hdf5_sys::h5p::{H5Pcreate, H5Pget_buffer, H5Pget_edc_check, H5Pset_buffer, H5Pset_edc_check};
+use hdf5_sys::h5z::H5Z_EDC_t;
+
+use crate::globals::H5P_DATASET_XFER;
+use crate::internal_prelude::*;
+
+/// Data transfer properties.
+#[repr(transparent)]
+pub struct DatasetTransfer(Handle);
+
+impl ObjectClass for DatasetTransfer {
+    const NAME: &'static str = "data transfer property list";
+    const VALID_TYPES: &'static [H5I_type_t] = &[H5I_GENPROP_LST];
+
+    fn from_handle(handle: Handle) -> Self {
+        Self(handle)
+    }
+
+    fn handle(&self) -> &Handle {
+        &self.0
+    }
+
+    fn validate(&self) -> Result<()> {
+        let class = self.class()?;
+        if class != PropertyListClass::DataTransfer {
+            fail!("expected data transfer property list, got {:?}", class);
+        }
+        Ok(())
+    }
+}
+
+impl Debug for DatasetTransfer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let _e = silence_errors();
+        let mut formatter = f.debug_struct("DatasetTransfer");
+        formatter.field("edc_check", &self.edc_check());
+        formatter.field("buffer_size", &self.buffer_size());
+        #[cfg(h5_have_parallel)]
+        formatter.field("mpio_transfer_mode", &self.mpio_transfer_mode());
+        formatter.finish()
+    }
+}
+
+impl Deref for DatasetTransfer {
+    type Target = PropertyList;
+
+    fn deref(&self) -> &PropertyList {
+        unsafe { self.transmute() }
+    }
+}
+
+impl PartialEq for DatasetTransfer {
+    fn eq(&self, other: &Self) -> bool {
+        <PropertyList as PartialEq>::eq(self, other)
+    }
+}
+
+impl Eq for DatasetTransfer {}
+
+impl Clone for DatasetTransfer {
+    fn clone(&self) -> Self {
+        unsafe { self.deref().clone().cast() }
+    }
+}
+
+/// MPI-IO data transfer mode.
+#[cfg(h5_have_parallel)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransferMode {
+    /// Each process performs its own I/O independently.
+    Independent,
+    /// All processes participate in collective I/O operations.
+    Collective,
+}
+
+#[cfg(h5_have_parallel)]
+impl Default for TransferMode {
+    fn default() -> Self {
+        TransferMode::Independent
+    }
+}
+
+#[cfg(h5_have_parallel)]
+impl From<H5FD_mpio_xfer_t> for TransferMode {
+    fn from(mode: H5FD_mpio_xfer_t) -> Self {
+        match mode {
+            H5FD_mpio_xfer_t::H5FD_MPIO_COLLECTIVE => TransferMode::Collective,
+            _ => TransferMode::Independent,
+        }
+    }
+}
+
+#[cfg(h5_have_parallel)]
+impl From<TransferMode> for H5FD_mpio_xfer_t {
+    fn from(mode: TransferMode) -> Self {
+        match mode {
+            TransferMode::Collective => H5FD_mpio_xfer_t::H5FD_MPIO_COLLECTIVE,
+            _ => H5FD_mpio_xfer_t::H5FD_MPIO_INDEPENDENT,
+        }
+    }
+}
+
+/// Builder used to create data transfer property list.
+#[derive(Clone, Debug, Default)]
+pub struct DatasetTransferBuilder {
+    edc_check: Option<bool>,
+    buffer_size: Option<usize>,
+    #[cfg(h5_have_parallel)]
+    mpio_transfer_mode: Option<TransferMode>,
+}
+
+impl DatasetTransferBuilder {
+    /// Creates a new data transfer property list builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new builder from an existing property list.
+    pub fn from_plist(plist: &DatasetTransfer) -> Result<Self> {
+        let mut builder = Self::default();
+        builder.edc_check(plist.get_edc_check()?);
+        builder.buffer_size(plist.get_buffer_size()?);
+        #[cfg(h5_have_parallel)]
+        builder.mpio_transfer_mode(plist.get_mpio_transfer_mode()?);
+        Ok(builder)
+    }
+
+    /// Enables or disables verification of error-detecting checksums (e.g. fletcher32) when
+    /// reading data (enabled by default).
+    pub fn edc_check(&mut self, enable: bool) -> &mut Self {
+        self.edc_check = Some(enable);
+        self
+    }
+
+    /// Sets the size in bytes of the type conversion buffer (1 MiB by default); it must be
+    /// large enough to hold at least one element of the memory and the file datatypes.
+    ///
+    /// Conversions requiring a background buffer (e.g. between compound datatypes) allocate
+    /// it with the same size, so the peak memory usage is up to twice as large. Larger
+    /// buffers mean fewer conversion passes over the data.
+    pub fn buffer_size(&mut self, size: usize) -> &mut Self {
+        self.buffer_size = Some(size);
+        self
+    }
+
+    #[cfg(h5_have_parallel)]
+    pub fn mpio_transfer_mode(&mut self, mode: TransferMode) -> &mut Self {
+        self.mpio_transfer_mode = Some(mode);
+        self
+    }
+
+    fn populate_plist(&self, id: hid_t) -> Result<()> {
+        if let Some(v) = self.edc_check {
+            let check = if v { H5Z_EDC_t::H5Z_ENABLE_EDC } else { H5Z_EDC_t::H5Z_DISABLE_EDC };
+            h5try!(H5Pset_edc_check(id, check));
+        }
+        if let Some(v) = self.buffer_size {
+            ensure!(v > 0, "Type conversion buffer size must be positive");
+            h5try!(H5Pset_buffer(id, v as _, ptr::null_mut(), ptr::null_mut()));
+        }
+        #[cfg(h5_have_parallel)]
+        {
+            if let Some(v) = self.mpio_transfer_mode {
+                h5try!(H5Pset_dxpl_mpio(id, v.into()));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn finish(&self) -> Result<DatasetTransfer> {
+        h5lock!({
+            let plist = DatasetTransfer::try_new()?;
+            self.populate_plist(plist.id())?;
+            Ok(plist)
+        })
+    }
+}
+
+/// Data transfer property list.
+impl DatasetTransfer {
+    pub fn try_new() -> Result<Self> {
+        Self::from_id(h5try!(H5Pcreate(*H5P_DATASET_XFER)))
+    }
+
+    pub fn copy(&self) -> Self {
+        unsafe { self.deref().copy().cast() }
+    }
+
+    pub fn build() -> DatasetTransferBuilder {
+        DatasetTransferBuilder::new()
+    }
+
+    #[doc(hidden)]
+    pub fn get_edc_check(&self) -> Result<bool> {
+        match h5lock!(H5Pget_edc_check(self.id())) {
+            H5Z_EDC_t::H5Z_ENABLE_EDC => Ok(true),
+            H5Z_EDC_t::H5Z_DISABLE_EDC => Ok(false),
+            _ => fail!("failed to get error detection setting"),
+        }
+    }
+
+    pub fn edc_check(&self) -> bool {
+        self.get_edc_check().unwrap_or(true)
+    }
+
+    #[doc(hidden)]
+    pub fn get_buffer_size(&self) -> Result<usize> {
+        h5lock!({
+            let size = H5Pget_buffer(self.id(), ptr::null_mut(), ptr::null_mut());
+            ensure!(size > 0, "failed to get type conversion buffer size");
+            Ok(size as _)
+        })
+    }
+
+    pub fn buffer_size(&self) -> usize {
+        self.get_buffer_size().unwrap_or(1024 * 1024)
+    }
+
+    #[cfg(h5_have_parallel)]
+    #[doc(hidden)]
+    pub fn get_mpio_transfer_mode(&self) -> Result<TransferMode> {
+        h5lock!({
+            let mut mode = H5FD_mpio_xfer_t::H5FD_MPIO_INDEPENDENT;
+            h5try!(H5Pget_dxpl_mpio(self.id(), &mut mode));
+            Ok(mode.into())
+        })
+    }
+
+    #[cfg(h5_have_parallel)]
+    pub fn mpio_transfer_mode(&self) -> TransferMode {
+        self.get_mpio_transfer_mode().unwrap_or_else(|_| TransferMode::default())
+    }
+}
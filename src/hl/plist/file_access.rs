@@ -46,6 +46,14 @@ use hdf5_sys::h5p::{
 use hdf5_sys::h5p::{H5Pget_fapl_direct, H5Pset_fapl_direct};
 #[cfg(feature = "mpio")]
 use hdf5_sys::h5p::{H5Pget_fapl_mpio, H5Pset_fapl_mpio};
+#[cfg(feature = "ros3")]
+use hdf5_sys::{
+    h5fd::{
+        H5FD_ros3_fapl_t, H5FD_CURR_ROS3_FAPL_T_VERSION, H5FD_ROS3_MAX_REGION_LEN,
+        H5FD_ROS3_MAX_SECRET_ID_LEN, H5FD_ROS3_MAX_SECRET_KEY_LEN,
+    },
+    h5p::{H5Pget_fapl_ros3, H5Pset_fapl_ros3},
+};
 
 #[cfg(hdf5_1_10_1)]
 use hdf5_sys::h5ac::{H5AC_cache_image_config_t, H5AC__CACHE_IMAGE__ENTRY_AGEOUT__NONE};
@@ -65,6 +73,8 @@ use hdf5_sys::h5p::{
     H5Pget_evict_on_close, H5Pget_mdc_image_config, H5Pget_page_buffer_size, H5Pset_evict_on_close,
     H5Pset_mdc_image_config, H5Pset_page_buffer_size,
 };
+#[cfg(hdf5_1_10_7)]
+use hdf5_sys::h5p::{H5Pget_file_locking, H5Pset_file_locking};
 #[cfg(hdf5_1_10_2)]
 use hdf5_sys::h5p::{H5Pget_libver_bounds, H5Pset_libver_bounds};
 #[cfg(hdf5_1_10_0)]
@@ -77,6 +87,8 @@ use hdf5_sys::h5p::{
 use crate::globals::H5FD_DIRECT;
 #[cfg(feature = "mpio")]
 use crate::globals::H5FD_MPIO;
+#[cfg(feature = "ros3")]
+use crate::globals::H5FD_ROS3;
 use crate::globals::{
     H5FD_CORE, H5FD_FAMILY, H5FD_LOG, H5FD_MULTI, H5FD_SEC2, H5FD_STDIO, H5P_FILE_ACCESS,
 };
@@ -118,6 +130,8 @@ impl Debug for FileAccess {
         formatter.field("small_data_block_size", &self.small_data_block_size());
         #[cfg(hdf5_1_10_2)]
         formatter.field("libver_bounds", &self.libver_bounds());
+        #[cfg(hdf5_1_10_7)]
+        formatter.field("file_locking", &self.file_locking());
         #[cfg(hdf5_1_8_7)]
         formatter.field("elink_file_cache_size", &self.elink_file_cache_size());
         formatter.field("meta_block_size", &self.meta_block_size());
@@ -452,6 +466,28 @@ mod mpio {
 #[cfg(feature = "mpio")]
 pub use self::mpio::*;
 
+/// Read-only S3 driver options; the file is accessed anonymously unless both the access
+/// key ID and the secret access key are provided.
+#[cfg(feature = "ros3")]
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Ros3Driver {
+    pub region: String,
+    pub access_key: Option<String>,
+    pub secret_key: Option<String>,
+}
+
+#[cfg(feature = "ros3")]
+impl Debug for Ros3Driver {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The secret access key is never printed.
+        f.debug_struct("Ros3Driver")
+            .field("region", &self.region)
+            .field("access_key", &self.access_key)
+            .field("secret_key", &self.secret_key.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
 #[cfg(h5_have_direct)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct DirectDriver {
@@ -480,6 +516,8 @@ pub enum FileDriver {
     Mpio(MpioDriver),
     #[cfg(h5_have_direct)]
     Direct(DirectDriver),
+    #[cfg(feature = "ros3")]
+    Ros3(Ros3Driver),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -558,6 +596,18 @@ impl Default for PageBufferSize {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FileLocking {
+    pub use_file_locking: bool,
+    pub ignore_when_disabled: bool,
+}
+
+impl Default for FileLocking {
+    fn default() -> Self {
+        Self { use_file_locking: true, ignore_when_disabled: false }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CacheIncreaseMode {
     Off,
@@ -978,6 +1028,7 @@ pub struct FileAccessBuilder {
     small_data_block_size: Option<u64>,
     #[cfg(hdf5_1_10_2)]
     libver_bounds: Option<LibVerBounds>,
+    file_locking: Option<FileLocking>,
 }
 
 impl FileAccessBuilder {
@@ -1003,6 +1054,11 @@ impl FileAccessBuilder {
             let v = plist.get_libver_bounds()?;
             builder.libver_bounds(v.low, v.high);
         }
+        #[cfg(hdf5_1_10_7)]
+        {
+            let v = plist.get_file_locking()?;
+            builder.file_locking(v.use_file_locking, v.ignore_when_disabled);
+        }
         #[cfg(hdf5_1_8_7)]
         {
             builder.elink_file_cache_size(plist.get_elink_file_cache_size()?);
@@ -1134,12 +1190,30 @@ impl FileAccessBuilder {
         self
     }
 
+    /// Sets the range of library versions whose object formats may be used when writing.
+    ///
+    /// Capping `high` makes the file readable by older versions of the library; features
+    /// that require newer formats then fail. SWMR writing requires `low` to be the latest
+    /// version; `low` must not exceed `high`, and `high` can't be `Earliest`.
     #[cfg(hdf5_1_10_2)]
     pub fn libver_bounds(&mut self, low: LibraryVersion, high: LibraryVersion) -> &mut Self {
         self.libver_bounds = Some(LibVerBounds { low, high });
         self
     }
 
+    /// Sets whether file locks are used when opening files, and whether they should be
+    /// silently skipped on file systems where locking is disabled (e.g. some NFS or Lustre
+    /// mounts).
+    ///
+    /// This requires HDF5 1.10.7 or later, creating the property list fails otherwise; on
+    /// older versions, the `HDF5_USE_FILE_LOCKING=FALSE` environment variable can be used.
+    pub fn file_locking(
+        &mut self, use_file_locking: bool, ignore_when_disabled: bool,
+    ) -> &mut Self {
+        self.file_locking = Some(FileLocking { use_file_locking, ignore_when_disabled });
+        self
+    }
+
     pub fn driver(&mut self, file_driver: &FileDriver) -> &mut Self {
         self.file_driver = Some(file_driver.clone());
         self
@@ -1240,6 +1314,24 @@ impl FileAccessBuilder {
         self.driver(&FileDriver::Direct(DirectDriver::default()))
     }
 
+    /// Uses the read-only S3 driver with the given AWS region and (optional) credentials.
+    #[cfg(feature = "ros3")]
+    pub fn ros3_options(
+        &mut self, region: &str, access_key: Option<&str>, secret_key: Option<&str>,
+    ) -> &mut Self {
+        self.driver(&FileDriver::Ros3(Ros3Driver {
+            region: region.into(),
+            access_key: access_key.map(Into::into),
+            secret_key: secret_key.map(Into::into),
+        }))
+    }
+
+    /// Uses the read-only S3 driver with anonymous access.
+    #[cfg(feature = "ros3")]
+    pub fn ros3(&mut self) -> &mut Self {
+        self.driver(&FileDriver::Ros3(Ros3Driver::default()))
+    }
+
     fn set_log(&self, id: hid_t) -> Result<()> {
         let opt = &self.log_options;
         let flags = opt.flags.bits() as _;
@@ -1343,6 +1435,40 @@ impl FileAccessBuilder {
         Ok(())
     }
 
+    #[cfg(feature = "ros3")]
+    fn set_ros3(id: hid_t, drv: &Ros3Driver) -> Result<()> {
+        let mut fa: H5FD_ros3_fapl_t = unsafe { mem::zeroed() };
+        fa.version = H5FD_CURR_ROS3_FAPL_T_VERSION;
+        ensure!(
+            drv.region.len() <= H5FD_ROS3_MAX_REGION_LEN,
+            "AWS region name is too long: {:?}",
+            drv.region
+        );
+        string_to_fixed_bytes(&drv.region, &mut fa.aws_region[..H5FD_ROS3_MAX_REGION_LEN]);
+        match (&drv.access_key, &drv.secret_key) {
+            (Some(access_key), Some(secret_key)) => {
+                ensure!(
+                    access_key.len() <= H5FD_ROS3_MAX_SECRET_ID_LEN,
+                    "AWS access key ID is too long"
+                );
+                ensure!(
+                    secret_key.len() <= H5FD_ROS3_MAX_SECRET_KEY_LEN,
+                    "AWS secret access key is too long"
+                );
+                fa.authenticate = 1;
+                string_to_fixed_bytes(access_key, &mut fa.secret_id[..H5FD_ROS3_MAX_SECRET_ID_LEN]);
+                string_to_fixed_bytes(
+                    secret_key,
+                    &mut fa.secret_key[..H5FD_ROS3_MAX_SECRET_KEY_LEN],
+                );
+            }
+            (None, None) => {}
+            _ => fail!("Both AWS access key ID and secret access key are required, or neither"),
+        }
+        h5try!(H5Pset_fapl_ros3(id, &mut fa));
+        Ok(())
+    }
+
     fn set_driver(&self, id: hid_t, drv: &FileDriver) -> Result<()> {
         match drv {
             FileDriver::Sec2 => {
@@ -1374,6 +1500,10 @@ impl FileAccessBuilder {
             FileDriver::Direct(drv) => {
                 Self::set_direct(id, drv)?;
             }
+            #[cfg(feature = "ros3")]
+            FileDriver::Ros3(drv) => {
+                Self::set_ros3(id, drv)?;
+            }
         }
         Ok(())
     }
@@ -1400,9 +1530,27 @@ impl FileAccessBuilder {
         #[cfg(hdf5_1_10_2)]
         {
             if let Some(v) = self.libver_bounds {
+                ensure!(
+                    v.low <= v.high,
+                    "Library version low bound ({:?}) must not exceed the high bound ({:?})",
+                    v.low,
+                    v.high
+                );
+                ensure!(!v.high.is_earliest(), "Library version high bound can't be `Earliest`");
                 h5try!(H5Pset_libver_bounds(id, v.low.into(), v.high.into()));
             }
         }
+        if let Some(v) = self.file_locking {
+            #[cfg(hdf5_1_10_7)]
+            h5try!(H5Pset_file_locking(id, v.use_file_locking as _, v.ignore_when_disabled as _));
+            #[cfg(not(hdf5_1_10_7))]
+            fail!(
+                "Configuring file locking requires HDF5 1.10.7 or later (use_file_locking: {}, \
+                 ignore_when_disabled: {}); set HDF5_USE_FILE_LOCKING=FALSE instead",
+                v.use_file_locking,
+                v.ignore_when_disabled
+            );
+        }
         #[cfg(hdf5_1_8_7)]
         {
             if let Some(v) = self.elink_file_cache_size {
@@ -1568,6 +1716,23 @@ impl FileAccess {
         Ok(DirectDriver { alignment: res.0 as _, block_size: res.1 as _, cbuf_size: res.2 as _ })
     }
 
+    #[doc(hidden)]
+    #[cfg(feature = "ros3")]
+    fn get_ros3(&self) -> Result<Ros3Driver> {
+        let mut fa: H5FD_ros3_fapl_t = unsafe { mem::zeroed() };
+        h5try!(H5Pget_fapl_ros3(self.id(), &mut fa));
+        let region = string_from_fixed_bytes(&fa.aws_region, H5FD_ROS3_MAX_REGION_LEN);
+        let (access_key, secret_key) = if fa.authenticate > 0 {
+            (
+                Some(string_from_fixed_bytes(&fa.secret_id, H5FD_ROS3_MAX_SECRET_ID_LEN)),
+                Some(string_from_fixed_bytes(&fa.secret_key, H5FD_ROS3_MAX_SECRET_KEY_LEN)),
+            )
+        } else {
+            (None, None)
+        };
+        Ok(Ros3Driver { region, access_key, secret_key })
+    }
+
     #[doc(hidden)]
     pub fn get_driver(&self) -> Result<FileDriver> {
         let drv_id = h5try!(H5Pget_driver(self.id()));
@@ -1583,6 +1748,12 @@ impl FileAccess {
                 return self.get_direct().map(FileDriver::Direct);
             }
         }
+        #[cfg(feature = "ros3")]
+        {
+            if drv_id == *H5FD_ROS3 {
+                return self.get_ros3().map(FileDriver::Ros3);
+            }
+        }
         if drv_id == *H5FD_SEC2 {
             Ok(FileDriver::Sec2)
         } else if drv_id == *H5FD_STDIO {
@@ -1820,4 +1991,20 @@ impl FileAccess {
     pub fn libver_bounds(&self) -> LibVerBounds {
         self.get_libver_bounds().ok().unwrap_or_else(LibVerBounds::default)
     }
+
+    #[cfg(hdf5_1_10_7)]
+    #[doc(hidden)]
+    pub fn get_file_locking(&self) -> Result<FileLocking> {
+        h5get!(H5Pget_file_locking(self.id()): hbool_t, hbool_t).map(
+            |(use_file_locking, ignore_when_disabled)| FileLocking {
+                use_file_locking: use_file_locking > 0,
+                ignore_when_disabled: ignore_when_disabled > 0,
+            },
+        )
+    }
+
+    #[cfg(hdf5_1_10_7)]
+    pub fn file_locking(&self) -> FileLocking {
+        self.get_file_locking().unwrap_or_else(|_| FileLocking::default())
+    }
 }
@@ -329,6 +329,11 @@ impl FileCreateBuilder {
 
     fn populate_plist(&self, id: hid_t) -> Result<()> {
         if let Some(v) = self.userblock {
+            ensure!(
+                v == 0 || (v >= 512 && v.is_power_of_two()),
+                "invalid userblock size: {} (expected 0 or a power of 2 not less than 512)",
+                v
+            );
             h5try!(H5Pset_userblock(id, v as _));
         }
         if let Some(v) = self.sym_k {
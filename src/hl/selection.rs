@@ -0,0 +1,253 @@
+use ndarray::Array2;
+
+use crate::internal_prelude::*;
+
+/// A regular hyperslab: for each dimension, `count` blocks of size `block` placed
+/// `stride` elements apart, the first one starting at `start`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Hyperslab {
+    pub start: Vec<Ix>,
+    pub stride: Vec<Ix>,
+    pub count: Vec<Ix>,
+    pub block: Vec<Ix>,
+}
+
+impl Hyperslab {
+    /// Creates a contiguous hyperslab of a given shape starting at a given offset.
+    pub fn new<D: Dimension>(start: D, shape: D) -> Self {
+        let ndim = start.ndim();
+        Self {
+            start: start.dims(),
+            stride: vec![1; ndim],
+            count: shape.dims(),
+            block: vec![1; ndim],
+        }
+    }
+
+    /// Sets the stride (distance between the starts of consecutive blocks) for each dimension.
+    pub fn stride<D: Dimension>(mut self, stride: D) -> Self {
+        self.stride = stride.dims();
+        self
+    }
+
+    /// Sets the count (number of blocks) for each dimension.
+    pub fn count<D: Dimension>(mut self, count: D) -> Self {
+        self.count = count.dims();
+        self
+    }
+
+    /// Sets the block size for each dimension.
+    pub fn block<D: Dimension>(mut self, block: D) -> Self {
+        self.block = block.dims();
+        self
+    }
+
+    /// Returns the rank of the hyperslab.
+    pub fn ndim(&self) -> usize {
+        self.start.len()
+    }
+
+    /// Returns the total number of elements in the hyperslab.
+    pub fn size(&self) -> usize {
+        self.count.iter().zip(&self.block).map(|(c, b)| c * b).product()
+    }
+
+    /// Checks that the hyperslab is consistent and fits into the given shape.
+    pub fn validate(&self, shape: &[Ix]) -> Result<()> {
+        let ndim = self.ndim();
+        ensure!(
+            self.stride.len() == ndim && self.count.len() == ndim && self.block.len() == ndim,
+            "hyperslab start/stride/count/block must have the same length"
+        );
+        ensure!(
+            ndim == shape.len(),
+            "hyperslab rank mismatch: dataspace has {} dims, hyperslab has {} dims",
+            shape.len(),
+            ndim
+        );
+        for i in 0..ndim {
+            let (start, stride, count, block) =
+                (self.start[i], self.stride[i], self.count[i], self.block[i]);
+            if count == 0 || block == 0 {
+                continue;
+            }
+            ensure!(stride >= 1, "hyperslab stride must be >= 1 (got {})", stride);
+            ensure!(
+                count == 1 || stride >= block,
+                "hyperslab blocks overlap: stride {} < block {}",
+                stride,
+                block
+            );
+            let end = start + (count - 1) * stride + block;
+            ensure!(
+                end <= shape[i],
+                "hyperslab extends beyond dataspace bounds along axis {}: {} > {}",
+                i,
+                end,
+                shape[i]
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Selection of elements within a dataspace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Selection {
+    /// All elements.
+    All,
+    /// No elements.
+    None,
+    /// Union of one or more regular hyperslabs.
+    Hyperslabs(Vec<Hyperslab>),
+    /// Individual elements given by their coordinates, an array of shape `[n_points, ndim]`.
+    Points(Array2<Ix>),
+}
+
+impl Default for Selection {
+    fn default() -> Self {
+        Selection::All
+    }
+}
+
+impl From<Hyperslab> for Selection {
+    fn from(hyperslab: Hyperslab) -> Self {
+        Selection::Hyperslabs(vec![hyperslab])
+    }
+}
+
+impl From<Vec<Hyperslab>> for Selection {
+    fn from(hyperslabs: Vec<Hyperslab>) -> Self {
+        Selection::Hyperslabs(hyperslabs)
+    }
+}
+
+impl From<Array2<Ix>> for Selection {
+    fn from(points: Array2<Ix>) -> Self {
+        Selection::Points(points)
+    }
+}
+
+impl Selection {
+    /// Checks that the selection fits into the given shape.
+    pub fn validate(&self, shape: &[Ix]) -> Result<()> {
+        match *self {
+            Selection::Hyperslabs(ref hyperslabs) => {
+                for hyperslab in hyperslabs {
+                    hyperslab.validate(shape)?;
+                }
+            }
+            Selection::Points(ref points) if points.nrows() > 0 => {
+                ensure!(
+                    points.ncols() == shape.len(),
+                    "point selection rank mismatch: dataspace has {} dims, points have {} dims",
+                    shape.len(),
+                    points.ncols()
+                );
+                for point in points.outer_iter() {
+                    for (i, (&x, &n)) in point.iter().zip(shape).enumerate() {
+                        ensure!(
+                            x < n,
+                            "point {:?} is out of dataspace bounds along axis {}: {} >= {}",
+                            point.to_vec(),
+                            i,
+                            x,
+                            n
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Returns the number of elements this selection would select within a dataspace.
+    ///
+    /// The selection is validated against the dataspace; the dataspace itself is unchanged.
+    pub fn npoints(&self, space: &Dataspace) -> Result<usize> {
+        let space = space.copy();
+        space.select(self)?;
+        Ok(space.selection_size())
+    }
+
+    /// Returns the bounding box of this selection within a dataspace, see
+    /// `Dataspace::selection_bounds()`.
+    pub fn bounds(&self, space: &Dataspace) -> Result<(Vec<Ix>, Vec<Ix>)> {
+        let space = space.copy();
+        space.select(self)?;
+        space.selection_bounds()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use ndarray::{arr2, Array2};
+
+    use crate::internal_prelude::*;
+
+    #[test]
+    pub fn test_hyperslab() {
+        let h = Hyperslab::new((1, 2), (3, 4));
+        assert_eq!(h.start, vec![1, 2]);
+        assert_eq!(h.stride, vec![1, 1]);
+        assert_eq!(h.count, vec![3, 4]);
+        assert_eq!(h.block, vec![1, 1]);
+        assert_eq!(h.ndim(), 2);
+        assert_eq!(h.size(), 12);
+        h.validate(&[4, 6]).unwrap();
+        assert_err!(h.validate(&[4, 5]), "beyond dataspace bounds along axis 1: 6 > 5");
+        assert_err!(h.validate(&[4]), "hyperslab rank mismatch");
+
+        let h = Hyperslab::new(1, 3).stride(4).block(2);
+        assert_eq!(h.size(), 6);
+        h.validate(&[11]).unwrap();
+        assert_err!(h.validate(&[10]), "beyond dataspace bounds");
+        assert_err!(Hyperslab::new(0, 2).stride(1).block(2).validate(&[10]), "blocks overlap");
+    }
+
+    #[test]
+    pub fn test_selection() {
+        assert_eq!(Selection::default(), Selection::All);
+        let h = Hyperslab::new(0, 2);
+        assert_eq!(Selection::from(h.clone()), Selection::Hyperslabs(vec![h.clone()]));
+        assert_err!(Selection::from(vec![h.clone(), Hyperslab::new(5, 2)]).validate(&[6]), "7 > 6");
+        Selection::None.validate(&[1]).unwrap();
+
+        let points: Array2<Ix> = arr2(&[[0, 1], [3, 2], [0, 0]]);
+        assert_eq!(Selection::from(points.clone()), Selection::Points(points.clone()));
+        Selection::from(points.clone()).validate(&[4, 3]).unwrap();
+        assert_err!(
+            Selection::from(points.clone()).validate(&[4, 2]),
+            "point [3, 2] is out of dataspace bounds along axis 1: 2 >= 2"
+        );
+        assert_err!(
+            Selection::from(points).validate(&[4, 3, 1]),
+            "point selection rank mismatch: dataspace has 3 dims, points have 2 dims"
+        );
+        Selection::Points(Array2::zeros((0, 0))).validate(&[4, 3]).unwrap();
+    }
+
+    #[test]
+    pub fn test_npoints_bounds() {
+        let space = Dataspace::try_new((10, 20), false).unwrap();
+        let h = Hyperslab::new((1, 2), (3, 4)).stride((3, 5)).block((2, 1));
+        let sel = Selection::from(h.clone());
+        assert_eq!(sel.npoints(&space).unwrap(), (3 * 2) * (4 * 1));
+        assert_eq!(sel.npoints(&space).unwrap(), h.size());
+        assert_eq!(sel.bounds(&space).unwrap(), (vec![1, 2], vec![1 + 2 * 3 + 1, 2 + 3 * 5]));
+        assert_eq!(space.selection_size(), 200);
+
+        assert_eq!(Selection::All.npoints(&space).unwrap(), 200);
+        assert_eq!(Selection::None.npoints(&space).unwrap(), 0);
+        let points: Array2<Ix> = arr2(&[[0, 1], [3, 2]]);
+        assert_eq!(
+            Selection::from(points.clone()).bounds(&space).unwrap(),
+            (vec![0, 1], vec![3, 2])
+        );
+        assert_err!(
+            Selection::from(Hyperslab::new((9, 0), (2, 1))).npoints(&space),
+            "beyond dataspace bounds"
+        );
+    }
+}
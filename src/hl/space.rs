@@ -3,12 +3,18 @@ use std::fmt::{self, Debug};
 use std::ops::Deref;
 use std::ptr;
 
-use ndarray::SliceOrIndex;
+use ndarray::{Array2, SliceOrIndex};
 
 use hdf5_sys::h5s::{
-    H5Scopy, H5Screate_simple, H5Sget_simple_extent_dims, H5Sget_simple_extent_ndims,
-    H5Sselect_hyperslab, H5S_SELECT_SET,
+    H5S_sel_type, H5S_seloper_t, H5Scopy, H5Screate_simple, H5Sget_select_bounds,
+    H5Sget_select_elem_npoints, H5Sget_select_elem_pointlist, H5Sget_select_hyper_blocklist,
+    H5Sget_select_hyper_nblocks, H5Sget_select_npoints, H5Sget_select_type,
+    H5Sget_simple_extent_dims, H5Sget_simple_extent_ndims, H5Sselect_all, H5Sselect_elements,
+    H5Sselect_hyperslab, H5Sselect_none, H5S_SELECT_AND, H5S_SELECT_NOTA, H5S_SELECT_NOTB,
+    H5S_SELECT_OR, H5S_SELECT_SET, H5S_SELECT_XOR,
 };
+#[cfg(hdf5_1_10_0)]
+use hdf5_sys::h5s::{H5Sget_regular_hyperslab, H5Sis_regular_hyperslab};
 
 use crate::internal_prelude::*;
 
@@ -112,6 +118,183 @@ impl Dataspace {
         }
     }
 
+    /// Applies the selection to the dataspace, replacing any previous selection.
+    pub fn select(&self, selection: &Selection) -> Result<()> {
+        selection.validate(&self.dims())?;
+        h5lock!({
+            match *selection {
+                Selection::All => {
+                    h5try!(H5Sselect_all(self.id()));
+                }
+                Selection::None => {
+                    h5try!(H5Sselect_none(self.id()));
+                }
+                Selection::Hyperslabs(ref hyperslabs) => {
+                    h5try!(H5Sselect_none(self.id()));
+                    for (i, h) in hyperslabs.iter().enumerate() {
+                        let op = if i == 0 { H5S_SELECT_SET } else { H5S_SELECT_OR };
+                        self.select_hyperslab_op(h, op)?;
+                    }
+                }
+                Selection::Points(ref points) => {
+                    if points.nrows() == 0 {
+                        h5try!(H5Sselect_none(self.id()));
+                    } else {
+                        let coords: Vec<hsize_t> = points.iter().map(|&x| x as _).collect();
+                        h5try!(H5Sselect_elements(
+                            self.id(),
+                            H5S_SELECT_SET,
+                            points.nrows() as _,
+                            coords.as_ptr()
+                        ));
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn select_hyperslab_op(&self, h: &Hyperslab, op: H5S_seloper_t) -> Result<()> {
+        let to_hsize = |v: &[Ix]| v.iter().map(|&x| x as hsize_t).collect::<Vec<_>>();
+        let (start, stride) = (to_hsize(&h.start), to_hsize(&h.stride));
+        let (count, block) = (to_hsize(&h.count), to_hsize(&h.block));
+        h5call!(H5Sselect_hyperslab(
+            self.id(),
+            op,
+            start.as_ptr(),
+            stride.as_ptr(),
+            count.as_ptr(),
+            block.as_ptr()
+        ))
+        .and(Ok(()))
+    }
+
+    fn combine_hyperslab(&self, hyperslab: &Hyperslab, op: H5S_seloper_t) -> Result<()> {
+        hyperslab.validate(&self.dims())?;
+        self.select_hyperslab_op(hyperslab, op)
+    }
+
+    /// Adds a hyperslab to the current selection (union).
+    pub fn select_or(&self, hyperslab: &Hyperslab) -> Result<()> {
+        self.combine_hyperslab(hyperslab, H5S_SELECT_OR)
+    }
+
+    /// Intersects the current selection with a hyperslab.
+    pub fn select_and(&self, hyperslab: &Hyperslab) -> Result<()> {
+        self.combine_hyperslab(hyperslab, H5S_SELECT_AND)
+    }
+
+    /// Selects elements that are either in the current selection or in a hyperslab, but not
+    /// in both.
+    pub fn select_xor(&self, hyperslab: &Hyperslab) -> Result<()> {
+        self.combine_hyperslab(hyperslab, H5S_SELECT_XOR)
+    }
+
+    /// Removes the elements of a hyperslab from the current selection.
+    pub fn select_notb(&self, hyperslab: &Hyperslab) -> Result<()> {
+        self.combine_hyperslab(hyperslab, H5S_SELECT_NOTB)
+    }
+
+    /// Selects the elements of a hyperslab that are not in the current selection.
+    pub fn select_nota(&self, hyperslab: &Hyperslab) -> Result<()> {
+        self.combine_hyperslab(hyperslab, H5S_SELECT_NOTA)
+    }
+
+    /// Returns the current selection of the dataspace.
+    ///
+    /// Regular hyperslabs are returned as is (HDF5 1.10+); irregular hyperslab selections
+    /// are decomposed into a union of contiguous blocks.
+    pub fn get_selection(&self) -> Result<Selection> {
+        h5lock!({
+            match H5Sget_select_type(self.id()) {
+                H5S_sel_type::H5S_SEL_ALL => Ok(Selection::All),
+                H5S_sel_type::H5S_SEL_NONE => Ok(Selection::None),
+                H5S_sel_type::H5S_SEL_HYPERSLABS => self.get_hyperslabs().map(Selection::from),
+                H5S_sel_type::H5S_SEL_POINTS => self.get_points().map(Selection::Points),
+                _ => fail!("invalid selection type"),
+            }
+        })
+    }
+
+    #[cfg(hdf5_1_10_0)]
+    fn get_regular_hyperslab(&self) -> Result<Option<Hyperslab>> {
+        h5lock!({
+            if h5try!(H5Sis_regular_hyperslab(self.id())) <= 0 {
+                return Ok(None);
+            }
+            let ndim = self.ndim();
+            let (mut start, mut stride) = (vec![0; ndim], vec![0; ndim]);
+            let (mut count, mut block) = (vec![0; ndim], vec![0; ndim]);
+            h5try!(H5Sget_regular_hyperslab(
+                self.id(),
+                start.as_mut_ptr(),
+                stride.as_mut_ptr(),
+                count.as_mut_ptr(),
+                block.as_mut_ptr()
+            ));
+            let to_ix = |v: Vec<hsize_t>| -> Vec<Ix> { v.into_iter().map(|x| x as _).collect() };
+            Ok(Some(Hyperslab {
+                start: to_ix(start),
+                stride: to_ix(stride),
+                count: to_ix(count),
+                block: to_ix(block),
+            }))
+        })
+    }
+
+    #[cfg(not(hdf5_1_10_0))]
+    fn get_regular_hyperslab(&self) -> Result<Option<Hyperslab>> {
+        Ok(None)
+    }
+
+    fn get_hyperslabs(&self) -> Result<Vec<Hyperslab>> {
+        if let Some(hyperslab) = self.get_regular_hyperslab()? {
+            return Ok(vec![hyperslab]);
+        }
+        h5lock!({
+            let ndim = self.ndim();
+            let nblocks = h5try!(H5Sget_select_hyper_nblocks(self.id())) as usize;
+            let mut buf: Vec<hsize_t> = vec![0; 2 * ndim * nblocks];
+            h5try!(H5Sget_select_hyper_blocklist(self.id(), 0, nblocks as _, buf.as_mut_ptr()));
+            Ok(buf
+                .chunks(2 * ndim)
+                .map(|b| Hyperslab {
+                    start: b[..ndim].iter().map(|&x| x as Ix).collect(),
+                    stride: vec![1; ndim],
+                    count: vec![1; ndim],
+                    block: (0..ndim).map(|i| (b[ndim + i] - b[i] + 1) as Ix).collect(),
+                })
+                .collect())
+        })
+    }
+
+    fn get_points(&self) -> Result<Array2<Ix>> {
+        h5lock!({
+            let ndim = self.ndim();
+            let npoints = h5try!(H5Sget_select_elem_npoints(self.id())) as usize;
+            let mut buf: Vec<hsize_t> = vec![0; ndim * npoints];
+            h5try!(H5Sget_select_elem_pointlist(self.id(), 0, npoints as _, buf.as_mut_ptr()));
+            let coords = buf.into_iter().map(|x| x as Ix).collect();
+            Ok(Array2::from_shape_vec((npoints, ndim), coords)?)
+        })
+    }
+
+    /// Returns the number of elements in the current selection of the dataspace.
+    pub fn selection_size(&self) -> usize {
+        h5call!(H5Sget_select_npoints(self.id())).unwrap_or(0) as _
+    }
+
+    /// Returns the corners of the bounding box of the current selection: the coordinates
+    /// of the first and of the last (inclusive) selected element along each axis.
+    pub fn selection_bounds(&self) -> Result<(Vec<Ix>, Vec<Ix>)> {
+        ensure!(self.selection_size() > 0, "Cannot get the bounds of an empty selection");
+        let ndim = self.ndim();
+        let (mut start, mut end) = (vec![0; ndim], vec![0; ndim]);
+        h5call!(H5Sget_select_bounds(self.id(), start.as_mut_ptr(), end.as_mut_ptr()))?;
+        let to_ix = |v: Vec<hsize_t>| -> Vec<Ix> { v.into_iter().map(|x| x as _).collect() };
+        Ok((to_ix(start), to_ix(end)))
+    }
+
     pub fn try_new<D: Dimension>(d: D, resizable: bool) -> Result<Self> {
         let rank = d.ndim();
         let mut dims: Vec<hsize_t> = vec![];
@@ -168,6 +351,8 @@ impl Dimension for Dataspace {
 
 #[cfg(test)]
 pub mod tests {
+    use ndarray::{arr2, Array2};
+
     use crate::internal_prelude::*;
 
     #[test]
@@ -226,4 +411,85 @@ pub mod tests {
         );
         assert_eq!(Dataspace::try_new((5, 6), true).unwrap().resizable(), true);
     }
+
+    #[test]
+    pub fn test_selection() {
+        let _e = silence_errors();
+        let d = Dataspace::try_new((5, 6), false).unwrap();
+        assert_eq!(d.get_selection().unwrap(), Selection::All);
+        assert_eq!(d.selection_size(), 30);
+
+        d.select(&Selection::None).unwrap();
+        assert_eq!(d.get_selection().unwrap(), Selection::None);
+        assert_eq!(d.selection_size(), 0);
+
+        let h = Hyperslab::new((1, 0), (2, 3)).stride((2, 2));
+        d.select(&h.clone().into()).unwrap();
+        assert_eq!(d.selection_size(), 6);
+        #[cfg(hdf5_1_10_0)]
+        assert_eq!(d.get_selection().unwrap(), h.clone().into());
+
+        let blocks = vec![Hyperslab::new((0, 0), (1, 2)), Hyperslab::new((3, 4), (2, 2))];
+        d.select(&blocks.clone().into()).unwrap();
+        assert_eq!(d.selection_size(), 6);
+        let sel = d.get_selection().unwrap();
+        d.select(&sel).unwrap();
+        assert_eq!(d.selection_size(), 6);
+
+        assert_err!(d.select(&Hyperslab::new((4, 0), (2, 1)).into()), "beyond dataspace bounds");
+
+        let points: Array2<Ix> = arr2(&[[4, 5], [0, 0], [2, 3]]);
+        d.select(&points.clone().into()).unwrap();
+        assert_eq!(d.selection_size(), 3);
+        assert_eq!(d.get_selection().unwrap(), Selection::Points(points));
+        assert_err!(d.select(&Selection::Points(arr2(&[[5, 0]]))), "out of dataspace bounds");
+
+        d.select(&Selection::All).unwrap();
+        assert_eq!(d.selection_size(), 30);
+        assert_eq!(d.selection_bounds().unwrap(), (vec![0, 0], vec![4, 5]));
+        d.select(&Selection::None).unwrap();
+        assert_err!(d.selection_bounds(), "empty selection");
+    }
+
+    #[test]
+    pub fn test_select_ops() {
+        let _e = silence_errors();
+        let d = Dataspace::try_new((5, 6), false).unwrap();
+        let (a, b) = (Hyperslab::new((0, 0), (2, 3)), Hyperslab::new((1, 1), (2, 3)));
+
+        d.select(&a.clone().into()).unwrap();
+        d.select_or(&b).unwrap();
+        assert_eq!(d.selection_size(), 10);
+        d.select(&a.clone().into()).unwrap();
+        d.select_and(&b).unwrap();
+        assert_eq!(d.selection_size(), 2);
+        d.select(&a.clone().into()).unwrap();
+        d.select_xor(&b).unwrap();
+        assert_eq!(d.selection_size(), 8);
+        d.select(&a.clone().into()).unwrap();
+        d.select_notb(&b).unwrap();
+        assert_eq!(d.selection_size(), 4);
+        d.select(&a.clone().into()).unwrap();
+        d.select_nota(&b).unwrap();
+        assert_eq!(d.selection_size(), 4);
+        assert_err!(d.select_or(&Hyperslab::new((4, 0), (2, 1))), "beyond dataspace bounds");
+
+        with_tmp_file(|file| {
+            let data: Vec<u32> = (0..30).collect();
+            let ds = file.new_dataset::<u32>().create("data", (5, 6)).unwrap();
+            ds.write_raw(&data).unwrap();
+
+            let d = ds.space().unwrap();
+            let (a, b) = (Hyperslab::new((0, 0), (1, 2)), Hyperslab::new((3, 4), (2, 2)));
+            d.select(&a.clone().into()).unwrap();
+            d.select_or(&b).unwrap();
+            let union = d.get_selection().unwrap();
+            let values = ds.read_selection::<u32, _>(union.clone()).unwrap();
+            assert_eq!(values.len(), a.size() + b.size());
+            assert_eq!(values.to_vec(), vec![0, 1, 22, 23, 28, 29]);
+
+            ds.write_selection(&[0_u32; 6], union).unwrap();
+            assert_eq!(ds.read_raw::<u32>().unwrap().iter().filter(|&&x| x == 0).count(), 6);
+        })
+    }
 }
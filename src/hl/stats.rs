@@ -0,0 +1,249 @@
+//! Summary statistics of numeric datasets computed without reading them whole.
+
+use crate::internal_prelude::*;
+
+/// Approximate maximum number of elements read at once when not iterating over chunks.
+const BLOCK_SIZE: usize = 1 << 20;
+
+/// Numeric element types supported by `Dataset::stats()` and `Dataset::reduce()`.
+pub trait Numeric: H5Type + Copy + PartialOrd {
+    /// Returns `true` if the value is NaN (never the case for integers).
+    fn is_nan(self) -> bool {
+        false
+    }
+
+    /// Converts the value to `f64` for summation.
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_numeric {
+    ($($ty:ty),*) => {
+        $(impl Numeric for $ty {
+            fn to_f64(self) -> f64 {
+                self as _
+            }
+        })*
+    };
+}
+
+impl_numeric!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl Numeric for f32 {
+    fn is_nan(self) -> bool {
+        self.is_nan()
+    }
+
+    fn to_f64(self) -> f64 {
+        self.into()
+    }
+}
+
+impl Numeric for f64 {
+    fn is_nan(self) -> bool {
+        self.is_nan()
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
+/// Summary statistics of a numeric dataset, see `Dataset::stats()`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats<T> {
+    /// Minimum of the non-NaN values (`None` if there are none).
+    pub min: Option<T>,
+    /// Maximum of the non-NaN values (`None` if there are none).
+    pub max: Option<T>,
+    /// Sum of the non-NaN values, accumulated as `f64`.
+    pub sum: f64,
+    /// Number of non-NaN values.
+    pub count: usize,
+    /// Number of NaN values.
+    pub nan_count: usize,
+}
+
+/// A single reduction computed by `Dataset::reduce()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReduceOp {
+    Min,
+    Max,
+    Sum,
+    NanCount,
+}
+
+/// Result of `Dataset::reduce()`, corresponding to the requested `ReduceOp`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReduceResult<T> {
+    /// Minimum of the non-NaN values (`None` if there are none).
+    Min(Option<T>),
+    /// Maximum of the non-NaN values (`None` if there are none).
+    Max(Option<T>),
+    /// Sum of the non-NaN values, accumulated as `f64`.
+    Sum(f64),
+    /// Number of NaN values.
+    NanCount(usize),
+}
+
+impl<T> Default for Stats<T> {
+    fn default() -> Self {
+        Self { min: None, max: None, sum: 0., count: 0, nan_count: 0 }
+    }
+}
+
+impl<T: Numeric> Stats<T> {
+    fn update(&mut self, value: T) {
+        self.update_repeated(value, 1);
+    }
+
+    fn update_repeated(&mut self, value: T, n: usize) {
+        if value.is_nan() {
+            self.nan_count += n;
+            return;
+        }
+        if self.min.map_or(true, |min| value < min) {
+            self.min = Some(value);
+        }
+        if self.max.map_or(true, |max| value > max) {
+            self.max = Some(value);
+        }
+        self.sum += value.to_f64() * n as f64;
+        self.count += n;
+    }
+}
+
+impl Dataset {
+    /// Computes the minimum, maximum, sum and NaN count of the dataset elements, converted
+    /// to `T` when read.
+    ///
+    /// Chunked datasets are read chunk by chunk via `iter_chunks()` (requires HDF5 1.10.5),
+    /// with chunks that haven't been allocated counted as the fill value (if it's defined).
+    /// Other datasets are read in blocks of about a million elements along the first axis
+    /// (consisting of whole chunks for chunked datasets with older HDF5 versions), so only
+    /// a part of the dataset is kept in memory at a time.
+    pub fn stats<T: Numeric>(&self) -> Result<Stats<T>> {
+        let mut stats = Stats::default();
+        let shape = self.shape();
+        if shape.is_empty() {
+            stats.update(self.read_scalar::<T>()?);
+            return Ok(stats);
+        }
+        let row_size: usize = shape[1..].iter().product();
+        if shape[0] == 0 || row_size == 0 {
+            return Ok(stats);
+        }
+        #[cfg(hdf5_1_10_5)]
+        {
+            if self.is_chunked() {
+                let mut n_read = 0;
+                for chunk in self.iter_chunks::<T>()? {
+                    let (_, data) = chunk?;
+                    n_read += data.len();
+                    data.iter().for_each(|&value| stats.update(value));
+                }
+                let n_missing = shape[0] * row_size - n_read;
+                if n_missing > 0 {
+                    if let Some(fill_value) = self.fill_value::<T>()? {
+                        stats.update_repeated(fill_value, n_missing);
+                    }
+                }
+                return Ok(stats);
+            }
+        }
+        let max_rows = (BLOCK_SIZE / row_size).max(1);
+        let block_rows = match self.chunks() {
+            Some(chunk) => (max_rows / chunk[0]).max(1) * chunk[0],
+            None => max_rows,
+        };
+        let mut start = 0;
+        while start < shape[0] {
+            let rows = block_rows.min(shape[0] - start);
+            let mut offset = vec![0; shape.len()];
+            offset[0] = start;
+            let mut count = shape.clone();
+            count[0] = rows;
+            for &value in &self.read_selection::<T, _>(Hyperslab::new(offset, count))? {
+                stats.update(value);
+            }
+            start += rows;
+        }
+        Ok(stats)
+    }
+
+    /// Computes a single reduction of the dataset elements, converted to `T` when read.
+    ///
+    /// This reads the dataset the same way as `stats()`, which computes all reductions in
+    /// a single pass and should be preferred when more than one of them is needed.
+    pub fn reduce<T: Numeric>(&self, op: ReduceOp) -> Result<ReduceResult<T>> {
+        let stats = self.stats::<T>()?;
+        Ok(match op {
+            ReduceOp::Min => ReduceResult::Min(stats.min),
+            ReduceOp::Max => ReduceResult::Max(stats.max),
+            ReduceOp::Sum => ReduceResult::Sum(stats.sum),
+            ReduceOp::NanCount => ReduceResult::NanCount(stats.nan_count),
+        })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use ndarray::{s, Array2};
+
+    use super::{ReduceOp, ReduceResult, Stats};
+    use crate::internal_prelude::*;
+
+    #[test]
+    pub fn test_stats() {
+        with_tmp_file(|file| {
+            let mut data = Array2::from_shape_fn((25, 7), |(i, j)| (i as f64 - 10.) * (j as f64));
+            data[(3, 4)] = std::f64::NAN;
+            data[(24, 6)] = std::f64::NAN;
+            let ds = file.new_dataset::<f64>().chunk((4, 3)).create("floats", (25, 7)).unwrap();
+            ds.write(&data).unwrap();
+
+            let values = ds.read_raw::<f64>().unwrap();
+            let valid: Vec<f64> = values.iter().cloned().filter(|x| !x.is_nan()).collect();
+            let stats = ds.stats::<f64>().unwrap();
+            assert_eq!(stats.nan_count, 2);
+            assert_eq!(stats.count, valid.len());
+            assert_eq!(stats.min, Some(-60.));
+            assert_eq!(stats.max, Some(78.));
+            assert_eq!(stats.sum, valid.iter().sum::<f64>());
+            assert_eq!(ds.reduce::<f64>(ReduceOp::Min).unwrap(), ReduceResult::Min(Some(-60.)));
+            assert_eq!(ds.reduce::<f64>(ReduceOp::Max).unwrap(), ReduceResult::Max(Some(78.)));
+            assert_eq!(ds.reduce::<f64>(ReduceOp::Sum).unwrap(), ReduceResult::Sum(stats.sum));
+            assert_eq!(ds.reduce::<f64>(ReduceOp::NanCount).unwrap(), ReduceResult::NanCount(2));
+
+            // Chunks that have never been written contain the fill value.
+            let ds = file
+                .new_dataset::<f32>()
+                .chunk((1, 8))
+                .fill_value(0.5)
+                .create("sparse", (100, 32))
+                .unwrap();
+            ds.write_slice(&Array2::from_elem((2, 32), -1.5_f32), &s![40..42, ..]).unwrap();
+            let values = ds.read_raw::<f32>().unwrap();
+            let stats = ds.stats::<f32>().unwrap();
+            assert_eq!((stats.min, stats.max), (Some(-1.5), Some(0.5)));
+            assert_eq!((stats.count, stats.nan_count), (values.len(), 0));
+            assert_eq!(stats.sum, values.iter().map(|&x| f64::from(x)).sum::<f64>());
+            assert_eq!(stats.sum, 1472.);
+
+            let ds = file.new_dataset::<i16>().create("ints", 1000).unwrap();
+            let ints: Vec<i16> = (0..1000).map(|x| (x % 37) as i16 - 18).collect();
+            ds.write(&ints).unwrap();
+            let stats = ds.stats::<i16>().unwrap();
+            assert_eq!((stats.min, stats.max), (Some(-18), Some(18)));
+            assert_eq!(stats.sum, ints.iter().map(|&x| f64::from(x)).sum::<f64>());
+            assert_eq!((stats.count, stats.nan_count), (1000, 0));
+            // Elements are converted when read.
+            assert_eq!(ds.stats::<f32>().unwrap().min, Some(-18.));
+
+            let ds = file.new_dataset::<u8>().create("empty", (0, 3)).unwrap();
+            assert_eq!(ds.stats::<u8>().unwrap(), Stats::default());
+            let ds = file.new_dataset::<u32>().create("scalar", ()).unwrap();
+            ds.write_scalar(&5).unwrap();
+            assert_eq!(ds.stats::<u32>().unwrap().max, Some(5));
+        })
+    }
+}
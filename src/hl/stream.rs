@@ -0,0 +1,177 @@
+//! Byte stream access to one-dimensional `u8` datasets.
+
+use std::fmt::{self, Debug};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+use ndarray::s;
+
+use hdf5_types::{IntSize, TypeDescriptor};
+
+use crate::internal_prelude::*;
+
+/// Reads a one-dimensional `u8` dataset as a byte stream, see `Dataset::as_byte_reader()`.
+pub struct ByteReader {
+    ds: Dataset,
+    pos: u64,
+}
+
+/// Writes a one-dimensional `u8` dataset as a byte stream, see `Dataset::as_byte_writer()`.
+pub struct ByteWriter {
+    ds: Dataset,
+    pos: u64,
+}
+
+impl Debug for ByteReader {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ByteReader").field("dataset", &self.ds).field("pos", &self.pos).finish()
+    }
+}
+
+impl Debug for ByteWriter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ByteWriter").field("dataset", &self.ds).field("pos", &self.pos).finish()
+    }
+}
+
+impl Dataset {
+    /// Creates a reader implementing `io::Read` and `io::Seek` over the bytes of this
+    /// dataset, starting at the beginning; the dataset must be a one-dimensional `u8` array.
+    pub fn as_byte_reader(&self) -> Result<ByteReader> {
+        ensure_byte_stream(self)?;
+        Ok(ByteReader { ds: self.clone(), pos: 0 })
+    }
+
+    /// Creates a writer implementing `io::Write` and `io::Seek` over the bytes of this
+    /// dataset, starting at the beginning; the dataset must be a one-dimensional `u8` array.
+    ///
+    /// Resizable datasets are extended when writing past the end (any gap left by seeking
+    /// beyond the end is filled with the fill value); otherwise, writes are truncated.
+    pub fn as_byte_writer(&self) -> Result<ByteWriter> {
+        ensure_byte_stream(self)?;
+        Ok(ByteWriter { ds: self.clone(), pos: 0 })
+    }
+}
+
+fn ensure_byte_stream(ds: &Dataset) -> Result<()> {
+    let is_u8 = ds.dtype()?.to_descriptor().ok() == Some(TypeDescriptor::Unsigned(IntSize::U1));
+    ensure!(ds.ndim() == 1 && is_u8, "Byte streams require a one-dimensional u8 dataset");
+    Ok(())
+}
+
+fn to_io_error(err: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, err)
+}
+
+fn seek_to(pos: u64, len: u64, from: SeekFrom) -> io::Result<u64> {
+    let (base, offset) = match from {
+        SeekFrom::Start(offset) => return Ok(offset),
+        SeekFrom::End(offset) => (len, offset),
+        SeekFrom::Current(offset) => (pos, offset),
+    };
+    let new_pos = base as i64 + offset;
+    if new_pos < 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "Cannot seek before the start"));
+    }
+    Ok(new_pos as _)
+}
+
+impl Read for ByteReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.ds.size() as u64;
+        let n = len.saturating_sub(self.pos).min(buf.len() as u64) as usize;
+        if n == 0 {
+            return Ok(0);
+        }
+        let start = self.pos as usize;
+        let data = self.ds.read_slice_1d::<u8, _>(&s![start..start + n]).map_err(to_io_error)?;
+        buf[..n].copy_from_slice(&data.into_raw_vec());
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl Seek for ByteReader {
+    fn seek(&mut self, from: SeekFrom) -> io::Result<u64> {
+        self.pos = seek_to(self.pos, self.ds.size() as _, from)?;
+        Ok(self.pos)
+    }
+}
+
+impl Write for ByteWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let start = self.pos as usize;
+        let end = start + buf.len();
+        if end > self.ds.size() && self.ds.is_resizable() {
+            self.ds.resize(end).map_err(to_io_error)?;
+        }
+        let n = self.ds.size().saturating_sub(start).min(buf.len());
+        if n == 0 {
+            return Ok(0);
+        }
+        self.ds.write_slice(&buf[..n], &s![start..start + n]).map_err(to_io_error)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for ByteWriter {
+    fn seek(&mut self, from: SeekFrom) -> io::Result<u64> {
+        self.pos = seek_to(self.pos, self.ds.size() as _, from)?;
+        Ok(self.pos)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::io::{Read, Seek, SeekFrom, Write};
+
+    use crate::internal_prelude::*;
+
+    #[test]
+    pub fn test_byte_stream() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<u8>().chunk(4).resizable(true).create("stream", 0).unwrap();
+            let mut writer = ds.as_byte_writer().unwrap();
+            writer.write_all(b"hello, ").unwrap();
+            writer.write_all(b"world").unwrap();
+            assert_eq!(ds.size(), 12);
+            assert_eq!(writer.seek(SeekFrom::Start(7)).unwrap(), 7);
+            writer.write_all(b"W").unwrap();
+            assert_eq!(writer.seek(SeekFrom::End(0)).unwrap(), 12);
+            writer.write_all(b"!").unwrap();
+            writer.flush().unwrap();
+            assert_eq!(ds.read_raw::<u8>().unwrap(), b"hello, World!".to_vec());
+
+            let mut reader = ds.as_byte_reader().unwrap();
+            let mut buf = [0; 5];
+            reader.read_exact(&mut buf).unwrap();
+            assert_eq!(&buf, b"hello");
+            assert_eq!(reader.seek(SeekFrom::Current(2)).unwrap(), 7);
+            let mut rest = Vec::new();
+            reader.read_to_end(&mut rest).unwrap();
+            assert_eq!(rest, b"World!".to_vec());
+            assert_eq!(reader.read(&mut buf).unwrap(), 0);
+            assert_eq!(reader.seek(SeekFrom::End(-6)).unwrap(), 7);
+            reader.read_exact(&mut buf).unwrap();
+            assert_eq!(&buf, b"World");
+            assert!(reader.seek(SeekFrom::Current(-100)).is_err());
+
+            // Fixed-size datasets are not extended.
+            let ds = file.new_dataset::<u8>().create("fixed", 4).unwrap();
+            let mut writer = ds.as_byte_writer().unwrap();
+            assert_eq!(writer.write(b"abcdef").unwrap(), 4);
+            assert_eq!(writer.write(b"gh").unwrap(), 0);
+            assert!(writer.write_all(b"gh").is_err());
+            assert_eq!(ds.read_raw::<u8>().unwrap(), b"abcd".to_vec());
+
+            let ds = file.new_dataset::<u16>().create("wide", 4).unwrap();
+            assert_err!(ds.as_byte_reader(), "Byte streams require a one-dimensional u8 dataset");
+            let ds = file.new_dataset::<u8>().create("matrix", (2, 2)).unwrap();
+            assert_err!(ds.as_byte_writer(), "Byte streams require a one-dimensional u8 dataset");
+        })
+    }
+}
@@ -0,0 +1,341 @@
+//! Snapshots of whole group hierarchies for inspection and debugging.
+
+use std::collections::BTreeMap;
+
+use ndarray::{ArrayD, IxDyn};
+#[cfg(feature = "serde")]
+use ndarray::{ArrayViewD, Axis};
+
+use hdf5_sys::{h5a::H5Aread, h5d::H5Dread, h5o::H5Oopen};
+use hdf5_types::{TypeDescriptor, VarLenUnicode};
+
+use crate::internal_prelude::*;
+
+/// Default maximum depth of the group hierarchy in `Group::to_value()`.
+pub const DEFAULT_MAX_DEPTH: usize = 32;
+
+/// Default maximum number of elements read from a dataset or attribute in `Group::to_value()`.
+pub const DEFAULT_MAX_SIZE: usize = 1024;
+
+/// Options controlling how group hierarchies are read via `Group::to_value_with()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TreeOptions {
+    max_depth: usize,
+    max_size: usize,
+}
+
+impl Default for TreeOptions {
+    fn default() -> Self {
+        Self { max_depth: DEFAULT_MAX_DEPTH, max_size: DEFAULT_MAX_SIZE }
+    }
+}
+
+impl TreeOptions {
+    /// Creates a new set of options with the default limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum nesting level of groups; deeper groups are skipped.
+    pub fn max_depth(&mut self, max_depth: usize) -> &mut Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets the maximum number of elements to read; larger datasets and attributes are skipped.
+    pub fn max_size(&mut self, max_size: usize) -> &mut Self {
+        self.max_size = max_size;
+        self
+    }
+
+    pub fn get_max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    pub fn get_max_size(&self) -> usize {
+        self.max_size
+    }
+}
+
+/// A single element of a dataset or an attribute.
+///
+/// With the `serde` feature, scalars are serialized as plain values.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(untagged))]
+pub enum Scalar {
+    Boolean(bool),
+    Integer(i64),
+    Unsigned(u64),
+    Float(f64),
+    String(String),
+}
+
+/// Contents of a dataset or an attribute.
+///
+/// With the `serde` feature, arrays are serialized as nested sequences.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Data {
+    /// Value of a scalar dataset/attribute.
+    Scalar(Scalar),
+    /// Values of a dataset/attribute with one or more dimensions.
+    Array(#[cfg_attr(feature = "serde", serde(serialize_with = "serialize_array"))] ArrayD<Scalar>),
+    /// Data that hasn't been read, along with the reason (e.g. size limit or datatype).
+    Skipped(String),
+}
+
+/// Contents of a group: its attributes and members.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GroupValue {
+    pub attrs: BTreeMap<String, Data>,
+    pub members: BTreeMap<String, Node>,
+}
+
+/// A member of a group.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Node {
+    Group(GroupValue),
+    Dataset {
+        data: Data,
+        attrs: BTreeMap<String, Data>,
+    },
+    /// A member that hasn't been read, along with the reason (e.g. depth limit or link type).
+    Skipped(String),
+}
+
+#[cfg(feature = "serde")]
+fn serialize_array<S: serde::Serializer>(
+    arr: &ArrayD<Scalar>, serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    use serde::Serialize;
+    NestedArray(arr.view()).serialize(serializer)
+}
+
+/// Serializes an array as nested sequences along its axes (e.g. a list of rows).
+#[cfg(feature = "serde")]
+struct NestedArray<'a>(ArrayViewD<'a, Scalar>);
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for NestedArray<'a> {
+    fn serialize<S: serde::Serializer>(
+        &self, serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+        let mut seq = serializer.serialize_seq(Some(self.0.len_of(Axis(0))))?;
+        for sub in self.0.outer_iter() {
+            if sub.ndim() == 0 {
+                for value in sub.iter() {
+                    seq.serialize_element(value)?;
+                }
+            } else {
+                seq.serialize_element(&NestedArray(sub))?;
+            }
+        }
+        seq.end()
+    }
+}
+
+impl Group {
+    /// Reads the whole hierarchy under this group (following hard links only) along with
+    /// all attributes, using the default depth and size limits.
+    ///
+    /// Only numeric, boolean and string data is supported; this is meant for inspecting
+    /// small hierarchies (e.g. configuration), not for transferring data.
+    pub fn to_value(&self) -> Result<GroupValue> {
+        self.to_value_with(&TreeOptions::default())
+    }
+
+    /// Reads the whole hierarchy under this group, see `to_value()`.
+    pub fn to_value_with(&self, options: &TreeOptions) -> Result<GroupValue> {
+        read_group(self, options, 0)
+    }
+}
+
+fn read_group(group: &Group, options: &TreeOptions, depth: usize) -> Result<GroupValue> {
+    let mut value = GroupValue { attrs: read_attrs(group, options)?, members: BTreeMap::new() };
+    for name in group.member_names()? {
+        let node = read_member(group, &name, options, depth + 1)?;
+        value.members.insert(name, node);
+    }
+    Ok(value)
+}
+
+fn read_member(group: &Group, name: &str, options: &TreeOptions, depth: usize) -> Result<Node> {
+    match group.link_type(name)? {
+        LinkType::Hard => {}
+        LinkType::Soft => return Ok(Node::Skipped("soft link".into())),
+        LinkType::External => return Ok(Node::Skipped("external link".into())),
+    }
+    let c_name = to_cstring(name)?;
+    let loc =
+        h5lock!(Location::from_id(h5try!(H5Oopen(group.id(), c_name.as_ptr(), H5P_DEFAULT))))?;
+    // The object is only opened once, its handle is cast once its type is known.
+    Ok(match loc.id_type() {
+        H5I_GROUP if depth > options.max_depth => Node::Skipped("maximum depth exceeded".into()),
+        H5I_GROUP => Node::Group(read_group(&unsafe { loc.cast::<Group>() }, options, depth)?),
+        H5I_DATASET => {
+            let ds = unsafe { loc.cast::<Dataset>() };
+            Node::Dataset { data: read_data(&ds, options)?, attrs: read_attrs(&ds, options)? }
+        }
+        H5I_DATATYPE => Node::Skipped("named datatype".into()),
+        _ => Node::Skipped("unknown object type".into()),
+    })
+}
+
+fn read_attrs(loc: &Location, options: &TreeOptions) -> Result<BTreeMap<String, Data>> {
+    let mut attrs = BTreeMap::new();
+    for name in loc.attr_names()? {
+        let data = read_data(&loc.attr(&name)?, options)?;
+        attrs.insert(name, data);
+    }
+    Ok(attrs)
+}
+
+fn read_data(obj: &Container, options: &TreeOptions) -> Result<Data> {
+    let size = obj.size();
+    if size > options.max_size {
+        return Ok(Data::Skipped(format!("too large ({} elements)", size)));
+    }
+    let values: Vec<Scalar> = match obj.dtype()?.to_descriptor() {
+        Ok(TypeDescriptor::Boolean) => {
+            obj.read_raw::<bool>()?.into_iter().map(Scalar::Boolean).collect()
+        }
+        Ok(TypeDescriptor::Integer(_)) => {
+            obj.read_raw::<i64>()?.into_iter().map(Scalar::Integer).collect()
+        }
+        Ok(TypeDescriptor::Unsigned(_)) => {
+            obj.read_raw::<u64>()?.into_iter().map(Scalar::Unsigned).collect()
+        }
+        Ok(TypeDescriptor::Float(_)) => {
+            obj.read_raw::<f64>()?.into_iter().map(Scalar::Float).collect()
+        }
+        Ok(TypeDescriptor::VarLenAscii) | Ok(TypeDescriptor::VarLenUnicode) => obj
+            .read_raw::<VarLenUnicode>()?
+            .into_iter()
+            .map(|s| Scalar::String(s.as_str().into()))
+            .collect(),
+        Ok(TypeDescriptor::FixedAscii(len)) | Ok(TypeDescriptor::FixedUnicode(len)) => {
+            read_fixed_strings(obj, len)?.into_iter().map(Scalar::String).collect()
+        }
+        _ => return Ok(Data::Skipped("unsupported datatype".into())),
+    };
+    if obj.is_scalar() {
+        Ok(values.into_iter().next().map_or_else(|| Data::Skipped("empty".into()), Data::Scalar))
+    } else {
+        Ok(Data::Array(ArrayD::from_shape_vec(IxDyn(&obj.shape()), values)?))
+    }
+}
+
+fn read_fixed_strings(obj: &Container, len: usize) -> Result<Vec<String>> {
+    // Strings are read in their stored datatype since the library can't convert them
+    // into variable-length strings.
+    let dtype = obj.dtype()?;
+    let mut buf: Vec<c_char> = vec![0; obj.size() * len];
+    if obj.is_attr() {
+        h5call!(H5Aread(obj.id(), dtype.id(), buf.as_mut_ptr() as *mut _))?;
+    } else {
+        h5call!(H5Dread(
+            obj.id(),
+            dtype.id(),
+            H5S_ALL,
+            H5S_ALL,
+            H5P_DEFAULT,
+            buf.as_mut_ptr() as *mut _
+        ))?;
+    }
+    Ok(buf.chunks(len.max(1)).map(|s| string_from_fixed_bytes(s, len).trim_end().into()).collect())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::collections::BTreeMap;
+
+    use ndarray::{arr1, arr2};
+
+    use hdf5_types::{FixedAscii, VarLenUnicode};
+
+    use super::{Data, GroupValue, Node, Scalar, TreeOptions};
+    use crate::internal_prelude::*;
+
+    #[test]
+    pub fn test_to_value() {
+        with_tmp_file(|file| {
+            file.set_attr("version", &3_u16).unwrap();
+            let config = file.create_group("config").unwrap();
+            config.write_scalar("rate", &0.5_f64).unwrap();
+            config.write_scalar("enabled", &true).unwrap();
+            let name: VarLenUnicode = "main".parse().unwrap();
+            config.write_scalar("name", &name).unwrap();
+            let tag = FixedAscii::<[u8; 8]>::from_ascii(b"abc").unwrap();
+            config.write_scalar("tag", &tag).unwrap();
+            let ds = config.new_dataset::<i32>().create("matrix", (2, 2)).unwrap();
+            ds.write(&arr2(&[[1, -2], [3, -4]])).unwrap();
+            ds.set_attr("unit", &name).unwrap();
+            config.new_dataset::<u8>().create("big", 100).unwrap();
+            config.create_group("nested").unwrap().create_group("deep").unwrap();
+            file.link_soft("/config", "alias").unwrap();
+
+            let mut options = TreeOptions::new();
+            options.max_depth(2).max_size(10);
+            let value = file.to_value_with(&options).unwrap();
+
+            let mut expected = GroupValue::default();
+            expected.attrs.insert("version".into(), Data::Scalar(Scalar::Unsigned(3)));
+            let mut group = GroupValue::default();
+            let scalar = |s| Node::Dataset { data: Data::Scalar(s), attrs: BTreeMap::new() };
+            group.members.insert("rate".into(), scalar(Scalar::Float(0.5)));
+            group.members.insert("enabled".into(), scalar(Scalar::Boolean(true)));
+            group.members.insert("name".into(), scalar(Scalar::String("main".into())));
+            group.members.insert("tag".into(), scalar(Scalar::String("abc".into())));
+            let matrix = arr2(&[[1, -2], [3, -4]]).mapv(Scalar::Integer).into_dyn();
+            let mut attrs = BTreeMap::new();
+            attrs.insert("unit".into(), Data::Scalar(Scalar::String("main".into())));
+            group
+                .members
+                .insert("matrix".into(), Node::Dataset { data: Data::Array(matrix), attrs });
+            let big = Data::Skipped("too large (100 elements)".into());
+            group.members.insert("big".into(), Node::Dataset { data: big, attrs: BTreeMap::new() });
+            let mut nested = GroupValue::default();
+            nested.members.insert("deep".into(), Node::Skipped("maximum depth exceeded".into()));
+            group.members.insert("nested".into(), Node::Group(nested));
+            expected.members.insert("config".into(), Node::Group(group));
+            expected.members.insert("alias".into(), Node::Skipped("soft link".into()));
+            assert_eq!(value, expected);
+
+            let value = file.group("config").unwrap().to_value().unwrap();
+            match value.members["big"] {
+                Node::Dataset { data: Data::Array(ref arr), .. } => {
+                    assert_eq!(arr, &arr1(&[0; 100]).mapv(Scalar::Unsigned).into_dyn())
+                }
+                ref node => panic!("unexpected node: {:?}", node),
+            }
+        })
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    pub fn test_to_value_serialize() {
+        with_tmp_file(|file| {
+            file.set_attr("version", &3_u16).unwrap();
+            let group = file.create_group("data").unwrap();
+            let ds = group.new_dataset::<i32>().create("matrix", (2, 2)).unwrap();
+            ds.write(&arr2(&[[1, -2], [3, -4]])).unwrap();
+            group.write_scalar("rate", &0.5_f64).unwrap();
+            file.link_soft("/data", "alias").unwrap();
+
+            let json = serde_json::to_string(&file.to_value().unwrap()).unwrap();
+            assert_eq!(
+                json,
+                concat!(
+                    r#"{"attrs":{"version":{"Scalar":3}},"members":{"#,
+                    r#""alias":{"Skipped":"soft link"},"#,
+                    r#""data":{"Group":{"attrs":{},"members":{"#,
+                    r#""matrix":{"Dataset":{"data":{"Array":[[1,-2],[3,-4]]},"attrs":{}}},"#,
+                    r#""rate":{"Dataset":{"data":{"Scalar":0.5},"attrs":{}}}}}}}}"#
+                )
+            );
+        })
+    }
+}
@@ -0,0 +1,101 @@
+//! Datasets with an element type checked once when opened.
+
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+
+use ndarray::{Array, ArrayView, SliceInfo, SliceOrIndex};
+
+use crate::internal_prelude::*;
+
+/// Dataset whose stored datatype is known to match `T`, see `Dataset::typed()`.
+///
+/// Reading and writing don't require specifying the element type at each call site.
+pub struct TypedDataset<T> {
+    ds: Dataset,
+    phantom: PhantomData<T>,
+}
+
+impl<T> Debug for TypedDataset<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TypedDataset").field("dataset", &self.ds).finish()
+    }
+}
+
+impl<T> Clone for TypedDataset<T> {
+    fn clone(&self) -> Self {
+        Self { ds: self.ds.clone(), phantom: PhantomData }
+    }
+}
+
+impl Dataset {
+    /// Returns a handle for reading and writing elements of type `T`.
+    ///
+    /// Fails if the type descriptor of the stored datatype is not the same as the one of `T`
+    /// (so that no conversion is ever needed, unlike with untyped reads and writes).
+    pub fn typed<T: H5Type>(&self) -> Result<TypedDataset<T>> {
+        let expected = T::type_descriptor();
+        let actual = self.dtype()?.to_descriptor()?;
+        ensure!(actual == expected, "Type mismatch: expected {:?}, got {:?}", expected, actual);
+        Ok(TypedDataset { ds: self.clone(), phantom: PhantomData })
+    }
+}
+
+impl<T: H5Type> TypedDataset<T> {
+    /// Reads the dataset into an n-dimensional array, see `Container::read()`.
+    pub fn read_array<D: ndarray::Dimension>(&self) -> Result<Array<T, D>> {
+        self.ds.read()
+    }
+
+    /// Reads a slice of the dataset, see `Container::read_slice()`.
+    pub fn read_slice<S, D>(&self, slice: &SliceInfo<S, D>) -> Result<Array<T, D>>
+    where
+        S: AsRef<[SliceOrIndex]>,
+        D: ndarray::Dimension,
+    {
+        self.ds.read_slice(slice)
+    }
+
+    /// Writes an n-dimensional array view into the dataset, see `Container::write()`.
+    pub fn write<'b, A, D>(&self, arr: A) -> Result<()>
+    where
+        A: Into<ArrayView<'b, T, D>>,
+        D: ndarray::Dimension,
+    {
+        self.ds.write(arr)
+    }
+
+    /// Returns the underlying dataset.
+    pub fn dataset(&self) -> &Dataset {
+        &self.ds
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use ndarray::{arr2, s, Array2};
+
+    use hdf5_types::VarLenUnicode;
+
+    use crate::internal_prelude::*;
+
+    #[test]
+    pub fn test_typed() {
+        with_tmp_file(|file| {
+            let ds = file.new_dataset::<f32>().create("floats", (2, 3)).unwrap();
+            let floats = ds.typed::<f32>().unwrap();
+            floats.write(&arr2(&[[1., 2., 3.], [4., 5., 6.]])).unwrap();
+            let arr: Array2<f32> = floats.read_array().unwrap();
+            assert_eq!(arr, arr2(&[[1., 2., 3.], [4., 5., 6.]]));
+            assert_eq!(floats.read_slice(&s![.., 1]).unwrap().to_vec(), vec![2., 5.]);
+            assert_eq!(floats.dataset().shape(), vec![2, 3]);
+
+            assert_err!(ds.typed::<f64>(), "Type mismatch: expected Float(U8), got Float(U4)");
+            assert_err!(ds.typed::<i32>(), "Type mismatch: expected Integer(U4), got Float(U4)");
+
+            let ds = file.new_dataset::<VarLenUnicode>().create("strings", 2).unwrap();
+            let strings = ds.typed::<VarLenUnicode>().unwrap();
+            strings.write(&["a".parse().unwrap(), "b".parse().unwrap()]).unwrap();
+            assert_eq!(strings.read_array::<ndarray::Ix1>().unwrap()[1].as_str(), "b");
+        })
+    }
+}
@@ -0,0 +1,190 @@
+//! Integrity checks of whole files.
+
+use std::collections::HashSet;
+
+use hdf5_sys::{
+    h5d::{H5Dread, H5Dvlen_reclaim},
+    h5o::H5Oopen,
+};
+
+use crate::internal_prelude::*;
+
+/// Problems found while validating a file, see `File::validate()`.
+#[derive(Clone, Debug, Default)]
+pub struct ValidationReport {
+    errors: Vec<(String, Error)>,
+}
+
+impl ValidationReport {
+    /// Returns `true` if no problems have been found.
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns paths of the broken objects along with the errors encountered.
+    pub fn errors(&self) -> &[(String, Error)] {
+        &self.errors
+    }
+
+    fn push(&mut self, path: &str, err: Error) {
+        self.errors.push((path.into(), err));
+    }
+}
+
+impl File {
+    /// Walks all objects reachable from the root group via hard links and checks that they
+    /// can be opened, along with the dataspace and datatype of each dataset.
+    ///
+    /// If `check_data` is set, one element of each chunk (or of each non-chunked dataset)
+    /// is also read, which decodes the whole chunk and thus detects filter and checksum
+    /// errors. Problems are collected into the report rather than returned as an error.
+    pub fn validate(&self, check_data: bool) -> Result<ValidationReport> {
+        let mut report = ValidationReport::default();
+        let mut visited = HashSet::new();
+        let root = self.group("/")?;
+        visited.insert(root.object_address()?);
+        check_group(&root, "/", check_data, &mut visited, &mut report);
+        Ok(report)
+    }
+}
+
+fn check_group(
+    group: &Group, path: &str, check_data: bool, visited: &mut HashSet<u64>,
+    report: &mut ValidationReport,
+) {
+    let names = match group.member_names() {
+        Ok(names) => names,
+        Err(err) => return report.push(path, err),
+    };
+    for name in names {
+        let member = if path.ends_with('/') {
+            format!("{}{}", path, name)
+        } else {
+            format!("{}/{}", path, name)
+        };
+        if let Err(err) = check_member(group, &name, &member, check_data, visited, report) {
+            report.push(&member, err);
+        }
+    }
+}
+
+fn check_member(
+    group: &Group, name: &str, path: &str, check_data: bool, visited: &mut HashSet<u64>,
+    report: &mut ValidationReport,
+) -> Result<()> {
+    // Soft and external links are not followed, dangling ones are not an error.
+    if group.link_type(name)? != LinkType::Hard {
+        return Ok(());
+    }
+    let c_name = to_cstring(name)?;
+    let loc =
+        h5lock!(Location::from_id(h5try!(H5Oopen(group.id(), c_name.as_ptr(), H5P_DEFAULT))))?;
+    if !visited.insert(loc.object_address()?) {
+        return Ok(());
+    }
+    match loc.id_type() {
+        H5I_GROUP => check_group(&group.group(name)?, path, check_data, visited, report),
+        H5I_DATASET => check_dataset(&group.dataset(name)?, check_data)?,
+        H5I_DATATYPE => {
+            group.datatype(name)?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn check_dataset(ds: &Dataset, check_data: bool) -> Result<()> {
+    let dtype = ds.dtype()?;
+    let shape = ds.space()?.dims();
+    if !check_data || shape.iter().any(|&s| s == 0) {
+        return Ok(());
+    }
+    let block = ds.chunks().unwrap_or_else(|| shape.clone());
+    let mut offset = vec![0; shape.len()];
+    loop {
+        read_element(ds, &dtype, &offset)?;
+
+        // Advance to the next chunk in row-major order.
+        let mut axis = shape.len();
+        loop {
+            if axis == 0 {
+                return Ok(());
+            }
+            axis -= 1;
+            offset[axis] += block[axis];
+            if offset[axis] < shape[axis] {
+                break;
+            }
+            offset[axis] = 0;
+        }
+    }
+}
+
+/// Reads a single element in its stored datatype, decoding the chunk containing it.
+pub(crate) fn read_element(ds: &Dataset, dtype: &Datatype, offset: &[Ix]) -> Result<()> {
+    let mut buf = vec![0_u8; dtype.size()];
+    let count = vec![1; offset.len()];
+    let fspace = ds.space()?;
+    if !offset.is_empty() {
+        fspace.select(&Hyperslab::new(offset.to_vec(), count.clone()).into())?;
+    }
+    let mspace = Dataspace::try_new(count, false)?;
+    h5lock!({
+        h5try!(H5Dread(
+            ds.id(),
+            dtype.id(),
+            mspace.id(),
+            fspace.id(),
+            H5P_DEFAULT,
+            buf.as_mut_ptr() as *mut _
+        ));
+        // Variable-length data allocated by the library while reading must be released.
+        h5try!(H5Dvlen_reclaim(dtype.id(), mspace.id(), H5P_DEFAULT, buf.as_mut_ptr() as *mut _));
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::fs;
+
+    use crate::internal_prelude::*;
+
+    #[test]
+    pub fn test_validate() {
+        with_tmp_path(|path| {
+            let pattern = [0xde_u8, 0xad, 0xbe, 0xef, 0x51, 0x0f, 0xca, 0x7e];
+            {
+                let file = File::create(&path).unwrap();
+                let group = file.create_group_all("a/b").unwrap();
+                group.new_dataset::<f32>().create("scalar", ()).unwrap();
+                group.new_dataset::<u8>().create("empty", 0).unwrap();
+                file.link_hard("a", "a/b/cycle").unwrap();
+                file.link_soft("/missing", "dangling").unwrap();
+                file.commit_type::<u32>("type").unwrap();
+                let ds = file
+                    .new_dataset::<u64>()
+                    .chunk(16)
+                    .fletcher32(true)
+                    .create("data", 64)
+                    .unwrap();
+                ds.write(&vec![u64::from_le_bytes(pattern); 64]).unwrap();
+                let report = file.validate(true).unwrap();
+                assert!(report.is_ok(), "{:?}", report);
+            }
+
+            // Corrupt the first chunk of data so that its checksum no longer matches.
+            let mut bytes = fs::read(&path).unwrap();
+            let pos = bytes.windows(pattern.len()).position(|w| w == pattern).unwrap();
+            bytes[pos] ^= 0xff;
+            fs::write(&path, bytes).unwrap();
+
+            let file = File::open(&path).unwrap();
+            assert!(file.validate(false).unwrap().is_ok());
+            let report = file.validate(true).unwrap();
+            assert!(!report.is_ok());
+            assert_eq!(report.errors().len(), 1);
+            assert_eq!(report.errors()[0].0, "/data");
+        })
+    }
+}
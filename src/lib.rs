@@ -16,15 +16,24 @@
 #[cfg(all(feature = "mpio", not(h5_have_parallel)))]
 compile_error!("Enabling \"mpio\" feature requires HDF5 library built with MPI support");
 
+#[cfg(all(feature = "ros3", not(h5_have_ros3_vfd)))]
+compile_error!("Enabling \"ros3\" feature requires HDF5 library built with ROS3 support");
+
 mod export {
     pub use crate::{
         class::from_id,
         dim::{Dimension, Ix},
-        error::{silence_errors, Error, Result},
-        filters::Filters,
+        error::{
+            reset_error_handler, set_error_handler, silence_errors, Error, ErrorFrame, ErrorStack,
+            Result,
+        },
+        filters::{gzip_available, szip_available, zstd_available, Filter, Filters, SzipCoding},
         hl::{
-            Container, Conversion, Dataset, DatasetBuilder, Dataspace, Datatype, File, FileBuilder,
-            Group, Location, Object, PropertyList, Reader, Writer,
+            AttrValue, Attribute, AttributeBuilder, ByteOrder, CharEncoding, Container, Conversion,
+            ConversionPolicy, CopyOptions, Dataset, DatasetBuilder, Dataspace, Datatype, File,
+            FileBuilder, FilteredGroup, Group, GroupBuilder, Hyperslab, IndexType, IterationOrder,
+            LinkType, Location, MemberIter, Object, ObjectToken, ObjectType, PropertyList, Reader,
+            Selection, StringPadding, TypeClass, ValidationReport, VisitAction, Writer,
         },
     };
 
@@ -36,18 +45,51 @@ mod export {
     }
 
     pub mod dataset {
-        pub use crate::hl::dataset::{Chunk, Dataset, DatasetBuilder};
+        #[cfg(feature = "io-stats")]
+        pub use crate::hl::dataset::IoStats;
+        pub use crate::hl::dataset::{
+            AllocTime, Chunk, Dataset, DatasetBuilder, FillTime, Layout, RepackOptions, RowIter,
+            SpaceStatus, COMPACT_MAX_SIZE,
+        };
+        #[cfg(hdf5_1_10_5)]
+        pub use crate::hl::dataset::{ChunkInfo, ChunkIter};
+        #[cfg(hdf5_1_10_0)]
+        pub use crate::hl::dataset::{ChunkOpts, VirtualMapping};
         pub use crate::hl::plist::dataset_access::*;
+        pub use crate::hl::plist::dataset_transfer::*;
     }
 
     pub mod file {
-        pub use crate::hl::file::{File, FileBuilder, OpenMode};
+        pub use crate::hl::file::{File, FileBuilder, FlushScope, OpenMode};
         pub use crate::hl::plist::file_access::*;
         pub use crate::hl::plist::file_create::*;
     }
 
+    pub mod packet {
+        pub use crate::hl::packet::{PacketTable, DEFAULT_CHUNK_SIZE};
+    }
+
+    pub mod stats {
+        pub use crate::hl::stats::{Numeric, ReduceOp, ReduceResult, Stats};
+    }
+
+    pub mod stream {
+        pub use crate::hl::stream::{ByteReader, ByteWriter};
+    }
+
+    pub mod tree {
+        pub use crate::hl::tree::{
+            Data, GroupValue, Node, Scalar, TreeOptions, DEFAULT_MAX_DEPTH, DEFAULT_MAX_SIZE,
+        };
+    }
+
+    pub mod typed {
+        pub use crate::hl::typed::TypedDataset;
+    }
+
     pub mod plist {
         pub use crate::hl::plist::dataset_access::DatasetAccess;
+        pub use crate::hl::plist::dataset_transfer::DatasetTransfer;
         pub use crate::hl::plist::file_access::FileAccess;
         pub use crate::hl::plist::file_create::FileCreate;
         pub use crate::hl::plist::{PropertyList, PropertyListClass};
@@ -55,6 +97,9 @@ mod export {
         pub mod dataset_access {
             pub use crate::hl::plist::dataset_access::*;
         }
+        pub mod dataset_transfer {
+            pub use crate::hl::plist::dataset_transfer::*;
+        }
         pub mod file_access {
             pub use crate::hl::plist::file_access::*;
         }
@@ -138,12 +183,158 @@ pub fn is_library_threadsafe() -> bool {
     }
 }
 
+/// Frees all unused memory cached in the internal free lists of the HDF5 library.
+///
+/// Note that the library does this automatically when it's being shut down; this is only
+/// useful for long-running processes that create and release lots of objects.
+pub fn garbage_collect() -> Result<()> {
+    use hdf5_sys::h5::H5garbage_collect;
+    h5call!(H5garbage_collect()).and(Ok(()))
+}
+
+/// Limits (in bytes) on the memory kept in the internal free lists of the HDF5 library.
+///
+/// Each kind of free list (regular, array and block) has a limit on the total size of all
+/// lists of this kind and a limit on the size of each single list; `None` means no limit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FreeListLimits {
+    pub regular_global: Option<usize>,
+    pub regular_list: Option<usize>,
+    pub array_global: Option<usize>,
+    pub array_list: Option<usize>,
+    pub block_global: Option<usize>,
+    pub block_list: Option<usize>,
+}
+
+/// Sets the limits on the memory kept in the internal free lists of the HDF5 library.
+///
+/// The limits apply to the whole process; free lists are garbage-collected when they
+/// grow beyond them.
+pub fn set_free_list_limits(limits: &FreeListLimits) -> Result<()> {
+    use self::internal_prelude::c_int;
+    use hdf5_sys::h5::H5set_free_list_limits;
+    let to_c = |limit: Option<usize>| -> Result<c_int> {
+        match limit {
+            None => Ok(-1),
+            Some(n) if n <= c_int::max_value() as usize => Ok(n as _),
+            Some(n) => fail!("Free list limit is too large: {}", n),
+        }
+    };
+    let (reg_global, reg_list) = (to_c(limits.regular_global)?, to_c(limits.regular_list)?);
+    let (arr_global, arr_list) = (to_c(limits.array_global)?, to_c(limits.array_list)?);
+    let (blk_global, blk_list) = (to_c(limits.block_global)?, to_c(limits.block_list)?);
+    h5call!(H5set_free_list_limits(
+        reg_global, reg_list, arr_global, arr_list, blk_global, blk_list
+    ))
+    .and(Ok(()))
+}
+
+/// Runs a closure while holding the global lock guarding all calls into the HDF5 library.
+///
+/// Each operation acquires the lock separately, which can be costly for lots of small
+/// operations (e.g. reading many scalar attributes) if other threads use the library too;
+/// inside a batch, the lock is only taken once. The lock is reentrant, so other operations
+/// of this crate (including silencing errors and nested batches) can be performed within
+/// the closure. Note that all other threads are blocked from using HDF5 until the batch
+/// completes.
+pub fn batch<T, F>(func: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    sync::sync(func)
+}
+
 #[cfg(test)]
 pub mod tests {
-    use crate::library_version;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::internal_prelude::*;
+    use crate::{batch, garbage_collect, library_version, set_free_list_limits, FreeListLimits};
 
     #[test]
     pub fn test_library_version() {
         assert!(library_version() >= (1, 8, 4));
     }
+
+    #[test]
+    pub fn test_batch() {
+        with_tmp_file(|file| {
+            for i in 0..1000_u32 {
+                file.set_attr(&format!("attr{}", i), &i).unwrap();
+            }
+            let read = || -> Vec<u32> {
+                (0..1000)
+                    .map(|i| file.attr(&format!("attr{}", i)).unwrap().read_scalar().unwrap())
+                    .collect()
+            };
+            let unbatched = read();
+            assert_eq!(unbatched, (0..1000).collect::<Vec<_>>());
+            assert_eq!(batch(read), unbatched);
+            assert_eq!(batch(|| batch(read)), unbatched);
+
+            // Other threads can't use the library while a batch is running.
+            let (tx, rx) = mpsc::channel();
+            batch(|| {
+                thread::spawn(move || tx.send(library_version()).unwrap());
+                thread::sleep(Duration::from_millis(100));
+                assert!(rx.try_recv().is_err());
+            });
+            assert!(rx.recv().unwrap() >= (1, 8, 4));
+        })
+    }
+
+    #[test]
+    pub fn test_batch_silence_errors() {
+        // Errors are silenced while holding the library lock inside a batch in one thread
+        // and outside of it in another one; this must not deadlock.
+        let (tx, rx) = mpsc::channel();
+        let batched = thread::spawn({
+            let tx = tx.clone();
+            move || {
+                with_tmp_file(|file| {
+                    for _ in 0..200 {
+                        batch(|| {
+                            assert!(!file.link_exists("missing"));
+                            assert!(!file.attr_exists("missing"));
+                        });
+                    }
+                });
+                tx.send(()).unwrap();
+            }
+        });
+        let silencing = thread::spawn(move || {
+            for _ in 0..200 {
+                let _e = silence_errors();
+                thread::yield_now();
+            }
+            tx.send(()).unwrap();
+        });
+        for _ in 0..2 {
+            rx.recv_timeout(Duration::from_secs(30)).expect("deadlock");
+        }
+        batched.join().unwrap();
+        silencing.join().unwrap();
+    }
+
+    #[test]
+    pub fn test_garbage_collect() {
+        for i in 0..50_u32 {
+            with_tmp_file(|file| {
+                let ds = file.new_dataset::<u32>().create("data", 100).unwrap();
+                ds.write_raw(&vec![i; 100]).unwrap();
+            })
+        }
+        garbage_collect().unwrap();
+
+        let limits = FreeListLimits { regular_global: Some(1 << 20), ..Default::default() };
+        set_free_list_limits(&limits).unwrap();
+        set_free_list_limits(&FreeListLimits::default()).unwrap();
+        if std::mem::size_of::<usize>() > 4 {
+            let limits =
+                FreeListLimits { block_list: Some((1_u64 << 40) as _), ..Default::default() };
+            assert_err!(set_free_list_limits(&limits), "Free list limit is too large");
+        }
+    }
 }
@@ -0,0 +1,53 @@
+#![cfg(feature = "complex")]
+
+use ndarray::Array2;
+use num_complex::Complex;
+
+use hdf5::H5Type;
+
+mod common;
+
+use self::common::util::new_in_memory_file;
+
+#[test]
+fn test_complex_roundtrip() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let arr = Array2::from_shape_fn((4, 3), |(i, j)| {
+        Complex::new(i as f64 / 3.0 - 1e-300, -(j as f64) * std::f64::consts::PI)
+    });
+    let ds = file.new_dataset::<Complex<f64>>().create("c128", arr.dim())?;
+    ds.write(&arr)?;
+    assert_eq!(ds.read_2d::<Complex<f64>>()?, arr);
+
+    let arr = arr.mapv(|c| Complex::new(c.re as f32, c.im as f32));
+    let ds = file.new_dataset::<Complex<f32>>().create("c64", arr.dim())?;
+    ds.write(&arr)?;
+    assert_eq!(ds.read_2d::<Complex<f32>>()?, arr);
+    Ok(())
+}
+
+#[test]
+fn test_complex_h5py_layout() -> hdf5::Result<()> {
+    // This is the compound type h5py uses for NumPy's complex128.
+    #[derive(H5Type, Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct H5pyComplex {
+        r: f64,
+        i: f64,
+    }
+
+    let file = new_in_memory_file()?;
+    let data = vec![H5pyComplex { r: 1.5, i: -2.0 }, H5pyComplex { r: 0.0, i: 1e10 }];
+    let ds = file.new_dataset::<H5pyComplex>().create("h5py", 2)?;
+    ds.write(&data)?;
+    assert_eq!(ds.dtype()?.to_descriptor()?, Complex::<f64>::type_descriptor());
+    assert_eq!(
+        ds.read_raw::<Complex<f64>>()?,
+        vec![Complex::new(1.5, -2.0), Complex::new(0.0, 1e10)]
+    );
+
+    let ds = file.new_dataset::<Complex<f64>>().create("native", 1)?;
+    ds.write(&[Complex::new(3.0, 4.0)])?;
+    assert_eq!(ds.read_raw::<H5pyComplex>()?, vec![H5pyComplex { r: 3.0, i: 4.0 }]);
+    Ok(())
+}
@@ -257,3 +257,521 @@ fn test_read_write_tuples() -> hdf5::Result<()> {
     test_read_write::<(i8, u64, f32)>()?;
     Ok(())
 }
+
+#[test]
+fn test_read_enum_invalid_value() -> hdf5::Result<()> {
+    use hdf5::H5Type;
+    use hdf5_sys::{h5d::H5Dwrite, h5p::H5P_DEFAULT, h5s::H5S_ALL};
+
+    #[derive(H5Type, Clone, Copy, Debug, PartialEq)]
+    #[repr(u8)]
+    enum Color {
+        Red = 0,
+        Green = 1,
+        Blue = 2,
+    }
+
+    #[derive(H5Type, Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct Pixel {
+        x: u16,
+        colors: [Color; 2],
+    }
+
+    let file = new_in_memory_file()?;
+    let colors = vec![Color::Blue, Color::Red, Color::Green, Color::Blue];
+    let ds = file.new_dataset::<Color>().create("colors", 4)?;
+    ds.write(&colors)?;
+    assert_eq!(ds.read_raw::<Color>()?, colors);
+
+    // Bypass the type checks in order to store a value that's not a valid variant.
+    let raw: [u8; 4] = [0, 5, 1, 2];
+    let dtype = ds.dtype()?;
+    let ret = unsafe {
+        H5Dwrite(ds.id(), dtype.id(), H5S_ALL, H5S_ALL, H5P_DEFAULT, raw.as_ptr() as *const _)
+    };
+    assert!(ret >= 0);
+    let err = ds.read_raw::<Color>().unwrap_err();
+    assert!(err.description().contains("Invalid enum value: 5"));
+    assert!(err.description().contains("at element 1"));
+    assert!(ds.read_1d::<Color>().is_err());
+    assert_eq!(ds.read_selection::<Color, _>(hdf5::Hyperslab::new(2, 2))?.to_vec(), &colors[2..]);
+
+    let pixels = vec![Pixel { x: 1, colors: [Color::Red, Color::Blue] }];
+    let ds = file.new_dataset::<Pixel>().create("pixels", 1)?;
+    ds.write(&pixels)?;
+    assert_eq!(ds.read_raw::<Pixel>()?, pixels);
+    let raw: [u8; 4] = [1, 0, 2, 3];
+    let dtype = ds.dtype()?;
+    let ret = unsafe {
+        H5Dwrite(ds.id(), dtype.id(), H5S_ALL, H5S_ALL, H5P_DEFAULT, raw.as_ptr() as *const _)
+    };
+    assert!(ret >= 0);
+    let err = ds.read_raw::<Pixel>().unwrap_err();
+    assert!(err
+        .description()
+        .contains("Invalid enum value: 3 does not match any of the enum members"));
+    assert!(err.description().contains("at element 0"));
+    Ok(())
+}
+
+#[test]
+fn test_read_write_with_dxpl() -> hdf5::Result<()> {
+    use hdf5::plist::DatasetTransfer;
+
+    let file = new_in_memory_file()?;
+    let dxpl = DatasetTransfer::try_new()?;
+    let ds = file.new_dataset::<u32>().create("foo", (2, 3))?;
+    let arr = Array2::from_shape_vec((2, 3), vec![1, 2, 3, 4, 5, 6]).unwrap();
+    ds.as_writer().dxpl(&dxpl).write(&arr)?;
+    assert_eq!(ds.as_reader().transfer_plist(&dxpl).read_2d::<u32>()?, arr);
+    ds.as_writer().dxpl(&dxpl).write_slice(&[7, 8], s![1, 1..])?;
+    assert_eq!(ds.as_reader().dxpl(&dxpl).read_raw::<u32>()?, vec![1, 2, 3, 4, 7, 8]);
+
+    // A tiny conversion buffer forces converting one element at a time.
+    let dxpl = DatasetTransfer::build().buffer_size(16).finish()?;
+    let row = ds.as_reader().dxpl(&dxpl).read_slice_1d::<f64, _>(&s![1, ..])?;
+    assert_eq!(row.to_vec(), vec![4., 7., 8.]);
+    ds.as_writer().dxpl(&dxpl).write_slice(&[9_u8, 10], s![0, ..2])?;
+    assert_eq!(ds.read_raw::<u32>()?, vec![9, 10, 3, 4, 7, 8]);
+    Ok(())
+}
+
+#[test]
+fn test_read_write_bitfield() -> hdf5::Result<()> {
+    use hdf5_sys::{
+        h5d::{H5Dcreate2, H5Dwrite},
+        h5p::H5P_DEFAULT,
+        h5s::H5S_ALL,
+        h5t::{H5T_NATIVE_B16, H5T_STD_B16BE},
+    };
+    use hdf5_types::IntSize;
+
+    let file = new_in_memory_file()?;
+    // Create a big-endian bitfield dataset the same way a C program would.
+    let space = hdf5::Dataspace::try_new(4, false)?;
+    let name = std::ffi::CString::new("flags").unwrap();
+    let flags: [u16; 4] = [0b1, 0b1010_0000_0000_0101, 0xffff, 0];
+    let ret = unsafe {
+        let ds_id = H5Dcreate2(
+            file.id(),
+            name.as_ptr(),
+            *H5T_STD_B16BE,
+            space.id(),
+            H5P_DEFAULT,
+            H5P_DEFAULT,
+            H5P_DEFAULT,
+        );
+        assert!(ds_id >= 0);
+        let ds = hdf5::from_id::<hdf5::Dataset>(ds_id)?;
+        H5Dwrite(
+            ds.id(),
+            *H5T_NATIVE_B16,
+            H5S_ALL,
+            H5S_ALL,
+            H5P_DEFAULT,
+            flags.as_ptr() as *const _,
+        )
+    };
+    assert!(ret >= 0);
+
+    let ds = file.dataset("flags")?;
+    assert_eq!(ds.dtype()?.to_descriptor()?, TypeDescriptor::Bitfield(IntSize::U2));
+    assert_eq!(ds.read_raw::<u16>()?, flags.to_vec());
+    assert_eq!(ds.read_1d::<u16>()?[1] & 0b101, 0b101);
+    assert!(ds.read_raw::<u32>().is_err());
+    assert!(ds.read_raw::<i16>().is_err());
+
+    ds.write(&[0x8000u16, 2, 4, 8])?;
+    assert_eq!(ds.read_raw::<u16>()?, vec![0x8000, 2, 4, 8]);
+    Ok(())
+}
+
+#[test]
+fn test_read_write_bool_h5py() -> hdf5::Result<()> {
+    use std::ffi::CString;
+
+    use hdf5_sys::{
+        h5d::{H5Dcreate2, H5Dwrite},
+        h5p::H5P_DEFAULT,
+        h5s::H5S_ALL,
+        h5t::{H5Tclose, H5Tenum_create, H5Tenum_insert, H5Tequal, H5T_NATIVE_INT8},
+    };
+
+    let file = new_in_memory_file()?;
+    let space = hdf5::Dataspace::try_new(4, false)?;
+    let name = CString::new("h5py").unwrap();
+    let raw: [i8; 4] = [1, 0, 0, 1];
+    let invalid: [i8; 4] = [0, 1, 2, 1];
+    unsafe {
+        // This is how h5py creates the datatype for NumPy's bool.
+        let dtype_id = H5Tenum_create(*H5T_NATIVE_INT8);
+        let (f, t) = (CString::new("FALSE").unwrap(), CString::new("TRUE").unwrap());
+        assert!(H5Tenum_insert(dtype_id, f.as_ptr(), &0i8 as *const _ as *const _) >= 0);
+        assert!(H5Tenum_insert(dtype_id, t.as_ptr(), &1i8 as *const _ as *const _) >= 0);
+        let ds_id = H5Dcreate2(
+            file.id(),
+            name.as_ptr(),
+            dtype_id,
+            space.id(),
+            H5P_DEFAULT,
+            H5P_DEFAULT,
+            H5P_DEFAULT,
+        );
+        let ds = hdf5::from_id::<hdf5::Dataset>(ds_id)?;
+        assert!(
+            H5Dwrite(ds.id(), dtype_id, H5S_ALL, H5S_ALL, H5P_DEFAULT, raw.as_ptr() as *const _)
+                >= 0
+        );
+        // Datasets of bools written by the crate have exactly the same type.
+        let ds = file.new_dataset::<bool>().create("native", 4)?;
+        assert!(H5Tequal(ds.dtype()?.id(), dtype_id) > 0);
+        H5Tclose(dtype_id);
+    }
+
+    let ds = file.dataset("h5py")?;
+    assert_eq!(ds.dtype()?.to_descriptor()?, TypeDescriptor::Boolean);
+    assert_eq!(ds.read_raw::<bool>()?, vec![true, false, false, true]);
+    ds.write(&[false, true, true, false])?;
+    assert_eq!(ds.read_raw::<i8>()?, vec![0, 1, 1, 0]);
+
+    // Stored values other than 0 and 1 must not be converted to bools.
+    let dtype = ds.dtype()?;
+    let ret = unsafe {
+        H5Dwrite(ds.id(), dtype.id(), H5S_ALL, H5S_ALL, H5P_DEFAULT, invalid.as_ptr() as *const _)
+    };
+    assert!(ret >= 0);
+    let err = ds.read_raw::<bool>().unwrap_err();
+    assert!(err.description().contains("Invalid boolean value: 2 (expected 0 or 1)"));
+    assert_eq!(
+        ds.read_selection::<bool, _>(hdf5::Hyperslab::new(0, 2))?.to_vec(),
+        vec![false, true]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_read_write_nested_varlen() -> hdf5::Result<()> {
+    use std::{iter, ptr};
+
+    use hdf5::plist::DatasetTransfer;
+    use hdf5::types::VarLenArray;
+    use hdf5_sys::h5p::H5Pset_vlen_mem_manager;
+
+    type Ragged = VarLenArray<VarLenArray<f64>>;
+
+    fn ragged(rows: &[&[f64]]) -> Ragged {
+        let rows: Vec<_> = rows.iter().map(|row| VarLenArray::from_slice(row)).collect();
+        VarLenArray::from_slice(&rows)
+    }
+
+    let file = new_in_memory_file()?;
+    let data = vec![
+        ragged(&[&[1., 2., 3.], &[], &[4.]]),
+        ragged(&[]),
+        ragged(&[&[5., 6.], &[7., 8., 9., 10.]]),
+    ];
+    let ds = file.new_dataset::<Ragged>().create("ragged", 3)?;
+    ds.write(&data)?;
+    assert_eq!(ds.read_raw::<Ragged>()?, data);
+
+    let lengths = |v: &Ragged| v.iter().map(|row| row.len()).collect::<Vec<_>>();
+    let arr = ds.read_1d::<Ragged>()?;
+    assert_eq!(
+        arr.iter().map(lengths).collect::<Vec<_>>(),
+        vec![vec![3, 0, 1], vec![], vec![2, 4]]
+    );
+    assert_eq!(&*arr[2][1], &[7., 8., 9., 10.]);
+    assert_eq!(ds.read_slice_1d::<Ragged, _>(&s![1..])?.to_vec(), &data[1..]);
+
+    // Every buffer allocated by the library while reading must be owned by the result
+    // (and therefore released when it's dropped), at all levels of nesting.
+    extern "C" fn alloc(size: libc::size_t, info: *mut libc::c_void) -> *mut libc::c_void {
+        let ptr = unsafe { libc::malloc(size) };
+        unsafe { (*(info as *mut Vec<usize>)).push(ptr as usize) };
+        ptr
+    }
+    let mut allocated: Vec<usize> = Vec::new();
+    let dxpl = DatasetTransfer::try_new()?;
+    let info = &mut allocated as *mut Vec<usize> as *mut _;
+    unsafe { H5Pset_vlen_mem_manager(dxpl.id(), Some(alloc), info, None, ptr::null_mut()) };
+    let read = ds.as_reader().dxpl(&dxpl).read_raw::<Ragged>()?;
+    assert_eq!(read, data);
+    let mut owned: Vec<usize> = read
+        .iter()
+        .flat_map(|v| iter::once(v.as_ptr() as usize).chain(v.iter().map(|r| r.as_ptr() as usize)))
+        .filter(|&addr| addr != 0)
+        .collect();
+    allocated.sort();
+    owned.sort();
+    assert_eq!(allocated.len(), 6);
+    assert_eq!(allocated, owned);
+    drop(read);
+
+    assert!(ds.read_raw::<VarLenArray<f64>>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_read_into_buffer() -> hdf5::Result<()> {
+    use hdf5_sys::{h5d::H5Dwrite, h5p::H5P_DEFAULT, h5s::H5S_ALL};
+
+    let file = new_in_memory_file()?;
+    let ds = file.new_dataset::<u32>().create("data", (4, 5))?;
+    let mut buf = vec![0u32; 20];
+    let mut row = vec![0u32; 5];
+    for i in 0..3 {
+        let arr = Array2::from_shape_fn((4, 5), |(r, c)| (i * 100 + r * 10 + c) as u32);
+        ds.write(&arr)?;
+        ds.read_into(&mut buf)?;
+        assert_eq!(buf, ds.read_raw::<u32>()?);
+        assert_eq!(buf.as_slice(), arr.as_slice().unwrap());
+        ds.read_slice_into(&mut row, &s![2, ..])?;
+        assert_eq!(row, ds.read_slice_1d::<u32, _>(&s![2, ..])?.to_vec());
+        ds.as_reader().read_slice_into(&mut row[..2], &s![1..3, 4])?;
+        assert_eq!(&row[..2], &[arr[[1, 4]], arr[[2, 4]]]);
+    }
+
+    let err = ds.read_into(&mut buf[..19]).unwrap_err();
+    assert!(err.description().contains("length mismatch when reading: memory = 19, source = 20"));
+    assert!(ds.read_slice_into(&mut row, &s![.., 0]).is_err());
+    assert!(ds.read_slice_into(&mut row, &s![0]).is_err());
+
+    // The buffer is left untouched if invalid values were read.
+    let ds = file.new_dataset::<bool>().create("flags", 3)?;
+    let raw: [u8; 3] = [1, 2, 0];
+    let dtype = ds.dtype()?;
+    let ret = unsafe {
+        H5Dwrite(ds.id(), dtype.id(), H5S_ALL, H5S_ALL, H5P_DEFAULT, raw.as_ptr() as *const _)
+    };
+    assert!(ret >= 0);
+    let mut flags = [true; 3];
+    assert!(ds.read_into(&mut flags).is_err());
+    assert_eq!(flags, [true; 3]);
+    Ok(())
+}
+
+#[test]
+fn test_read_strings() -> hdf5::Result<()> {
+    use hdf5::{CharEncoding, StringPadding};
+    use hdf5_types::{FixedAscii, FixedUnicode, VarLenAscii, VarLenUnicode};
+
+    let file = new_in_memory_file()?;
+
+    let ascii = ["foo", "", "bar baz"];
+    let values: Vec<_> = ascii.iter().map(|s| VarLenAscii::from_ascii(s).unwrap()).collect();
+    let ds = file.new_dataset::<VarLenAscii>().create("ascii", 3)?;
+    ds.write(&values)?;
+    assert_eq!(ds.dtype()?.string_encoding(), Some(CharEncoding::Ascii));
+    assert_eq!(ds.read_strings()?, ascii);
+    // ASCII strings are valid UTF-8 strings, so they can be read either way.
+    let unicode: Vec<_> = ds.read_raw::<VarLenUnicode>()?;
+    assert_eq!(unicode.iter().map(|s| s.as_str()).collect::<Vec<_>>(), ascii);
+
+    let utf8 = ["héllo", "", "日本語"];
+    let values: Vec<VarLenUnicode> = utf8.iter().map(|s| s.parse().unwrap()).collect();
+    let ds = file.new_dataset::<VarLenUnicode>().create("utf8", 3)?;
+    ds.write(&values)?;
+    assert_eq!(ds.dtype()?.string_encoding(), Some(CharEncoding::Utf8));
+    assert_eq!(ds.read_strings()?, utf8);
+    let err = ds.read_raw::<VarLenAscii>().unwrap_err();
+    assert!(err
+        .description()
+        .contains("string encoding mismatch: cannot convert UTF-8 strings into ASCII strings"));
+    assert!(ds.read_raw::<FixedAscii<[u8; 16]>>().is_err());
+
+    let values: Vec<FixedUnicode<[u8; 12]>> = utf8.iter().map(|s| s.parse().unwrap()).collect();
+    let ds = file.new_dataset::<FixedUnicode<[u8; 12]>>().create("fixed_utf8", 3)?;
+    ds.write(&values)?;
+    assert_eq!(ds.read_strings()?, utf8);
+
+    let values: Vec<FixedAscii<[u8; 8]>> =
+        ascii.iter().map(|s| FixedAscii::from_ascii(s).unwrap()).collect();
+    let ds = file.new_dataset::<FixedAscii<[u8; 8]>>().string_padding(StringPadding::SpacePad);
+    let ds = ds.create("fixed_ascii", 3)?;
+    ds.write(&values)?;
+    assert_eq!(ds.read_strings()?, ascii);
+
+    let ds = file.new_dataset::<u32>().create("numbers", 3)?;
+    assert!(ds.dtype()?.string_encoding().is_none());
+    assert!(ds.read_strings().unwrap_err().description().contains("Expected a string dataset"));
+    Ok(())
+}
+
+#[test]
+fn test_read_field() -> hdf5::Result<()> {
+    use hdf5::H5Type;
+
+    #[derive(H5Type, Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct Record {
+        id: u64,
+        value: f32,
+        flag: i8,
+    }
+
+    let file = new_in_memory_file()?;
+    let records = Array2::from_shape_fn((2, 3), |(i, j)| Record {
+        id: (i * 3 + j) as u64,
+        value: (i * 3 + j) as f32 / 2.,
+        flag: -((i * 3 + j) as i8),
+    });
+    let ds = file.new_dataset::<Record>().create("records", (2, 3))?;
+    ds.write(&records)?;
+
+    // The result only holds the requested field; the other fields are never read.
+    let values: Array2<f32> = ds.read_field("value")?;
+    assert_eq!(values, records.map(|r| r.value));
+    let values = ds.read_field::<f64, IxDyn>("value")?;
+    assert_eq!(values.shape(), &[2, 3]);
+    assert_eq!(values.iter().cloned().collect::<Vec<_>>(), vec![0., 0.5, 1., 1.5, 2., 2.5]);
+    assert_eq!(ds.read_field::<i8, _>("flag")?, records.map(|r| r.flag));
+
+    let err = ds.read_field::<f32, ndarray::Ix2>("missing").unwrap_err();
+    assert!(err.description().contains("Field \"missing\" does not exist"));
+    let err = ds.read_field::<hdf5::types::VarLenAscii, ndarray::Ix2>("value").unwrap_err();
+    assert!(err.description().contains("Cannot read field \"value\""));
+    assert!(ds.read_field::<f32, ndarray::Ix1>("value").is_err());
+
+    let ds = file.new_dataset::<f32>().create("plain", 3)?;
+    let err = ds.read_field::<f32, ndarray::Ix1>("value").unwrap_err();
+    assert!(err.description().contains("not a compound datatype"));
+    Ok(())
+}
+
+#[test]
+fn test_write_field() -> hdf5::Result<()> {
+    use hdf5::H5Type;
+
+    #[derive(H5Type, Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct Record {
+        a: i32,
+        b: f64,
+        c: [u8; 3],
+    }
+
+    let file = new_in_memory_file()?;
+    let records: Vec<_> =
+        (0..4).map(|i| Record { a: i, b: f64::from(i) / 4., c: [i as u8, 0xff, 7] }).collect();
+    let ds = file.new_dataset::<Record>().create("records", 4)?;
+    ds.write(&records)?;
+
+    ds.write_field("b", &[10., 20., 30., 40.])?;
+    let stored = ds.read_raw::<Record>()?;
+    for (i, (old, new)) in records.iter().zip(&stored).enumerate() {
+        assert_eq!(new.a, old.a);
+        assert_eq!(new.b, 10. * (i + 1) as f64);
+        assert_eq!(new.c, old.c);
+    }
+    // Values are converted into the stored field type.
+    ds.write_field("a", &[-1_i8, -2, -3, -4])?;
+    assert_eq!(ds.read_field::<i32, ndarray::Ix1>("a")?.to_vec(), vec![-1, -2, -3, -4]);
+    assert_eq!(ds.read_field::<[u8; 3], ndarray::Ix1>("c")?[2], [2, 0xff, 7]);
+
+    let err = ds.write_field("b", &[1., 2.]).unwrap_err();
+    assert!(err.description().contains("shape mismatch when writing: memory = [2]"));
+    let err = ds.write_field("d", &[1., 2., 3., 4.]).unwrap_err();
+    assert!(err.description().contains("Field \"d\" does not exist"));
+    assert!(ds.write_field("c", &[1., 2., 3., 4.]).is_err());
+    assert_eq!(ds.read_raw::<Record>()?[3].b, 40.);
+
+    let attr = file.new_attr::<Record>().create("attr", 4)?;
+    attr.write(&records)?;
+    let err = attr.write_field("b", &[1., 2., 3., 4.]).unwrap_err();
+    assert!(err.description().contains("single fields cannot be written to attributes"));
+    Ok(())
+}
+
+#[test]
+fn test_read_converted() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let arr = Array2::from_shape_fn((3, 4), |(i, j)| (i as f64) * 0.1 + (j as f64) / 3.);
+    let ds = file.new_dataset::<f64>().create("data", (3, 4))?;
+    ds.write(&arr)?;
+
+    let converted: Array2<f32> = ds.read_2d()?;
+    assert_eq!(converted.shape(), &[3, 4]);
+    for (&x, &y) in arr.iter().zip(converted.iter()) {
+        assert!((x - f64::from(y)).abs() <= x.abs() * f64::from(std::f32::EPSILON));
+    }
+    let sliced: Array1<f32> = ds.read_slice_1d(s![1, ..])?;
+    assert_eq!(sliced, converted.row(1));
+    assert_eq!(ds.read_raw::<i32>()?, vec![0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1]);
+
+    let err = ds.as_reader().no_convert().read_2d::<f32>().unwrap_err();
+    assert!(err.description().contains("no-op conversion path required"));
+    let err = ds.read_raw::<FixedStruct>().unwrap_err();
+    assert!(err.description().contains("no conversion paths found"));
+    let err = ds.read_raw::<hdf5::types::VarLenUnicode>().unwrap_err();
+    assert!(err.description().contains("no conversion paths found"));
+    Ok(())
+}
+
+#[test]
+fn test_conversion_policy() -> hdf5::Result<()> {
+    use hdf5::ConversionPolicy::{Lossless, Lossy, Strict};
+
+    let file = new_in_memory_file()?;
+    let values = vec![1_i64, -2, 1 << 40, -(1 << 40)];
+    let ds = file.new_dataset::<i64>().create("wide", 4)?;
+    ds.write(&values)?;
+
+    let err = ds.as_reader().policy(Strict).read_raw::<i32>().unwrap_err();
+    assert!(err.description().contains("Strict conversion policy"));
+    let err = ds.as_reader().policy(Lossless).read_raw::<i32>().unwrap_err();
+    assert!(err.description().contains("Lossless conversion policy"));
+    assert!(ds.as_reader().policy(Lossless).read_raw::<f64>().is_err());
+    // Values out of range are clamped by the library.
+    let narrow = ds.as_reader().policy(Lossy).read_raw::<i32>()?;
+    assert_eq!(narrow, vec![1, -2, std::i32::MAX, std::i32::MIN]);
+    assert_eq!(ds.as_reader().policy(Strict).read_raw::<i64>()?, values);
+
+    let ds = file.new_dataset::<u16>().create("narrow", 2)?;
+    ds.write(&[1_u16, 65535])?;
+    for &policy in &[Lossless, Lossy] {
+        assert_eq!(ds.as_reader().policy(policy).read_raw::<u32>()?, vec![1, 65535]);
+        assert_eq!(ds.as_reader().policy(policy).read_raw::<i32>()?, vec![1, 65535]);
+        assert_eq!(ds.as_reader().policy(policy).read_raw::<f32>()?, vec![1., 65535.]);
+    }
+    assert!(ds.as_reader().policy(Lossless).read_raw::<i16>().is_err());
+    assert!(ds.as_reader().policy(Strict).read_raw::<u32>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_read_flattened() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let arr = Array2::from_shape_fn((6, 4), |(i, j)| (i * 4 + j) as u32);
+    let ds = file.new_dataset::<u32>().create("matrix", (6, 4))?;
+    ds.write(&arr)?;
+    assert_eq!(ds.read_raw::<u32>()?, (0..24).collect::<Vec<_>>());
+    let flat = Array1::from_shape_vec(24, ds.read_raw::<u32>()?)?;
+    assert_eq!(flat, arr.into_shape(24)?);
+    assert_eq!(ds.shape(), vec![6, 4]);
+    Ok(())
+}
+
+#[test]
+fn test_write_finite() -> hdf5::Result<()> {
+    use std::f64::{INFINITY, NAN, NEG_INFINITY};
+
+    let file = new_in_memory_file()?;
+    let arr = ndarray::arr2(&[[1.5, NAN, 3.], [INFINITY, -2., NEG_INFINITY]]);
+    let ds = file.new_dataset::<f64>().create("data", (2, 3))?;
+    assert_eq!(ds.write_finite(&arr, -999.)?, 3);
+    assert_eq!(ds.read_raw::<f64>()?, vec![1.5, -999., 3., -999., -2., -999.]);
+    assert!(arr[(0, 1)].is_nan());
+
+    // Without a sentinel, non-finite values are stored as is.
+    ds.write(&arr)?;
+    let values = ds.read_raw::<f64>()?;
+    assert!(values[1].is_nan());
+    assert_eq!(values[3], INFINITY);
+
+    let ds = file.new_dataset::<f32>().create("finite", 2)?;
+    assert_eq!(ds.as_writer().write_finite(&[1_f32, 2.], 0.)?, 0);
+    assert_eq!(ds.read_raw::<f32>()?, vec![1., 2.]);
+    assert!(ds.write_finite(&[1_f32, 2.], std::f32::NAN).is_err());
+    Ok(())
+}
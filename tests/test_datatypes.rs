@@ -6,6 +6,14 @@ use hdf5::{from_id, Datatype, H5Type};
 
 use hdf5_sys::h5i::H5I_INVALID_HID;
 
+use self::common::util::new_in_memory_file;
+
+struct Protobuf;
+
+impl OpaqueTag for Protobuf {
+    const TAG: &'static str = "protobuf";
+}
+
 macro_rules! check_roundtrip {
     ($ty:ty, $desc:expr) => {{
         let desc = <$ty as H5Type>::type_descriptor();
@@ -31,10 +39,19 @@ pub fn test_datatype_roundtrip() {
     check_roundtrip!(bool, TD::Boolean);
     check_roundtrip!([bool; 5], TD::FixedArray(Box::new(TD::Boolean), 5));
     check_roundtrip!(VarLenArray<bool>, TD::VarLenArray(Box::new(TD::Boolean)));
+    check_roundtrip!(
+        VarLenArray<VarLenArray<f64>>,
+        TD::VarLenArray(Box::new(TD::VarLenArray(Box::new(TD::Float(FloatSize::U8)))))
+    );
     check_roundtrip!(FixedAscii<[_; 5]>, TD::FixedAscii(5));
     check_roundtrip!(FixedUnicode<[_; 5]>, TD::FixedUnicode(5));
     check_roundtrip!(VarLenAscii, TD::VarLenAscii);
     check_roundtrip!(VarLenUnicode, TD::VarLenUnicode);
+    check_roundtrip!(RegionReference, TD::RegionReference);
+    check_roundtrip!(
+        OpaqueBytes<[u8; 16], Protobuf>,
+        TD::Opaque(OpaqueType { size: 16, tag: "protobuf".into() })
+    );
 
     #[allow(dead_code)]
     #[derive(H5Type)]
@@ -86,6 +103,185 @@ pub fn test_datatype_roundtrip() {
     check_roundtrip!(C, c_desc);
 }
 
+#[test]
+pub fn test_opaque() -> hdf5::Result<()> {
+    struct Other;
+
+    impl OpaqueTag for Other {
+        const TAG: &'static str = "other";
+    }
+
+    type Blob = OpaqueBytes<[u8; 8], Protobuf>;
+
+    let file = new_in_memory_file()?;
+    let blobs =
+        vec![Blob::from_bytes(&[0x08, 0x96, 0x01]).unwrap(), Blob::from_bytes(&[255; 8]).unwrap()];
+    file.new_dataset::<Blob>().create("blobs", 2)?.write(&blobs)?;
+
+    let ds = file.dataset("blobs")?;
+    assert_eq!(
+        ds.dtype()?.to_descriptor()?,
+        TD::Opaque(OpaqueType { size: 8, tag: "protobuf".into() })
+    );
+    assert_eq!(ds.read_raw::<Blob>()?, blobs);
+    assert_eq!(ds.read_raw::<Blob>()?[0].as_bytes(), &[0x08, 0x96, 0x01, 0, 0, 0, 0, 0]);
+    assert!(ds.read_raw::<OpaqueBytes<[u8; 8], Other>>().is_err());
+    Ok(())
+}
+
+#[test]
+pub fn test_type_class() -> hdf5::Result<()> {
+    use hdf5::TypeClass;
+
+    #[allow(dead_code)]
+    #[derive(H5Type, Clone, Copy)]
+    #[repr(u8)]
+    enum Color {
+        R = 1,
+        G = 2,
+    }
+
+    #[allow(dead_code)]
+    #[derive(H5Type, Clone, Copy)]
+    #[repr(C)]
+    struct Point {
+        x: f32,
+        y: i16,
+    }
+
+    let file = new_in_memory_file()?;
+    macro_rules! check {
+        ($ty:ty, $class:expr, $size:expr, $signed:expr) => {{
+            let ds = file.new_dataset::<$ty>().create(stringify!($ty), 1)?;
+            let dt = ds.dtype()?;
+            assert_eq!(dt.class()?, $class);
+            assert_eq!(dt.size(), $size);
+            assert_eq!(dt.is_signed(), $signed);
+        }};
+    }
+    check!(i8, TypeClass::Integer, 1, Some(true));
+    check!(u16, TypeClass::Integer, 2, Some(false));
+    check!(i64, TypeClass::Integer, 8, Some(true));
+    check!(f32, TypeClass::Float, 4, None);
+    check!(f64, TypeClass::Float, 8, None);
+    check!(bool, TypeClass::Enum, 1, None);
+    check!(Color, TypeClass::Enum, 1, None);
+    check!(Point, TypeClass::Compound, 8, None);
+    check!([u32; 3], TypeClass::Array, 12, None);
+    check!(FixedAscii<[u8; 5]>, TypeClass::String, 5, None);
+    check!(VarLenUnicode, TypeClass::String, std::mem::size_of::<usize>(), None);
+    check!(VarLenArray<u8>, TypeClass::VarLen, std::mem::size_of::<VarLenArray<u8>>(), None);
+    Ok(())
+}
+
+#[test]
+pub fn test_compound_fields_h5py() -> hdf5::Result<()> {
+    use std::ffi::CString;
+
+    use hdf5_sys::{
+        h5d::H5Dcreate2,
+        h5p::H5P_DEFAULT,
+        h5t::{
+            H5T_class_t, H5Tclose, H5Tcopy, H5Tcreate, H5Tinsert, H5Tset_size, H5T_C_S1,
+            H5T_IEEE_F64LE, H5T_STD_U32LE,
+        },
+    };
+
+    let file = new_in_memory_file()?;
+    let space = hdf5::Dataspace::try_new(2, false)?;
+    let name = CString::new("records").unwrap();
+    unsafe {
+        // This is how h5py stores a packed NumPy dtype [('id', '<u4'), ('value', '<f8'),
+        // ('tag', 'S3')].
+        let str_id = H5Tcopy(*H5T_C_S1);
+        H5Tset_size(str_id, 3);
+        let dtype_id = H5Tcreate(H5T_class_t::H5T_COMPOUND, 15);
+        let names: Vec<_> =
+            ["id", "value", "tag"].iter().map(|s| CString::new(*s).unwrap()).collect();
+        assert!(H5Tinsert(dtype_id, names[0].as_ptr(), 0, *H5T_STD_U32LE) >= 0);
+        assert!(H5Tinsert(dtype_id, names[1].as_ptr(), 4, *H5T_IEEE_F64LE) >= 0);
+        assert!(H5Tinsert(dtype_id, names[2].as_ptr(), 12, str_id) >= 0);
+        let ds_id = H5Dcreate2(
+            file.id(),
+            name.as_ptr(),
+            dtype_id,
+            space.id(),
+            H5P_DEFAULT,
+            H5P_DEFAULT,
+            H5P_DEFAULT,
+        );
+        from_id::<hdf5::Dataset>(ds_id)?;
+        H5Tclose(dtype_id);
+        H5Tclose(str_id);
+    }
+
+    let dtype = file.dataset("records")?.dtype()?;
+    assert_eq!(dtype.size(), 15);
+    assert_eq!(
+        dtype.compound_fields()?,
+        vec![
+            CompoundField::typed::<u32>("id", 0, 0),
+            CompoundField::typed::<f64>("value", 4, 1),
+            CompoundField::new("tag", TD::FixedAscii(3), 12, 2),
+        ]
+    );
+    assert_err!(Datatype::from_type::<u32>()?.compound_fields(), "Not a compound datatype");
+    Ok(())
+}
+
+#[test]
+pub fn test_array_h5py() -> hdf5::Result<()> {
+    use std::ffi::CString;
+
+    use hdf5_sys::{
+        h5d::H5Dcreate2,
+        h5p::H5P_DEFAULT,
+        h5t::{H5Tarray_create2, H5Tclose, H5Tget_array_ndims, H5T_IEEE_F64LE},
+    };
+
+    let file = new_in_memory_file()?;
+    let space = hdf5::Dataspace::try_new(2, false)?;
+    let name = CString::new("matrices").unwrap();
+    unsafe {
+        // This is how h5py stores a NumPy subarray dtype ('<f8', (2, 3)).
+        let dims = [2, 3];
+        let dtype_id = H5Tarray_create2(*H5T_IEEE_F64LE, 2, dims.as_ptr());
+        let ds_id = H5Dcreate2(
+            file.id(),
+            name.as_ptr(),
+            dtype_id,
+            space.id(),
+            H5P_DEFAULT,
+            H5P_DEFAULT,
+            H5P_DEFAULT,
+        );
+        from_id::<hdf5::Dataset>(ds_id)?;
+        H5Tclose(dtype_id);
+    }
+
+    let ds = file.dataset("matrices")?;
+    let desc = TD::FixedArray(Box::new(TD::FixedArray(Box::new(TD::Float(FloatSize::U8)), 3)), 2);
+    assert_eq!(ds.dtype()?.to_descriptor()?, desc);
+    assert_ne!(ds.dtype()?, Datatype::from_type::<[[f64; 3]; 2]>()?);
+    let data = [[[1., 2., 3.], [4., 5., 6.]], [[-1., -2., -3.], [-4., -5., -6.]]];
+    ds.write_raw(&data)?;
+    assert_eq!(ds.read_raw::<[[f64; 3]; 2]>()?, data.to_vec());
+
+    // Nested arrays are still stored as arrays of arrays, as in previous versions.
+    let ds = file.new_dataset::<[[f64; 3]; 2]>().create("nested", 2)?;
+    assert_eq!(ds.dtype()?, Datatype::from_type::<[[f64; 3]; 2]>()?);
+    assert_eq!(ds.dtype()?.to_descriptor()?, desc);
+    ds.write_raw(&data)?;
+    assert_eq!(ds.read_raw::<[[f64; 3]; 2]>()?, data.to_vec());
+    assert_eq!(unsafe { H5Tget_array_ndims(ds.dtype()?.id()) }, 1);
+
+    let arr = ndarray::Array2::from_shape_fn((3, 2), |(i, j)| [i as f32, j as f32, 0.5, -0.5]);
+    let ds = file.new_dataset::<[f32; 4]>().create("vectors", (3, 2))?;
+    ds.write(&arr)?;
+    assert_eq!(ds.read_2d::<[f32; 4]>()?, arr);
+    Ok(())
+}
+
 #[test]
 pub fn test_invalid_datatype() {
     assert_err!(from_id::<Datatype>(H5I_INVALID_HID), "Invalid datatype id");
@@ -101,3 +297,60 @@ pub fn test_eq() {
 pub fn test_debug() {
     assert_eq!(format!("{:?}", Datatype::from_type::<u32>().unwrap()), "<HDF5 datatype>");
 }
+
+#[test]
+pub fn test_committed_type() -> hdf5::Result<()> {
+    use std::ffi::CStr;
+
+    use hdf5_sys::h5i::H5Iget_name;
+
+    #[derive(H5Type, Clone, Copy, Debug, PartialEq)]
+    #[repr(C)]
+    struct Record {
+        id: u32,
+        value: f64,
+    }
+
+    fn type_name(dt: &Datatype) -> String {
+        let mut buf = [0 as libc::c_char; 64];
+        assert!(unsafe { H5Iget_name(dt.id(), buf.as_mut_ptr(), buf.len()) } >= 0);
+        unsafe { CStr::from_ptr(buf.as_ptr()) }.to_string_lossy().into_owned()
+    }
+
+    let file = new_in_memory_file()?;
+    assert!(!Datatype::from_type::<Record>()?.is_committed());
+    let dt = file.commit_type::<Record>("record")?;
+    assert!(dt.is_committed());
+    assert_eq!(type_name(&dt), "/record");
+    assert!(file.commit_type::<Record>("record").is_err());
+
+    let records = [Record { id: 1, value: 0.5 }, Record { id: 2, value: -1.5 }];
+    let ds1 = file.new_dataset::<Record>().committed_type(&dt).create("ds1", 2)?;
+    ds1.write(&records)?;
+    let dt = file.datatype("record")?;
+    assert!(dt.is_committed());
+    let ds2 = file.new_dataset::<Record>().committed_type(&dt).create("ds2", 1)?;
+    ds2.write(&records[1..])?;
+
+    for ds in &[file.dataset("ds1")?, file.dataset("ds2")?] {
+        let ds_dt = ds.dtype()?;
+        assert!(ds_dt.is_committed());
+        assert_eq!(type_name(&ds_dt), "/record");
+        assert_eq!(ds_dt, dt);
+    }
+    assert_eq!(file.dataset("ds1")?.read_raw::<Record>()?, records);
+    assert_eq!(file.dataset("ds2")?.read_raw::<Record>()?, &records[1..]);
+    assert!(!file.new_dataset::<Record>().create("ds3", 1)?.dtype()?.is_committed());
+
+    assert!(file.datatype("missing").is_err());
+    let transient = Datatype::from_type::<Record>()?;
+    assert_err!(
+        file.new_dataset::<Record>().committed_type(&transient).create("ds4", 1),
+        "Datatype is not committed"
+    );
+    assert_err!(
+        file.new_dataset::<u8>().committed_type(&dt).create("ds5", 1),
+        "no conversion paths found"
+    );
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use std::sync::{Arc, Mutex};
+
+mod common;
+
+use self::common::util::new_in_memory_file;
+
+#[test]
+fn test_error_handler() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    {
+        let reported = reported.clone();
+        hdf5::set_error_handler(move |stack| {
+            reported.lock().unwrap().push(stack.description().to_owned());
+        });
+    }
+
+    // errors are routed to the handler and still returned to the caller
+    let count = || reported.lock().unwrap().len();
+    let err = file.dataset("missing").unwrap_err();
+    assert!(count() > 0);
+    assert_eq!(reported.lock().unwrap().last().unwrap(), err.description());
+
+    // silencing takes precedence and reinstates the handler afterwards
+    {
+        let _e = hdf5::silence_errors();
+        let n = count();
+        assert!(file.group("missing").is_err());
+        assert_eq!(count(), n);
+    }
+    let n = count();
+    assert!(file.group("missing").is_err());
+    assert!(count() > n);
+
+    // existence checks never report errors
+    let n = count();
+    assert!(!file.attr_exists("missing"));
+    assert!(!file.link_exists("missing/a/b"));
+    file.new_attr::<u8>().create("attr", ())?;
+    assert!(file.attr_exists("attr"));
+    assert!(!file.attr("attr")?.attr_exists("attr"));
+    assert_eq!(count(), n);
+
+    hdf5::reset_error_handler();
+    let _e = hdf5::silence_errors();
+    let n = count();
+    assert!(file.group("missing").is_err());
+    assert_eq!(count(), n);
+    Ok(())
+}
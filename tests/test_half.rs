@@ -0,0 +1,88 @@
+#![cfg(feature = "half")]
+
+use half::f16;
+use ndarray::Array2;
+
+use hdf5::types::{FloatSize, TypeDescriptor as TD};
+use hdf5::H5Type;
+
+mod common;
+
+use self::common::util::new_in_memory_file;
+
+#[test]
+fn test_f16_roundtrip() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let bits: Vec<u16> = vec![
+        0x0000, 0x8000, 0x0001, 0x03ff, 0x0400, 0x3c00, 0xbc00, 0x3555, 0x7bff, 0x7c00, 0xfc00,
+        0x7e00,
+    ];
+    let arr =
+        Array2::from_shape_vec((3, 4), bits.iter().cloned().map(f16::from_bits).collect()).unwrap();
+    let ds = file.new_dataset::<f16>().create("f16", arr.dim())?;
+    ds.write(&arr)?;
+    assert_eq!(ds.dtype()?.to_descriptor()?, TD::Float(FloatSize::U2));
+    assert_eq!(ds.dtype()?.size(), 2);
+    let read: Vec<u16> = ds.read_2d::<f16>()?.iter().map(|x| x.to_bits()).collect();
+    assert_eq!(read, bits);
+
+    let ds = file.new_dataset::<f32>().create("f32", 3)?;
+    ds.write(&[1.5f32, -0.25, 65504.0])?;
+    let read = ds.read_raw::<f16>()?;
+    assert_eq!(read, vec![f16::from_f32(1.5), f16::from_f32(-0.25), f16::from_f32(65504.0)]);
+    Ok(())
+}
+
+#[test]
+fn test_f16_h5py_layout() -> hdf5::Result<()> {
+    use std::ffi::CString;
+
+    use hdf5_sys::{
+        h5d::{H5Dcreate2, H5Dwrite},
+        h5p::H5P_DEFAULT,
+        h5s::H5S_ALL,
+        h5t::{H5Tclose, H5Tcopy, H5Tset_ebias, H5Tset_fields, H5Tset_size, H5T_IEEE_F32LE},
+    };
+
+    let file = new_in_memory_file()?;
+    let space = hdf5::Dataspace::try_new(4, false)?;
+    let name = CString::new("h5py").unwrap();
+    let bits: [u16; 4] = [0x3c00, 0xc000, 0x7c00, 0x0200];
+    unsafe {
+        // This is how h5py creates the datatype for NumPy's float16 (little-endian).
+        let dtype_id = H5Tcopy(*H5T_IEEE_F32LE);
+        assert!(H5Tset_fields(dtype_id, 15, 10, 5, 0, 10) >= 0);
+        assert!(H5Tset_size(dtype_id, 2) >= 0);
+        assert!(H5Tset_ebias(dtype_id, 15) >= 0);
+        let ds_id = H5Dcreate2(
+            file.id(),
+            name.as_ptr(),
+            dtype_id,
+            space.id(),
+            H5P_DEFAULT,
+            H5P_DEFAULT,
+            H5P_DEFAULT,
+        );
+        let ds = hdf5::from_id::<hdf5::Dataset>(ds_id)?;
+        assert!(
+            H5Dwrite(ds.id(), dtype_id, H5S_ALL, H5S_ALL, H5P_DEFAULT, bits.as_ptr() as *const _)
+                >= 0
+        );
+        H5Tclose(dtype_id);
+    }
+
+    let ds = file.dataset("h5py")?;
+    assert_eq!(ds.dtype()?.to_descriptor()?, f16::type_descriptor());
+    assert!(ds.dtype()?.is::<f16>());
+    assert_eq!(
+        ds.read_raw::<f16>()?,
+        vec![
+            f16::from_f32(1.0),
+            f16::from_f32(-2.0),
+            f16::from_bits(0x7c00),
+            f16::from_bits(0x0200)
+        ]
+    );
+    assert_eq!(ds.read_raw::<f32>()?[..2], [1.0, -2.0]);
+    Ok(())
+}
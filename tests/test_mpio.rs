@@ -0,0 +1,49 @@
+#![cfg(all(feature = "mpio", h5_have_parallel))]
+
+use std::env;
+use std::ptr;
+
+use ndarray::s;
+
+use hdf5::dataset::{DatasetTransfer, TransferMode};
+
+// This test has to be launched under MPI with several ranks, e.g.:
+// `mpirun -n 2 target/debug/deps/test_mpio-<hash> --ignored`
+#[test]
+#[ignore]
+fn test_mpio_collective_write() -> hdf5::Result<()> {
+    let (mut rank, mut size) = (0, 0);
+    let comm = unsafe {
+        mpi_sys::MPI_Init(ptr::null_mut(), ptr::null_mut());
+        mpi_sys::MPI_Comm_rank(mpi_sys::RSMPI_COMM_WORLD, &mut rank);
+        mpi_sys::MPI_Comm_size(mpi_sys::RSMPI_COMM_WORLD, &mut size);
+        mpi_sys::RSMPI_COMM_WORLD
+    };
+    let (rank, size) = (rank as usize, size as usize);
+    let path = env::temp_dir().join("hdf5-rust-test-mpio.h5");
+    let n = 4;
+
+    {
+        let file = hdf5::File::with_options().with_fapl(|p| p.mpio(comm, None)).create(&path)?;
+        let ds = file.new_dataset::<u32>().no_chunk().create("data", (size, n))?;
+        let dxpl =
+            DatasetTransfer::build().mpio_transfer_mode(TransferMode::Collective).finish()?;
+        assert_eq!(dxpl.mpio_transfer_mode(), TransferMode::Collective);
+        let row: Vec<u32> = (0..n).map(|i| (rank * 10 + i) as _).collect();
+        ds.as_writer().dxpl(&dxpl).write_slice(&row, s![rank, ..])?;
+    }
+
+    unsafe { mpi_sys::MPI_Barrier(comm) };
+
+    {
+        let file = hdf5::File::with_options().with_fapl(|p| p.mpio(comm, None)).open(&path)?;
+        let arr = file.dataset("data")?.read_2d::<u32>()?;
+        assert_eq!(arr.shape(), &[size, n]);
+        for ((r, i), &x) in arr.indexed_iter() {
+            assert_eq!(x as usize, r * 10 + i);
+        }
+    }
+
+    unsafe { mpi_sys::MPI_Finalize() };
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use hdf5::packet::PacketTable;
+use hdf5::types::FixedAscii;
+use hdf5::H5Type;
+
+mod common;
+
+use self::common::util::new_in_memory_file;
+
+#[derive(H5Type, Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+struct Telemetry {
+    timestamp: u64,
+    sensor: FixedAscii<[u8; 8]>,
+    value: f64,
+    flags: [u8; 3],
+}
+
+impl Telemetry {
+    fn new(i: u64) -> Self {
+        let sensor = FixedAscii::from_ascii(format!("s{}", i % 7).as_bytes()).unwrap();
+        Self { timestamp: 1_000 + i, sensor, value: i as f64 / 4., flags: [i as u8, 0, 1] }
+    }
+}
+
+#[test]
+fn test_packet_table_append() -> hdf5::Result<()> {
+    let file = new_in_memory_file()?;
+    let group = file.create_group("telemetry")?;
+    let table = PacketTable::<Telemetry>::create(&group, "packets")?;
+    for i in 0..1000 {
+        table.append(&Telemetry::new(i))?;
+    }
+    assert_eq!(table.len(), 1000);
+    assert_eq!(table.get(500)?, Telemetry::new(500));
+    assert_eq!(table.get(999)?, Telemetry::new(999));
+    assert!(table.get(1000).is_err());
+
+    let batch: Vec<_> = (1000..1100).map(Telemetry::new).collect();
+    table.append_many(&batch)?;
+    let table = PacketTable::<Telemetry>::open(&group, "packets")?;
+    assert_eq!(table.len(), 1100);
+    assert_eq!(table.get(1050)?, Telemetry::new(1050));
+    let all = table.dataset().read_raw::<Telemetry>()?;
+    assert!(all.iter().enumerate().all(|(i, p)| *p == Telemetry::new(i as _)));
+    Ok(())
+}
@@ -499,6 +499,22 @@ fn test_fapl_set_libver_bounds() -> hdf5::Result<()> {
     Ok(())
 }
 
+#[test]
+#[cfg(hdf5_1_10_7)]
+fn test_fapl_set_file_locking() -> hdf5::Result<()> {
+    test_pl!(FA, file_locking: use_file_locking = false, ignore_when_disabled = false);
+    test_pl!(FA, file_locking: use_file_locking = false, ignore_when_disabled = true);
+    test_pl!(FA, file_locking: use_file_locking = true, ignore_when_disabled = true);
+    Ok(())
+}
+
+#[test]
+#[cfg(not(hdf5_1_10_7))]
+fn test_fapl_set_file_locking() {
+    let err = FA::build().file_locking(false, true).finish().unwrap_err();
+    assert!(err.to_string().contains("Configuring file locking requires HDF5 1.10.7 or later"));
+}
+
 #[test]
 #[cfg(hdf5_1_10_1)]
 fn test_fapl_set_page_buffer_size() -> hdf5::Result<()> {
@@ -577,3 +593,44 @@ fn test_dapl_set_virtual_printf_gap() -> hdf5::Result<()> {
     test_pl!(DA, virtual_printf_gap: 123);
     Ok(())
 }
+
+type DT = DatasetTransfer;
+#[cfg(h5_have_parallel)]
+type DTB = DatasetTransferBuilder;
+
+#[test]
+fn test_dxpl_default() -> hdf5::Result<()> {
+    let pl = DT::try_new()?;
+    assert_eq!(pl.class()?, PropertyListClass::DataTransfer);
+    assert_eq!(DT::build().finish()?, pl);
+    assert_eq!(pl.copy(), pl);
+    Ok(())
+}
+
+#[test]
+fn test_dxpl_set_buffer_size() -> hdf5::Result<()> {
+    assert_eq!(DT::try_new()?.buffer_size(), 1024 * 1024);
+    test_pl!(DT, buffer_size: 1);
+    test_pl!(DT, buffer_size: 64 * 1024 * 1024);
+    assert_eq!(DT::build().buffer_size(4096).finish()?.get_buffer_size()?, 4096);
+    assert!(DT::build().buffer_size(0).finish().is_err());
+    Ok(())
+}
+
+#[test]
+#[cfg(h5_have_parallel)]
+fn test_dxpl_common() -> hdf5::Result<()> {
+    test_pl_common!(DT, PropertyListClass::DataTransfer, |b: &mut DTB| b
+        .mpio_transfer_mode(TransferMode::Collective)
+        .finish());
+    Ok(())
+}
+
+#[test]
+#[cfg(h5_have_parallel)]
+fn test_dxpl_set_mpio_transfer_mode() -> hdf5::Result<()> {
+    assert_eq!(DT::try_new()?.mpio_transfer_mode(), TransferMode::Independent);
+    test_pl!(DT, mpio_transfer_mode: TransferMode::Collective);
+    test_pl!(DT, mpio_transfer_mode: TransferMode::Independent);
+    Ok(())
+}
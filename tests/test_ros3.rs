@@ -0,0 +1,36 @@
+#![cfg(feature = "ros3")]
+
+use hdf5::File;
+
+const URL: &str = "https://dandiarchive.s3.amazonaws.com/ros3test.hdf5";
+
+// These tests require network access, run them with `--ignored`.
+#[test]
+#[ignore]
+fn test_ros3_read() -> hdf5::Result<()> {
+    let file =
+        File::with_options().with_fapl(|p| p.ros3_options("us-east-2", None, None)).open(URL)?;
+    let ds = file.dataset("mydataset")?;
+    assert_eq!(ds.shape(), vec![100]);
+    assert_eq!(ds.read_raw::<f64>()?.len(), 100);
+
+    let file = File::with_options()
+        .with_fapl(|p| p.ros3_options("us-east-2", None, None))
+        .open("s3://dandiarchive/ros3test.hdf5")?;
+    assert_eq!(file.dataset("mydataset")?.shape(), vec![100]);
+
+    let file = File::open(URL)?;
+    assert_eq!(file.dataset("mydataset")?.shape(), vec![100]);
+    Ok(())
+}
+
+#[test]
+fn test_ros3_read_only() {
+    let err = File::with_options().with_fapl(|p| p.ros3()).create(URL).unwrap_err();
+    assert!(err.to_string().contains("ROS3 driver only supports read-only access"));
+    let err = File::with_options()
+        .with_fapl(|p| p.ros3_options("us-east-2", Some("id"), None))
+        .open(URL)
+        .unwrap_err();
+    assert!(err.to_string().contains("Both AWS access key ID and secret access key"));
+}
@@ -0,0 +1,53 @@
+#![cfg(hdf5_1_10_2)]
+
+use std::sync::mpsc;
+use std::thread;
+
+use ndarray::s;
+use tempdir::TempDir;
+
+use hdf5::file::LibraryVersion;
+use hdf5::File;
+
+#[test]
+fn test_swmr_reader_sees_appended_rows() -> hdf5::Result<()> {
+    let dir = TempDir::new_in(".", "tmp").unwrap();
+    let path = dir.path().join("swmr.h5");
+    let latest = LibraryVersion::latest();
+
+    let file = File::with_options().with_fapl(|p| p.libver_bounds(latest, latest)).create(&path)?;
+    let ds = file.new_dataset::<i32>().chunk((1, 4)).resizable(true).create("data", (0, 4))?;
+    file.start_swmr_write()?;
+
+    let (tx_rows, rx_rows) = mpsc::channel::<usize>();
+    let (tx_ack, rx_ack) = mpsc::channel::<()>();
+
+    let reader_path = path.clone();
+    let reader = thread::spawn(move || -> hdf5::Result<()> {
+        let file = File::with_options().with_swmr_read().open(&reader_path)?;
+        let ds = file.dataset("data")?;
+        tx_ack.send(()).unwrap();
+        for n_rows in rx_rows {
+            ds.refresh()?;
+            assert_eq!(ds.shape(), vec![n_rows, 4]);
+            let data = ds.read_2d::<i32>()?;
+            for (i, row) in data.outer_iter().enumerate() {
+                assert_eq!(row.to_vec(), vec![i as i32; 4]);
+            }
+            tx_ack.send(()).unwrap();
+        }
+        Ok(())
+    });
+
+    rx_ack.recv().unwrap();
+    for i in 0..5 {
+        ds.resize((i + 1, 4))?;
+        ds.write_slice(&[i as i32; 4], s![i, ..])?;
+        ds.flush()?;
+        tx_rows.send(i + 1).unwrap();
+        rx_ack.recv().unwrap();
+    }
+    drop(tx_rows);
+
+    reader.join().unwrap()
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "tokio")]
+
+use ndarray::{Array2, ArrayD};
+
+mod common;
+
+use self::common::util::new_in_memory_file;
+
+fn assert_send<T: Send>(value: T) -> T {
+    value
+}
+
+#[test]
+fn test_read_write_async() -> hdf5::Result<()> {
+    let mut rt = tokio::runtime::Builder::new().basic_scheduler().build().unwrap();
+    let file = new_in_memory_file()?;
+    let ds = file.new_dataset::<u32>().create("data", (3, 4))?;
+    let arr = Array2::from_shape_fn((3, 4), |(i, j)| (i * 10 + j) as u32);
+
+    rt.block_on(assert_send(ds.write_async(arr.clone())))?;
+    assert_eq!(ds.read_2d::<u32>()?, arr);
+
+    let read = rt.block_on(assert_send(ds.read_async::<u32, ndarray::Ix2>()))?;
+    assert_eq!(read, ds.read_2d::<u32>()?);
+    let read = rt.block_on(ds.read_async::<u32, ndarray::IxDyn>())?;
+    assert_eq!(read, ds.read_dyn::<u32>()?);
+    assert_eq!(rt.block_on(ds.read_raw_async::<u32>())?, ds.read_raw::<u32>()?);
+
+    let err = rt.block_on(ds.write_async(ArrayD::<u32>::zeros(vec![2]))).unwrap_err();
+    assert!(err.to_string().contains("shape mismatch"));
+    Ok(())
+}